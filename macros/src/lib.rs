@@ -0,0 +1,74 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! Expands JSON schemas into rust types at compile time, without a build script.
+
+use std::path::{Path, PathBuf};
+
+use jsonschema_code_generator::{Generator, SourceStyle};
+use proc_macro::{Literal, TokenStream, TokenTree};
+
+/// Expands to the types of the schema, whose path is relative to the directory of the
+/// `Cargo.toml` of the crate, e.g. `json_schema!("schemas/user.schema.json")`.
+///
+/// The crate is rebuilt when the schema or a file it refers to changes.
+#[proc_macro]
+pub fn json_schema(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(tokens) => tokens,
+        Err(message) => format!("compile_error!({});", Literal::string(&message))
+            .parse()
+            .unwrap(),
+    }
+}
+
+fn expand(input: TokenStream) -> Result<TokenStream, String> {
+    let path = parse_path(input)?;
+    let manifest_dir = PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default());
+    let mut generator = Generator::new();
+    generator.set_source_style(SourceStyle::RelativeTo(manifest_dir.clone()));
+    generator
+        .try_add_file(&manifest_dir.join(path))
+        .map_err(|error| error.to_string())?;
+
+    let code = generator
+        .generate_result()
+        .map_err(|error| error.to_string())?;
+
+    // Including the files is how rustc learns to rebuild the crate when they change
+    let tracked: String = generator
+        .files()
+        .iter()
+        .map(|file| tracking_constant(file))
+        .collect();
+
+    format!("{}{}", tracked, code)
+        .parse()
+        .map_err(|error| format!("{:?}", error))
+}
+
+/// The path of a string literal, which may be a raw string
+fn parse_path(input: TokenStream) -> Result<String, String> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let literal = match &tokens[..] {
+        [TokenTree::Literal(literal)] => literal.to_string(),
+        _ => {
+            return Err(String::from(
+                "expected the path of a schema, like \"user.json\"",
+            ))
+        }
+    };
+    let raw = literal.trim_start_matches('r').trim_matches('#');
+
+    match raw.strip_prefix('"').and_then(|x| x.strip_suffix('"')) {
+        Some(path) if literal.starts_with('r') => Ok(path.to_string()),
+        Some(path) => Ok(path.replace("\\\\", "\\").replace("\\\"", "\"")),
+        None => Err(format!("expected a string literal, found {}", literal)),
+    }
+}
+
+fn tracking_constant(file: &Path) -> String {
+    let file = Literal::string(&file.display().to_string());
+    format!("const _: &str = include_str!({});", file)
+}
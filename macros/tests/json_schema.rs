@@ -0,0 +1,45 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+mod across_files {
+    jsonschema_code_generator_macros::json_schema!("../src/examples/generator/loop1.schema.json");
+}
+
+mod inline {
+    jsonschema_code_generator_macros::json_schema!(r"../src/examples/generator/alias.schema.json");
+}
+
+mod reserved {
+    jsonschema_code_generator_macros::json_schema!(
+        "../src/examples/generator/reserved.names.schema.json"
+    );
+}
+
+#[test]
+fn should_expand_the_types_of_referenced_files() {
+    let value: across_files::Loop = serde_json::from_str(r#"{"a": {"c": {"b": {}}}}"#).unwrap();
+
+    assert!(value.a.unwrap().c.unwrap().b.is_some());
+}
+
+#[test]
+fn should_expand_raw_string_paths() {
+    let user: inline::User = serde_json::from_str(r#"{"id": "42", "tags": ["a"]}"#).unwrap();
+
+    assert_eq!(user.id, "42");
+    assert_eq!(user.tags, Some(vec![String::from("a")]));
+}
+
+#[test]
+fn should_not_shadow_the_imports_with_type_names() {
+    let setting: reserved::Setting =
+        serde_json::from_str(r#"{"value": 3, "result": {"ok": true}, "extra": {"len": 2}}"#)
+            .unwrap();
+
+    assert!(matches!(setting.value, reserved::Value1::Integer(3)));
+    assert!(matches!(
+        setting.extra,
+        Some(reserved::Extra::Vec1(reserved::Vec1 { len: 2 }))
+    ));
+}
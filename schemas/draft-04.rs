@@ -1,92 +1,494 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::BTreeMap;
+#[doc = "Core schema meta-schema"]
+#[doc = ""]
 #[doc = "Generated from schemas/draft-04.json"]
 #[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
 pub struct Unknown {
+    #[doc(alias = "$schema")]
     #[serde(rename = "$schema")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dollar_schema: Option<String>,
+    #[doc(alias = "additionalItems")]
     #[serde(rename = "additionalItems")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub additional_items: Option<Value>,
+    #[serde(default = "Unknown::default_additional_items")]
+    pub additional_items: Box<AdditionalItems>,
+    #[doc(alias = "additionalProperties")]
     #[serde(rename = "additionalProperties")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub additional_properties: Option<Value>,
+    #[serde(default = "Unknown::default_additional_properties")]
+    pub additional_properties: AdditionalProperties,
+    #[doc(alias = "allOf")]
     #[serde(rename = "allOf")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub all_of: Option<Vec<Unknown>>,
+    #[doc(alias = "anyOf")]
     #[serde(rename = "anyOf")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub any_of: Option<Vec<Unknown>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default: Option<Value>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub definitions: Option<BTreeMap<String, Value>>,
+    #[serde(default)]
+    pub definitions: BTreeMap<String, Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dependencies: Option<BTreeMap<String, Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[doc(alias = "enum")]
     #[serde(rename = "enum")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub enum_: Option<Vec<Value>>,
+    #[doc = "Requires `maximum`"]
+    #[doc(alias = "exclusiveMaximum")]
     #[serde(rename = "exclusiveMaximum")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub exclusive_maximum: Option<bool>,
+    #[serde(default)]
+    pub exclusive_maximum: bool,
+    #[doc = "Requires `minimum`"]
+    #[doc(alias = "exclusiveMinimum")]
     #[serde(rename = "exclusiveMinimum")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub exclusive_minimum: Option<bool>,
+    #[serde(default)]
+    pub exclusive_minimum: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub format: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub items: Option<Value>,
+    #[serde(default = "Unknown::default_items")]
+    pub items: Items,
+    #[doc(alias = "maxItems")]
     #[serde(rename = "maxItems")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub max_items: Option<i64>,
+    pub max_items: Option<u64>,
+    #[doc(alias = "maxLength")]
     #[serde(rename = "maxLength")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub max_length: Option<i64>,
+    pub max_length: Option<u64>,
+    #[doc(alias = "maxProperties")]
     #[serde(rename = "maxProperties")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub max_properties: Option<i64>,
+    pub max_properties: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub maximum: Option<f64>,
+    #[doc(alias = "minItems")]
     #[serde(rename = "minItems")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub min_items: Option<Value>,
+    #[doc(alias = "minLength")]
     #[serde(rename = "minLength")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub min_length: Option<Value>,
+    #[doc(alias = "minProperties")]
     #[serde(rename = "minProperties")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub min_properties: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub minimum: Option<f64>,
+    #[doc(alias = "multipleOf")]
     #[serde(rename = "multipleOf")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub multiple_of: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub not: Option<Box<Unknown>>,
+    #[doc(alias = "oneOf")]
     #[serde(rename = "oneOf")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub one_of: Option<Vec<Unknown>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pattern: Option<String>,
+    #[doc(alias = "patternProperties")]
     #[serde(rename = "patternProperties")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub pattern_properties: Option<BTreeMap<String, Value>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub properties: Option<BTreeMap<String, Value>>,
+    #[serde(default)]
+    pub pattern_properties: BTreeMap<String, Value>,
+    #[serde(default)]
+    pub properties: BTreeMap<String, Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
+    #[doc(alias = "type")]
     #[serde(rename = "type")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub type_: Option<Value>,
+    pub type_: Option<Type>,
+    #[doc(alias = "uniqueItems")]
     #[serde(rename = "uniqueItems")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub unique_items: Option<bool>,
+    #[serde(default)]
+    pub unique_items: bool,
+}
+impl Unknown {
+    pub fn default_additional_items() -> Box<AdditionalItems> {
+        serde_json::from_str("{}").unwrap()
+    }
+    pub fn default_additional_properties() -> AdditionalProperties {
+        serde_json::from_str("{}").unwrap()
+    }
+    pub fn default_items() -> Items {
+        serde_json::from_str("{}").unwrap()
+    }
+}
+impl Default for Unknown {
+    fn default() -> Self {
+        Unknown {
+            dollar_schema: None,
+            additional_items: Unknown::default_additional_items(),
+            additional_properties: Unknown::default_additional_properties(),
+            all_of: None,
+            any_of: None,
+            default: None,
+            definitions: Default::default(),
+            dependencies: None,
+            description: None,
+            enum_: None,
+            exclusive_maximum: Default::default(),
+            exclusive_minimum: Default::default(),
+            format: None,
+            id: None,
+            items: Unknown::default_items(),
+            max_items: None,
+            max_length: None,
+            max_properties: None,
+            maximum: None,
+            min_items: None,
+            min_length: None,
+            min_properties: None,
+            minimum: None,
+            multiple_of: None,
+            not: None,
+            one_of: None,
+            pattern: None,
+            pattern_properties: Default::default(),
+            properties: Default::default(),
+            required: None,
+            title: None,
+            type_: None,
+            unique_items: Default::default(),
+        }
+    }
+}
+#[doc = "Generated from schemas/draft-04.json#/properties/additionalItems"]
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum AdditionalItems {
+    Bool(bool),
+    Unknown(Box<Unknown>),
+}
+#[doc = "Generated from schemas/draft-04.json#/properties/additionalProperties"]
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum AdditionalProperties {
+    Bool(bool),
+    Unknown(Box<Unknown>),
+}
+#[doc = "Generated from schemas/draft-04.json#/properties/items"]
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Items {
+    Unknown(Box<Unknown>),
+    Array(Vec<Unknown>),
+}
+#[doc = "Generated from schemas/draft-04.json#/properties/type"]
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Type {
+    Any(Value),
+    Array(Vec<Value>),
+}
+impl AdditionalItems {
+    pub fn as_bool(&self) -> Option<&bool> {
+        match self {
+            AdditionalItems::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+    pub fn into_bool(self) -> Option<bool> {
+        match self {
+            AdditionalItems::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+    pub fn as_unknown(&self) -> Option<&Unknown> {
+        match self {
+            AdditionalItems::Unknown(value) => Some(&**value),
+            _ => None,
+        }
+    }
+    pub fn into_unknown(self) -> Option<Unknown> {
+        match self {
+            AdditionalItems::Unknown(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+impl From<bool> for AdditionalItems {
+    fn from(value: bool) -> Self {
+        AdditionalItems::Bool(value)
+    }
+}
+impl From<Unknown> for AdditionalItems {
+    fn from(value: Unknown) -> Self {
+        AdditionalItems::Unknown(Box::new(value))
+    }
+}
+impl AdditionalProperties {
+    pub fn as_bool(&self) -> Option<&bool> {
+        match self {
+            AdditionalProperties::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+    pub fn into_bool(self) -> Option<bool> {
+        match self {
+            AdditionalProperties::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+    pub fn as_unknown(&self) -> Option<&Unknown> {
+        match self {
+            AdditionalProperties::Unknown(value) => Some(&**value),
+            _ => None,
+        }
+    }
+    pub fn into_unknown(self) -> Option<Unknown> {
+        match self {
+            AdditionalProperties::Unknown(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+impl From<bool> for AdditionalProperties {
+    fn from(value: bool) -> Self {
+        AdditionalProperties::Bool(value)
+    }
+}
+impl From<Unknown> for AdditionalProperties {
+    fn from(value: Unknown) -> Self {
+        AdditionalProperties::Unknown(Box::new(value))
+    }
+}
+impl Items {
+    pub fn as_unknown(&self) -> Option<&Unknown> {
+        match self {
+            Items::Unknown(value) => Some(&**value),
+            _ => None,
+        }
+    }
+    pub fn into_unknown(self) -> Option<Unknown> {
+        match self {
+            Items::Unknown(value) => Some(*value),
+            _ => None,
+        }
+    }
+    pub fn as_array(&self) -> Option<&Vec<Unknown>> {
+        match self {
+            Items::Array(value) => Some(value),
+            _ => None,
+        }
+    }
+    pub fn into_array(self) -> Option<Vec<Unknown>> {
+        match self {
+            Items::Array(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+impl From<Unknown> for Items {
+    fn from(value: Unknown) -> Self {
+        Items::Unknown(Box::new(value))
+    }
+}
+impl From<Vec<Unknown>> for Items {
+    fn from(value: Vec<Unknown>) -> Self {
+        Items::Array(value)
+    }
+}
+impl Type {
+    pub fn as_any(&self) -> Option<&Value> {
+        match self {
+            Type::Any(value) => Some(value),
+            _ => None,
+        }
+    }
+    pub fn into_any(self) -> Option<Value> {
+        match self {
+            Type::Any(value) => Some(value),
+            _ => None,
+        }
+    }
+    pub fn as_array(&self) -> Option<&Vec<Value>> {
+        match self {
+            Type::Array(value) => Some(value),
+            _ => None,
+        }
+    }
+    pub fn into_array(self) -> Option<Vec<Value>> {
+        match self {
+            Type::Array(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+impl From<Value> for Type {
+    fn from(value: Value) -> Self {
+        Type::Any(value)
+    }
+}
+impl From<Vec<Value>> for Type {
+    fn from(value: Vec<Value>) -> Self {
+        Type::Array(value)
+    }
+}
+#[doc = r"A value which violates a constraint of its schema"]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ValidationError {
+    #[doc = r"JSON pointer to the invalid value, empty if it is the validated value itself"]
+    pub path: String,
+    pub message: String,
+}
+impl ValidationError {
+    pub fn new(message: impl Into<String>) -> Self {
+        ValidationError {
+            path: String::new(),
+            message: message.into(),
+        }
+    }
+    #[doc = r"The error of a value nested in the validated one under the key or index"]
+    pub fn at(mut self, segment: impl std::fmt::Display) -> Self {
+        let segment = segment.to_string().replace('~', "~0").replace('/', "~1");
+        self.path = format!("/{}{}", segment, self.path);
+        self
+    }
+}
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.path.is_empty() {
+            true => f.write_str(&self.message),
+            false => write!(f, "{} at {}", self.message, self.path),
+        }
+    }
+}
+impl std::error::Error for ValidationError {}
+impl From<String> for ValidationError {
+    fn from(message: String) -> Self {
+        ValidationError::new(message)
+    }
+}
+impl Unknown {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(value) = &self.enum_ {
+            if (value.len() as u64) < 1 {
+                return Err(ValidationError::new(format!(
+                    "has {} items, fewer than {}",
+                    value.len(),
+                    1
+                ))
+                .at("enum"));
+            }
+        }
+        if let Some(value) = &self.multiple_of {
+            if *value <= 0.0 {
+                return Err(ValidationError::new(format!(
+                    "is {}, not greater than {}",
+                    value, 0.0
+                ))
+                .at("multipleOf"));
+            }
+        }
+        {
+            let value = &self.additional_items;
+            let value = &**value;
+            value
+                .validate()
+                .map_err(|error| error.at("additionalItems"))?;
+        }
+        {
+            let value = &self.additional_properties;
+            value
+                .validate()
+                .map_err(|error| error.at("additionalProperties"))?;
+        }
+        {
+            let value = &self.all_of;
+            if let Some(value) = value {
+                for (index, value) in value.iter().enumerate() {
+                    value
+                        .validate()
+                        .map_err(|error| error.at(index).at("allOf"))?;
+                }
+            }
+        }
+        {
+            let value = &self.any_of;
+            if let Some(value) = value {
+                for (index, value) in value.iter().enumerate() {
+                    value
+                        .validate()
+                        .map_err(|error| error.at(index).at("anyOf"))?;
+                }
+            }
+        }
+        {
+            let value = &self.items;
+            value.validate().map_err(|error| error.at("items"))?;
+        }
+        {
+            let value = &self.not;
+            if let Some(value) = value {
+                let value = &**value;
+                value.validate().map_err(|error| error.at("not"))?;
+            }
+        }
+        {
+            let value = &self.one_of;
+            if let Some(value) = value {
+                for (index, value) in value.iter().enumerate() {
+                    value
+                        .validate()
+                        .map_err(|error| error.at(index).at("oneOf"))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+impl AdditionalItems {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        #[allow(unreachable_patterns)]
+        match self {
+            AdditionalItems::Unknown(value) => {
+                let value = &**value;
+                value.validate()?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+impl AdditionalProperties {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        #[allow(unreachable_patterns)]
+        match self {
+            AdditionalProperties::Unknown(value) => {
+                let value = &**value;
+                value.validate()?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+impl Items {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        #[allow(unreachable_patterns)]
+        match self {
+            Items::Unknown(value) => {
+                let value = &**value;
+                value.validate()?;
+            }
+            Items::Array(value) => {
+                for (index, value) in value.iter().enumerate() {
+                    value.validate().map_err(|error| error.at(index))?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
 }
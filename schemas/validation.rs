@@ -0,0 +1,385 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+#[doc = "Generated from schemas/validation.json"]
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Order {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub child: Option<Child>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grid: Option<Vec<Vec<i64>>>,
+    #[doc = "At most 2 entries"]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<BTreeMap<LabelsKey, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+#[doc = "Generated from schemas/validation.json#/definitions/child"]
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Child {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<i64>,
+}
+#[doc = "Generated from schemas/validation.json#/properties/labels/propertyNames"]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct LabelsKey(String);
+impl LabelsKey {
+    pub const PATTERN: &'static str = "^x-";
+    pub fn new(value: String) -> Result<Self, String> {
+        static REGEX: std::sync::OnceLock<Result<regex::Regex, String>> =
+            std::sync::OnceLock::new();
+        match REGEX.get_or_init(|| regex::Regex::new(Self::PATTERN).map_err(|e| e.to_string())) {
+            Ok(regex) if regex.is_match(&value) => Ok(LabelsKey(value)),
+            Ok(_) => Err(format!(
+                "{} '{}' does not match {}",
+                "LabelsKey",
+                value,
+                Self::PATTERN
+            )),
+            Err(error) => Err(error.clone()),
+        }
+    }
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+impl std::fmt::Display for LabelsKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+impl std::convert::TryFrom<String> for LabelsKey {
+    type Error = String;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        LabelsKey::new(value)
+    }
+}
+impl From<LabelsKey> for String {
+    fn from(value: LabelsKey) -> Self {
+        value.0
+    }
+}
+#[doc = r"A value which violates a constraint of its schema"]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ValidationError {
+    #[doc = r"JSON pointer to the invalid value, empty if it is the validated value itself"]
+    pub path: String,
+    pub message: String,
+}
+impl ValidationError {
+    pub fn new(message: impl Into<String>) -> Self {
+        ValidationError {
+            path: String::new(),
+            message: message.into(),
+        }
+    }
+    #[doc = r"The error of a value nested in the validated one under the key or index"]
+    pub fn at(mut self, segment: impl std::fmt::Display) -> Self {
+        let segment = segment.to_string().replace('~', "~0").replace('/', "~1");
+        self.path = format!("/{}{}", segment, self.path);
+        self
+    }
+}
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.path.is_empty() {
+            true => f.write_str(&self.message),
+            false => write!(f, "{} at {}", self.message, self.path),
+        }
+    }
+}
+impl std::error::Error for ValidationError {}
+impl From<String> for ValidationError {
+    fn from(message: String) -> Self {
+        ValidationError::new(message)
+    }
+}
+impl Order {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(value) = &self.count {
+            if *value < 5 {
+                return Err(
+                    ValidationError::new(format!("is {}, less than {}", value, 5)).at("count"),
+                );
+            }
+        }
+        if let Some(value) = &self.grid {
+            for (index, value) in value.iter().enumerate() {
+                for (index1, value) in value.iter().enumerate() {
+                    if *value > 9 {
+                        return Err(ValidationError::new(format!(
+                            "is {}, greater than {}",
+                            value, 9
+                        ))
+                        .at(index1)
+                        .at(index)
+                        .at("grid"));
+                    }
+                }
+            }
+        }
+        if let Some(value) = &self.labels {
+            if value.len() as u64 > 2 {
+                return Err(ValidationError::new(format!(
+                    "has {} entries, more than {}",
+                    value.len(),
+                    2
+                ))
+                .at("labels"));
+            }
+        }
+        if let Some(value) = &self.tags {
+            for (index, value) in value.iter().enumerate() {
+                {
+                    static PATTERN: std::sync::OnceLock<Result<regex::Regex, String>> =
+                        std::sync::OnceLock::new();
+                    let pattern = PATTERN
+                        .get_or_init(|| regex::Regex::new("^[a-z]+$").map_err(|e| e.to_string()))
+                        .as_ref()
+                        .map_err(|e| e.clone())?;
+                    if !pattern.is_match(value) {
+                        return Err(ValidationError::new(format!(
+                            "'{}' does not match {}",
+                            value, "^[a-z]+$"
+                        ))
+                        .at(index)
+                        .at("tags"));
+                    }
+                }
+            }
+        }
+        {
+            let value = &self.child;
+            if let Some(value) = value {
+                value.validate().map_err(|error| error.at("child"))?;
+            }
+        }
+        Ok(())
+    }
+}
+impl Child {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(value) = &self.x {
+            if *value > 3 {
+                return Err(
+                    ValidationError::new(format!("is {}, greater than {}", value, 3)).at("x"),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+mod regex {
+    const DIGITS: &[(char, char)] = &[('0', '9')];
+    const WORD: &[(char, char)] = &[('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')];
+    const SPACE: &[(char, char)] = &[(' ', ' '), ('\t', '\r')];
+    enum Node {
+        Char(char),
+        Any,
+        Class(Vec<(char, char)>, bool),
+        Start,
+        End,
+        Group(Vec<Vec<Node>>),
+        Repeat(Box<Node>, usize, Option<usize>),
+    }
+    pub struct Regex(Node);
+    impl Regex {
+        pub fn new(pattern: &str) -> Result<Regex, String> {
+            let chars: Vec<char> = pattern.chars().collect();
+            let mut pos = 0;
+            let node = parse_alternatives(&chars, &mut pos)?;
+            match pos == chars.len() {
+                true => Ok(Regex(node)),
+                false => Err(format!("unmatched ) in the pattern {}", pattern)),
+            }
+        }
+        pub fn is_match(&self, text: &str) -> bool {
+            let chars: Vec<char> = text.chars().collect();
+            (0..=chars.len()).any(|start| match_node(&self.0, &chars, start, &mut |_| true))
+        }
+    }
+    fn parse_alternatives(chars: &[char], pos: &mut usize) -> Result<Node, String> {
+        let mut alternatives = vec![Vec::new()];
+        while *pos < chars.len() && chars[*pos] != ')' {
+            let c = chars[*pos];
+            *pos += 1;
+            let atom = match c {
+                '|' => {
+                    alternatives.push(Vec::new());
+                    continue;
+                }
+                '^' => Node::Start,
+                '$' => Node::End,
+                '.' => Node::Any,
+                '(' => {
+                    if chars[*pos..].starts_with(&['?', ':']) {
+                        *pos += 2;
+                    }
+                    let group = parse_alternatives(chars, pos)?;
+                    if *pos == chars.len() {
+                        return Err(String::from("unclosed ( in the pattern"));
+                    }
+                    *pos += 1;
+                    group
+                }
+                '[' => parse_class(chars, pos)?,
+                '\\' => parse_escape(chars, pos)?,
+                '*' | '+' | '?' | '{' => return Err(format!("nothing to repeat with {}", c)),
+                c => Node::Char(c),
+            };
+            let atom = parse_quantifier(chars, pos, atom)?;
+            alternatives.last_mut().unwrap().push(atom);
+        }
+        Ok(Node::Group(alternatives))
+    }
+    fn parse_quantifier(chars: &[char], pos: &mut usize, atom: Node) -> Result<Node, String> {
+        let number = |text: &str| {
+            text.trim()
+                .parse::<usize>()
+                .map_err(|_| format!("invalid repetition {}", text))
+        };
+        let (min, max) = match chars.get(*pos) {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => {
+                let end = *pos
+                    + chars[*pos..]
+                        .iter()
+                        .position(|x| *x == '}')
+                        .ok_or("unclosed { in the pattern")?;
+                let bounds: String = chars[*pos + 1..end].iter().collect();
+                *pos = end;
+                match bounds.split_once(',') {
+                    None => (number(&bounds)?, Some(number(&bounds)?)),
+                    Some((min, "")) => (number(min)?, None),
+                    Some((min, max)) => (number(min)?, Some(number(max)?)),
+                }
+            }
+            _ => return Ok(atom),
+        };
+        *pos += 1;
+        if chars.get(*pos) == Some(&'?') {
+            *pos += 1;
+        }
+        Ok(Node::Repeat(Box::new(atom), min, max))
+    }
+    fn parse_escape(chars: &[char], pos: &mut usize) -> Result<Node, String> {
+        let c = *chars.get(*pos).ok_or("trailing \\ in the pattern")?;
+        *pos += 1;
+        Ok(match c {
+            'd' => Node::Class(DIGITS.to_vec(), false),
+            'D' => Node::Class(DIGITS.to_vec(), true),
+            'w' => Node::Class(WORD.to_vec(), false),
+            'W' => Node::Class(WORD.to_vec(), true),
+            's' => Node::Class(SPACE.to_vec(), false),
+            'S' => Node::Class(SPACE.to_vec(), true),
+            'n' => Node::Char('\n'),
+            't' => Node::Char('\t'),
+            'r' => Node::Char('\r'),
+            'f' => Node::Char('\x0C'),
+            'v' => Node::Char('\x0B'),
+            c if c.is_ascii_alphanumeric() => return Err(format!("unsupported escape \\{}", c)),
+            c => Node::Char(c),
+        })
+    }
+    fn parse_class(chars: &[char], pos: &mut usize) -> Result<Node, String> {
+        let negated = chars.get(*pos) == Some(&'^');
+        if negated {
+            *pos += 1;
+        }
+        let mut ranges = Vec::new();
+        loop {
+            let start = match parse_class_member(chars, pos)? {
+                None => return Ok(Node::Class(ranges, negated)),
+                Some(Node::Char(c)) => c,
+                Some(Node::Class(mut members, false)) => {
+                    ranges.append(&mut members);
+                    continue;
+                }
+                Some(_) => return Err(String::from("unsupported negated class in a class")),
+            };
+            let end = match (chars.get(*pos), chars.get(*pos + 1)) {
+                (Some('-'), Some(x)) if *x != ']' => {
+                    *pos += 1;
+                    match parse_class_member(chars, pos)? {
+                        Some(Node::Char(c)) => c,
+                        _ => return Err(String::from("invalid range in a class")),
+                    }
+                }
+                _ => start,
+            };
+            ranges.push((start, end));
+        }
+    }
+    fn parse_class_member(chars: &[char], pos: &mut usize) -> Result<Option<Node>, String> {
+        let c = *chars.get(*pos).ok_or("unclosed [ in the pattern")?;
+        *pos += 1;
+        match c {
+            ']' => Ok(None),
+            '\\' => parse_escape(chars, pos).map(Some),
+            c => Ok(Some(Node::Char(c))),
+        }
+    }
+    fn match_node(
+        node: &Node,
+        text: &[char],
+        pos: usize,
+        next: &mut dyn FnMut(usize) -> bool,
+    ) -> bool {
+        let c = text.get(pos).copied();
+        match node {
+            Node::Char(x) => c == Some(*x) && next(pos + 1),
+            Node::Any => c.is_some_and(|c| c != '\n') && next(pos + 1),
+            Node::Class(ranges, negated) => {
+                c.is_some_and(|c| {
+                    ranges.iter().any(|(start, end)| *start <= c && c <= *end) != *negated
+                }) && next(pos + 1)
+            }
+            Node::Start => pos == 0 && next(pos),
+            Node::End => pos == text.len() && next(pos),
+            Node::Group(alternatives) => alternatives
+                .iter()
+                .any(|x| match_sequence(x, text, pos, next)),
+            Node::Repeat(inner, min, max) => match_repeat(inner, *min, *max, text, pos, next),
+        }
+    }
+    fn match_sequence(
+        nodes: &[Node],
+        text: &[char],
+        pos: usize,
+        next: &mut dyn FnMut(usize) -> bool,
+    ) -> bool {
+        match nodes.split_first() {
+            None => next(pos),
+            Some((first, rest)) => match_node(first, text, pos, &mut |pos| {
+                match_sequence(rest, text, pos, next)
+            }),
+        }
+    }
+    fn match_repeat(
+        inner: &Node,
+        min: usize,
+        max: Option<usize>,
+        text: &[char],
+        pos: usize,
+        next: &mut dyn FnMut(usize) -> bool,
+    ) -> bool {
+        let more = max != Some(0)
+            && match_node(inner, text, pos, &mut |end| {
+                (end != pos || min > 0)
+                    && match_repeat(
+                        inner,
+                        min.saturating_sub(1),
+                        max.map(|x| x - 1),
+                        text,
+                        end,
+                        next,
+                    )
+            });
+        more || (min == 0 && next(pos))
+    }
+}
@@ -0,0 +1,297 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+
+use jsonschema_code_generator::{
+    scaffold_build_script, scaffold_next_steps, Generator, MapType, ScalarDefinitionStyle, SetType,
+    Visibility,
+};
+
+const USAGE: &str = "usage: jsonschema-codegen generate [--output <file> | --out-dir <dir>] [<option>...] <schema>...
+       jsonschema-codegen init [--schemas <dir>] [<project dir>]
+       jsonschema-codegen extract [--output <file>] [--rust <file>] <schema> <pointer>
+       jsonschema-codegen report <schema>...";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match args.first().map(|x| x.as_str()) {
+        Some("generate") => generate(&args[1..]),
+        Some("init") => init(&args[1..]),
+        Some("extract") => extract(&args[1..]),
+        Some("report") => report(&args[1..]),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    }
+}
+
+/// Writes the formatted types of the schemas to the file, the directory or stdout
+fn generate(args: &[String]) {
+    let mut generator = Generator::new();
+    let mut output = None;
+    let mut out_dir = None;
+    let mut schemas = Vec::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().unwrap_or_else(|| usage());
+
+        match arg.as_str() {
+            "--output" => output = Some(PathBuf::from(value())),
+            "--out-dir" => out_dir = Some(PathBuf::from(value())),
+            "--map-type" => generator.set_map_type(match value().as_str() {
+                "btree" => MapType::BTreeMap,
+                "hash" => MapType::HashMap,
+                #[cfg(feature = "indexmap")]
+                "index" => MapType::IndexMap,
+                _ => usage(),
+            }),
+            "--set-type" => generator.set_unique_items_type(match value().as_str() {
+                "vec" => SetType::Vec,
+                "btree" => SetType::BTreeSet,
+                "hash" => SetType::HashSet,
+                _ => usage(),
+            }),
+            "--scalar-definitions" => {
+                generator.set_scalar_definition_style(match value().as_str() {
+                    "inline" => ScalarDefinitionStyle::Inline,
+                    "alias" => ScalarDefinitionStyle::Alias,
+                    "newtype" => ScalarDefinitionStyle::Newtype,
+                    _ => usage(),
+                })
+            }
+            "--visibility" => generator.set_visibility(match value().as_str() {
+                "pub" => Visibility::Public,
+                "crate" => Visibility::Crate,
+                _ => usage(),
+            }),
+            "--derive" => generator.add_derives([value()]),
+            "--remove-derive" => generator.remove_derives([value()]),
+            "--builders" => match value().parse() {
+                Ok(min_fields) => generator.set_builder_threshold(Some(min_fields)),
+                Err(_) => usage(),
+            },
+            "--any-type" => generator.set_any_type(&value()),
+            "--header" => generator.set_header(&value()),
+            "--format" => match value().split_once('=') {
+                Some((format, type_path)) => generator.set_format_type(format, type_path),
+                None => usage(),
+            },
+            "--type-name" => match value().split_once('=') {
+                Some((src, name)) => generator.set_type_name(src, name),
+                None => usage(),
+            },
+            "--string-enums" => generator.set_string_enums(true),
+            "--serialize-none" => generator.set_serialize_none(true),
+            "--allow-unknown-fields" => generator.set_allow_unknown_fields(true),
+            "--standalone" => generator.set_standalone(true),
+            "--ordering" => generator.set_ordering(true),
+            "--eq-hash" => generator.set_eq_hash(true),
+            "--read-write-only" => generator.set_read_write_only(true),
+            "--constrained-newtypes" => generator.set_constrained_newtypes(true),
+            "--lenient-numbers" => generator.set_lenient_numbers(true),
+            "--empty-strings-as-none" => generator.set_empty_strings_as_none(true),
+            "--collapse-wrappers" => generator.set_collapse_wrappers(true),
+            "--namespace-by-file" => generator.set_namespace_by_file(true),
+            "--modules-by-id" => generator.set_modules_by_id(true),
+            "--modules-by-file" => generator.set_modules_by_file(true),
+            "--merge-identical-types" => generator.set_merge_identical_types(true),
+            "--round-trip-tests" => generator.set_round_trip_tests(true),
+            option if option.starts_with("--") => usage(),
+            schema => schemas.push(PathBuf::from(schema)),
+        }
+    }
+
+    if schemas.is_empty() || (output.is_some() && out_dir.is_some()) {
+        usage();
+    }
+
+    for schema in &schemas {
+        generator
+            .try_add_file(schema)
+            .unwrap_or_else(|error| fail(error));
+    }
+
+    if let Some(dir) = &out_dir {
+        generator
+            .generate_to_dir(dir)
+            .unwrap_or_else(|error| fail(format!("Could not write {}: {}", dir.display(), error)));
+        // rustfmt follows the `mod` declarations of `mod.rs` into the other files
+        rustfmt_file(&dir.join("mod.rs"));
+        return eprintln!("Wrote {}", dir.display());
+    }
+
+    let code = generator
+        .generate_result()
+        .unwrap_or_else(|error| fail(error));
+    let code = rustfmt(code.to_string());
+
+    match &output {
+        Some(path) => write(path, code),
+        None => print!("{}", code),
+    }
+}
+
+/// Falls back to the unformatted code if rustfmt is missing or fails
+fn rustfmt(code: String) -> String {
+    let formatted = Command::new("rustfmt")
+        .args(["--edition", "2018", "--emit", "stdout"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .and_then(|mut rustfmt| {
+            rustfmt.stdin.take().unwrap().write_all(code.as_bytes())?;
+            rustfmt.wait_with_output()
+        });
+
+    match formatted {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout).into(),
+        _ => {
+            eprintln!("warning: could not run rustfmt, the code is not formatted");
+            code
+        }
+    }
+}
+
+fn rustfmt_file(path: &Path) {
+    let status = Command::new("rustfmt")
+        .args(["--edition", "2018"])
+        .arg(path)
+        .status();
+
+    if !status.is_ok_and(|x| x.success()) {
+        eprintln!("warning: could not run rustfmt, the code is not formatted");
+    }
+}
+
+fn init(args: &[String]) {
+    let mut schemas = None;
+    let mut project_dir = PathBuf::from(".");
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--schemas" => match args.next() {
+                Some(dir) => schemas = Some(dir.clone()),
+                None => {
+                    eprintln!("{}", USAGE);
+                    process::exit(2);
+                }
+            },
+            dir => project_dir = PathBuf::from(dir),
+        }
+    }
+
+    let schemas = schemas.unwrap_or_else(|| ask("Directory of the JSON schemas", "schemas"));
+
+    match scaffold_build_script(&project_dir, &schemas) {
+        Ok(scaffold) => {
+            for path in scaffold.written {
+                println!("Wrote {}", path.display());
+            }
+
+            for path in scaffold.kept {
+                println!("Kept the existing {}", path.display());
+            }
+
+            println!();
+            print!("{}", scaffold_next_steps());
+        }
+        Err(error) => {
+            eprintln!("Could not scaffold {}: {}", project_dir.display(), error);
+            process::exit(1);
+        }
+    }
+}
+
+/// Writes the subset of the schema the pointer points to, and optionally its types
+fn extract(args: &[String]) {
+    let mut output = None;
+    let mut rust = None;
+    let mut positional = Vec::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--output" => output = Some(PathBuf::from(args.next().unwrap_or_else(|| usage()))),
+            "--rust" => rust = Some(PathBuf::from(args.next().unwrap_or_else(|| usage()))),
+            value => positional.push(value),
+        }
+    }
+
+    let (schema, pointer) = match positional[..] {
+        [schema, pointer] => (Path::new(schema), pointer),
+        _ => usage(),
+    };
+    let mut generator = Generator::new();
+    let subset = generator
+        .extract_subset(schema, pointer)
+        .unwrap_or_else(|error| fail(error));
+    let json = serde_json::to_string_pretty(&subset).unwrap();
+
+    match &output {
+        Some(path) => write(path, json),
+        None => println!("{}", json),
+    }
+
+    if let Some(path) = &rust {
+        generator
+            .try_add_subset(schema, pointer)
+            .unwrap_or_else(|error| fail(error));
+        write(path, generator.generate().to_string());
+    }
+}
+
+/// Prints the types the schemas would produce as JSON, for editor plugins and review bots
+fn report(args: &[String]) {
+    if args.is_empty() {
+        usage();
+    }
+
+    let mut generator = Generator::new();
+
+    for schema in args {
+        generator
+            .try_add_file(Path::new(schema))
+            .unwrap_or_else(|error| fail(error));
+    }
+
+    println!("{}", generator.report().to_json());
+}
+
+fn write(path: &Path, content: String) {
+    match std::fs::write(path, content) {
+        Ok(()) => eprintln!("Wrote {}", path.display()),
+        Err(error) => fail(format!("Could not write {}: {}", path.display(), error)),
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
+
+fn fail(error: impl std::fmt::Display) -> ! {
+    eprintln!("{}", error);
+    process::exit(1);
+}
+
+/// Falls back to the default when stdin is closed or the answer is empty
+fn ask(question: &str, default: &str) -> String {
+    print!("{} [{}]: ", question, default);
+    let _ = io::stdout().flush();
+
+    let mut answer = String::new();
+    let _ = io::stdin().lock().read_line(&mut answer);
+
+    match answer.trim() {
+        "" => String::from(default),
+        answer => String::from(answer),
+    }
+}
@@ -0,0 +1,224 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use crate::generated::{GeneratedType, TypeKind};
+use serde::Serialize;
+use std::collections::HashMap;
+
+const BORSH_DERIVES: [&str; 2] = ["borsh::BorshSerialize", "borsh::BorshDeserialize"];
+
+/// A construct which does not survive Borsh, bincode or formats like CBOR and
+/// MessagePack when they encode structs as arrays.
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct BinaryDiagnostic {
+    pub name: String,
+    pub src: String,
+    pub message: String,
+}
+
+/// Derives the Borsh traits for every type which doesn't refer to the type of
+/// untyped values, directly or through other generated types.
+pub fn add_borsh_derives(types: &mut [GeneratedType], any_type: &str) {
+    let unrepresentable = unrepresentable_types(types, any_type);
+
+    for generated_type in types.iter_mut() {
+        let derivable = !matches!(generated_type.kind, TypeKind::Alias(_))
+            && !unrepresentable.contains_key(&generated_type.name);
+
+        if derivable {
+            for derive in BORSH_DERIVES.iter() {
+                generated_type.derives.push(derive.to_string());
+            }
+        }
+    }
+}
+
+pub fn binary_diagnostics(types: &[GeneratedType], any_type: &str) -> Vec<BinaryDiagnostic> {
+    let unrepresentable = unrepresentable_types(types, any_type);
+    let mut diagnostics = Vec::new();
+
+    for generated_type in types {
+        let mut report = |message: String| {
+            diagnostics.push(BinaryDiagnostic {
+                name: generated_type.name.clone(),
+                src: generated_type.src.clone(),
+                message,
+            })
+        };
+
+        if let Some(reason) = unrepresentable.get(&generated_type.name) {
+            report(format!(
+                "refers to {}, which only self-describing formats can represent",
+                reason
+            ));
+        }
+
+        match &generated_type.kind {
+            TypeKind::UntaggedEnum(_) => report(String::from(
+                "is an untagged enum, which only self-describing formats can deserialize",
+            )),
+            TypeKind::TaggedEnum { .. } => report(String::from(
+                "is an internally tagged enum, which only self-describing formats can deserialize",
+            )),
+            TypeKind::Struct => {
+                for property in &generated_type.properties {
+                    if property.serde_options.flatten {
+                        report(format!(
+                            "flattens {}, which only self-describing formats can deserialize",
+                            property.name
+                        ));
+                    }
+
+                    if property.serde_options.skip_serializing_if.is_some() {
+                        report(format!(
+                            "skips {} when it is None, which shifts the fields of positional formats",
+                            property.name
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    diagnostics
+}
+
+/// Maps every type which can't derive the Borsh traits to the type it refers to
+/// `any_type` is the path of the type of untyped values, like `serde_json::Value`
+fn unrepresentable_types(types: &[GeneratedType], any_type: &str) -> HashMap<String, String> {
+    let any_name = any_type.rsplit("::").next().unwrap_or(any_type);
+    let mut unrepresentable: HashMap<String, String> = HashMap::new();
+
+    loop {
+        let mut changed = false;
+
+        for generated_type in types {
+            if unrepresentable.contains_key(&generated_type.name) {
+                continue;
+            }
+
+            let referenced = generated_type
+                .referenced_names()
+                .into_iter()
+                .find(|part| *part == any_name || unrepresentable.contains_key(*part));
+
+            if let Some(part) = referenced {
+                let reason = match part == any_name {
+                    true => any_type.to_string(),
+                    false => part.to_string(),
+                };
+                unrepresentable.insert(generated_type.name.clone(), reason);
+                changed = true;
+            }
+        }
+
+        if !changed {
+            return unrepresentable;
+        }
+    }
+}
+
+#[cfg(test)]
+mod binary_tests {
+    use crate::binary::{add_borsh_derives, binary_diagnostics, BinaryDiagnostic};
+    use crate::generated::{GeneratedProperty, GeneratedType, SerdeOptions, TypeKind};
+
+    fn struct_type(name: &str, properties: Vec<(&str, &str)>) -> GeneratedType {
+        GeneratedType {
+            src: format!("#/definitions/{}", name),
+            name: String::from(name),
+            doc_alias: None,
+            description: None,
+            derives: vec![],
+            removed_derives: vec![],
+            kind: TypeKind::Struct,
+            constants: vec![],
+            defaults: vec![],
+            deny_unknown_fields: false,
+            properties: properties
+                .into_iter()
+                .map(|(name, property_type)| GeneratedProperty {
+                    name: String::from(name),
+                    property_type: String::from(property_type),
+                    serde_options: SerdeOptions {
+                        rename: None,
+                        skip_serializing_if: match property_type.starts_with("Option<") {
+                            true => Some(String::from("Option::is_none")),
+                            false => None,
+                        },
+                        default: None,
+                        with: None,
+                        deserialize_with: None,
+                        aliases: vec![],
+                        flatten: false,
+                        skip_serializing: false,
+                        skip_deserializing: false,
+                    },
+                    description: None,
+                    map_constraints: None,
+                    value_constraints: None,
+                    requires: vec![],
+                })
+                .collect(),
+            examples: vec![],
+            module: vec![],
+            required_any: vec![],
+        }
+    }
+
+    #[test]
+    fn should_not_derive_borsh_for_types_reaching_value() {
+        let mut types = vec![
+            struct_type("Outer", vec![("inner", "Vec<Inner>")]),
+            struct_type("Inner", vec![("extra", "BTreeMap<String, Value>")]),
+            struct_type("Point", vec![("x", "f64")]),
+        ];
+
+        add_borsh_derives(&mut types, "serde_json::Value");
+
+        assert_eq!(
+            types
+                .iter()
+                .map(|x| x.derives.len())
+                .collect::<Vec<usize>>(),
+            vec![0, 0, 2]
+        );
+    }
+
+    #[test]
+    fn should_report_constructs_binary_formats_cannot_represent() {
+        let types = vec![
+            struct_type("Outer", vec![("inner", "Inner")]),
+            struct_type("Inner", vec![("extra", "Option<Value>")]),
+        ];
+
+        assert_eq!(
+            binary_diagnostics(&types, "serde_json::Value"),
+            vec![
+                BinaryDiagnostic {
+                    name: String::from("Outer"),
+                    src: String::from("#/definitions/Outer"),
+                    message: String::from(
+                        "refers to Inner, which only self-describing formats can represent"
+                    ),
+                },
+                BinaryDiagnostic {
+                    name: String::from("Inner"),
+                    src: String::from("#/definitions/Inner"),
+                    message: String::from(
+                        "refers to serde_json::Value, which only self-describing formats can represent"
+                    ),
+                },
+                BinaryDiagnostic {
+                    name: String::from("Inner"),
+                    src: String::from("#/definitions/Inner"),
+                    message: String::from(
+                        "skips extra when it is None, which shifts the fields of positional formats"
+                    ),
+                },
+            ]
+        );
+    }
+}
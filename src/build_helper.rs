@@ -0,0 +1,50 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! Generation from build scripts, which only rerun when a schema changed.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::Error;
+use crate::generator::{file_stem, Generator};
+
+/// Writes the types of every schema to `<out_dir>/<name>.rs`, named like the schema file
+/// up to its first dot, and asks cargo to rerun the build script when one of the schemas
+/// or a file they refer to, directly or through other files, changes.
+///
+/// Returns the written files, which [`crate::include_generated!`] includes by name.
+pub fn generate_into(out_dir: &Path, schemas: &[PathBuf]) -> Result<Vec<PathBuf>, Error> {
+    let mut written = Vec::new();
+
+    for schema in schemas {
+        let mut generator = Generator::new();
+        let result = generator.try_add_file(schema);
+
+        // Fixing a broken schema has to rerun the build script as well
+        println!("cargo:rerun-if-changed={}", schema.display());
+        result?;
+
+        for file in generator.files() {
+            if file != *schema {
+                println!("cargo:rerun-if-changed={}", file.display());
+            }
+        }
+
+        let path = out_dir.join(format!("{}.rs", file_stem(schema)));
+        let code = generator.generate_result()?.to_string();
+
+        // An unchanged file keeps its timestamp, so cargo doesn't rebuild the crate including it
+        if fs::read_to_string(&path).ok().as_ref() != Some(&code) {
+            fs::write(&path, code).map_err(|error| Error::Write {
+                file: path.clone(),
+                message: error.to_string(),
+            })?;
+        }
+
+        written.push(path);
+    }
+
+    Ok(written)
+}
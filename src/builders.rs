@@ -0,0 +1,107 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use crate::generated::{GeneratedProperty, GeneratedType, TypeKind};
+use crate::options::MethodAttributes;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+
+/// Emits a `FooBuilder` for every struct with at least `min_fields` fields,
+/// whose `build` method fails if a required field without a default is missing.
+pub fn builders(
+    types: &[GeneratedType],
+    min_fields: usize,
+    vis: &TokenStream,
+    attributes: MethodAttributes,
+) -> Vec<TokenStream> {
+    types
+        .iter()
+        .filter(|x| x.kind == TypeKind::Struct && x.properties.len() >= min_fields)
+        .map(|x| builder(&x.name, &x.properties, vis, attributes))
+        .collect()
+}
+
+fn builder(
+    name: &str,
+    properties: &[GeneratedProperty],
+    vis: &TokenStream,
+    attributes: MethodAttributes,
+) -> TokenStream {
+    let builder = Ident::new(&format!("{}Builder", name), Span::call_site());
+    let name = Ident::new(name, Span::call_site());
+    let method = attributes.method();
+    let mut fields = Vec::new();
+    let mut setters = Vec::new();
+    let mut values = Vec::new();
+
+    for property in properties {
+        let field = Ident::new(&property.name, Span::call_site());
+        // Setters must not shadow the methods of the builder, like `Default::default`
+        let setter = match property.name.as_str() {
+            "build" | "default" | "new" => {
+                Ident::new(&format!("set_{}", property.name), Span::call_site())
+            }
+            _ => field.clone(),
+        };
+        let optional =
+            property.property_type.starts_with("Option<") && property.property_type.ends_with('>');
+        let value_type = match optional {
+            true => &property.property_type["Option<".len()..property.property_type.len() - 1],
+            false => &property.property_type,
+        }
+        .parse::<TokenStream>()
+        .unwrap();
+        let key = property
+            .serde_options
+            .rename
+            .as_deref()
+            .unwrap_or(&property.name);
+        let missing = format!("{} is missing", key);
+
+        fields.push(quote! { #field: Option<#value_type> });
+        setters.push(quote! {
+            #method
+            pub fn #setter(mut self, value: impl Into<#value_type>) -> Self {
+                self.#field = Some(value.into());
+                self
+            }
+        });
+        values.push(match property.serde_options.default.as_deref() {
+            _ if optional => quote! { #field: self.#field },
+            Some(function) if !function.is_empty() => {
+                let function = function.parse::<TokenStream>().unwrap();
+                quote! { #field: self.#field.unwrap_or_else(#function) }
+            }
+            Some(_) => quote! { #field: self.#field.unwrap_or_default() },
+            None if property.serde_options.flatten => {
+                quote! { #field: self.#field.unwrap_or_default() }
+            }
+            None => quote! { #field: self.#field.ok_or(#missing)? },
+        });
+    }
+
+    let conversion = attributes.conversion();
+
+    quote! {
+        #[derive(Default)]
+        #vis struct #builder {
+            #(#fields),*
+        }
+        impl #builder {
+            #(#setters)*
+            #conversion
+            pub fn build(self) -> Result<#name, &'static str> {
+                Ok(#name {
+                    #(#values),*
+                })
+            }
+        }
+        impl #name {
+            #method
+            pub fn builder() -> #builder {
+                <#builder as ::core::default::Default>::default()
+            }
+        }
+    }
+}
@@ -0,0 +1,317 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::generated::GeneratedType;
+
+/// The shape of generated types, which can be saved and compared against a later schema.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Snapshot {
+    pub types: Vec<SnapshotType>,
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct SnapshotType {
+    pub name: String,
+    pub src: String,
+    pub properties: Vec<SnapshotProperty>,
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct SnapshotProperty {
+    /// The name in the JSON document
+    pub name: String,
+    pub property_type: String,
+    /// Whether documents without the property are rejected
+    pub required: bool,
+}
+
+impl From<&[GeneratedType]> for Snapshot {
+    fn from(types: &[GeneratedType]) -> Self {
+        let types = types
+            .iter()
+            .map(|generated_type| SnapshotType {
+                name: generated_type.name.clone(),
+                src: generated_type.src.clone(),
+                properties: generated_type
+                    .properties
+                    .iter()
+                    .map(|property| SnapshotProperty {
+                        name: property
+                            .serde_options
+                            .rename
+                            .clone()
+                            .unwrap_or_else(|| property.name.clone()),
+                        property_type: property.property_type.clone(),
+                        required: property.serde_options.skip_serializing_if.is_none()
+                            && property.serde_options.default.is_none(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Snapshot { types }
+    }
+}
+
+/// Compatibility in the sense of schema registries: backward compatible schemas
+/// read documents written for the previous one, forward compatible schemas write
+/// documents the previous one can read.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Compatibility {
+    Full,
+    Backward,
+    Forward,
+    None,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SchemaChange {
+    pub src: String,
+    pub property: String,
+    pub description: String,
+    pub backward: bool,
+    pub forward: bool,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct CompatibilityReport {
+    pub compatibility: Compatibility,
+    pub changes: Vec<SchemaChange>,
+}
+
+/// Types are matched by their source, so renaming a generated type is not a change.
+pub fn check_compatibility(previous: &Snapshot, current: &Snapshot) -> CompatibilityReport {
+    let previous_srcs = type_srcs(previous);
+    let current_srcs = type_srcs(current);
+    let mut changes = Vec::new();
+
+    for current_type in &current.types {
+        let previous_type = match previous.types.iter().find(|x| x.src == current_type.src) {
+            Some(previous_type) => previous_type,
+            None => continue,
+        };
+
+        let mut change = |property: &str, description: String, backward, forward| {
+            changes.push(SchemaChange {
+                src: current_type.src.clone(),
+                property: property.to_string(),
+                description,
+                backward,
+                forward,
+            })
+        };
+
+        for property in &current_type.properties {
+            match previous_type
+                .properties
+                .iter()
+                .find(|x| x.name == property.name)
+            {
+                None => change(
+                    &property.name,
+                    match property.required {
+                        true => String::from("added required property"),
+                        false => String::from("added optional property"),
+                    },
+                    !property.required,
+                    true,
+                ),
+                Some(previous_property) => {
+                    let previous_type = normalize(&previous_property.property_type, &previous_srcs);
+                    let current_type = normalize(&property.property_type, &current_srcs);
+
+                    if previous_type != current_type {
+                        change(
+                            &property.name,
+                            format!("changed type from {} to {}", previous_type, current_type),
+                            false,
+                            false,
+                        );
+                    } else if previous_property.required != property.required {
+                        change(
+                            &property.name,
+                            match property.required {
+                                true => String::from("made property required"),
+                                false => String::from("made property optional"),
+                            },
+                            !property.required,
+                            property.required,
+                        );
+                    }
+                }
+            }
+        }
+
+        for previous_property in &previous_type.properties {
+            if !current_type
+                .properties
+                .iter()
+                .any(|x| x.name == previous_property.name)
+            {
+                change(
+                    &previous_property.name,
+                    match previous_property.required {
+                        true => String::from("removed required property"),
+                        false => String::from("removed optional property"),
+                    },
+                    true,
+                    !previous_property.required,
+                );
+            }
+        }
+    }
+
+    let backward = changes.iter().all(|x| x.backward);
+    let forward = changes.iter().all(|x| x.forward);
+
+    CompatibilityReport {
+        compatibility: match (backward, forward) {
+            (true, true) => Compatibility::Full,
+            (true, false) => Compatibility::Backward,
+            (false, true) => Compatibility::Forward,
+            (false, false) => Compatibility::None,
+        },
+        changes,
+    }
+}
+
+fn type_srcs(snapshot: &Snapshot) -> HashMap<&str, &str> {
+    snapshot
+        .types
+        .iter()
+        .map(|x| (x.name.as_str(), x.src.as_str()))
+        .collect()
+}
+
+/// Replaces the names of generated types by their sources and drops `Option`,
+/// which only reflects whether the property is required
+fn normalize(property_type: &str, srcs: &HashMap<&str, &str>) -> String {
+    let property_type = match property_type.strip_prefix("Option<") {
+        Some(inner) => inner.strip_suffix('>').unwrap_or(inner),
+        None => property_type,
+    };
+
+    let mut normalized = String::new();
+    let mut word = String::new();
+
+    for c in property_type.chars().chain(std::iter::once(' ')) {
+        if c.is_alphanumeric() || c == '_' {
+            word.push(c);
+            continue;
+        }
+
+        match srcs.get(word.as_str()) {
+            Some(src) => normalized.push_str(&format!("<{}>", src)),
+            None => normalized.push_str(&word),
+        }
+        word.clear();
+
+        if c != ' ' {
+            normalized.push(c);
+        }
+    }
+
+    normalized
+}
+
+#[cfg(test)]
+mod compatibility_tests {
+    use crate::compatibility::{
+        check_compatibility, Compatibility, Snapshot, SnapshotProperty, SnapshotType,
+    };
+
+    type Properties<'a> = Vec<(&'a str, &'a str, bool)>;
+
+    fn snapshot(types: Vec<(&str, &str, Properties)>) -> Snapshot {
+        Snapshot {
+            types: types
+                .into_iter()
+                .map(|(name, src, properties)| SnapshotType {
+                    name: String::from(name),
+                    src: String::from(src),
+                    properties: properties
+                        .into_iter()
+                        .map(|(name, property_type, required)| SnapshotProperty {
+                            name: String::from(name),
+                            property_type: String::from(property_type),
+                            required,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn should_be_fully_compatible_when_adding_optional_properties() {
+        let report = check_compatibility(
+            &snapshot(vec![("User", "user.json", vec![("id", "String", true)])]),
+            &snapshot(vec![(
+                "User",
+                "user.json",
+                vec![("id", "String", true), ("age", "Option<i64>", false)],
+            )]),
+        );
+
+        assert_eq!(report.compatibility, Compatibility::Full);
+        assert_eq!(report.changes[0].description, "added optional property");
+    }
+
+    #[test]
+    fn should_classify_required_changes() {
+        let previous = snapshot(vec![("User", "user.json", vec![("id", "String", true)])]);
+        let added = snapshot(vec![(
+            "User",
+            "user.json",
+            vec![("id", "String", true), ("email", "String", true)],
+        )]);
+        let relaxed = snapshot(vec![(
+            "User",
+            "user.json",
+            vec![("id", "Option<String>", false)],
+        )]);
+
+        assert_eq!(
+            check_compatibility(&previous, &added).compatibility,
+            Compatibility::Forward
+        );
+        assert_eq!(
+            check_compatibility(&previous, &relaxed).compatibility,
+            Compatibility::Backward
+        );
+    }
+
+    #[test]
+    fn should_ignore_renamed_types_and_detect_type_changes() {
+        let previous = snapshot(vec![
+            ("Order", "order.json", vec![("item", "Item", true)]),
+            ("Item", "order.json#/definitions/item", vec![]),
+        ]);
+        let renamed = snapshot(vec![
+            ("Order", "order.json", vec![("item", "LineItem", true)]),
+            ("LineItem", "order.json#/definitions/item", vec![]),
+        ]);
+        let changed = snapshot(vec![(
+            "Order",
+            "order.json",
+            vec![("item", "String", true)],
+        )]);
+
+        assert_eq!(
+            check_compatibility(&previous, &renamed).compatibility,
+            Compatibility::Full
+        );
+
+        let report = check_compatibility(&previous, &changed);
+        assert_eq!(report.compatibility, Compatibility::None);
+        assert_eq!(
+            report.changes[0].description,
+            "changed type from <order.json#/definitions/item> to String"
+        );
+    }
+}
@@ -0,0 +1,205 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use crate::generated::{GeneratedType, GeneratedVariant, TypeKind};
+use crate::options::MethodAttributes;
+use crate::sanitizer::sanitize_property_name;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use std::collections::{HashMap, HashSet};
+
+/// Emits `as_*` and `into_*` accessors for every variant of the untagged enums,
+/// and `From` impls for the variant types which are unique within their enum.
+pub fn variant_conversions(
+    types: &[GeneratedType],
+    attributes: MethodAttributes,
+) -> Vec<TokenStream> {
+    let aliases: HashMap<&str, &str> = types
+        .iter()
+        .filter_map(|x| match &x.kind {
+            TypeKind::Alias(target) => Some((x.name.as_str(), target.as_str())),
+            _ => None,
+        })
+        .collect();
+
+    types
+        .iter()
+        .filter_map(|x| match &x.kind {
+            TypeKind::UntaggedEnum(variants) => {
+                Some(conversions(&x.name, variants, &aliases, attributes))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn conversions(
+    name: &str,
+    variants: &[GeneratedVariant],
+    aliases: &HashMap<&str, &str>,
+    attributes: MethodAttributes,
+) -> TokenStream {
+    let variants: Vec<&GeneratedVariant> =
+        variants.iter().filter(|x| x.variant_type != "()").collect();
+    let targets: Vec<&str> = variants
+        .iter()
+        .map(|x| resolve_alias(unboxed(&x.variant_type), aliases))
+        .collect();
+    let name = Ident::new(name, Span::call_site());
+    let rest = match variants.len() > 1 {
+        true => quote! { _ => None, },
+        false => quote! {},
+    };
+    let method = attributes.method();
+    let conversion = attributes.conversion();
+    let mut method_names = HashSet::new();
+    let mut accessors = Vec::new();
+    let mut from_impls = Vec::new();
+
+    for (variant, target) in variants.iter().zip(&targets) {
+        let ident = Ident::new(&variant.name, Span::call_site());
+        let inner = unboxed(&variant.variant_type)
+            .parse::<TokenStream>()
+            .unwrap();
+        let boxed = variant.variant_type.starts_with("Box<");
+        let method_name = sanitize_property_name(variant.name.clone());
+        let method_name = method_name.trim_end_matches('_');
+
+        if method_names.insert(method_name.to_string()) {
+            let as_method = Ident::new(&format!("as_{}", method_name), Span::call_site());
+            let into_method = Ident::new(&format!("into_{}", method_name), Span::call_site());
+            let (reference, value) = match boxed {
+                true => (quote! { &**value }, quote! { *value }),
+                false => (quote! { value }, quote! { value }),
+            };
+
+            accessors.push(quote! {
+                #method
+                pub fn #as_method(&self) -> Option<&#inner> {
+                    match self {
+                        #name::#ident(value) => Some(#reference),
+                        #rest
+                    }
+                }
+                #method
+                pub fn #into_method(self) -> Option<#inner> {
+                    match self {
+                        #name::#ident(value) => Some(#value),
+                        #rest
+                    }
+                }
+            });
+        }
+
+        // Two variants of the same type, or a variant of the enum itself, would conflict
+        let unique = targets.iter().filter(|x| *x == target).count() == 1;
+
+        if unique && name != *target {
+            let value = match boxed {
+                true => quote! { Box::new(value) },
+                false => quote! { value },
+            };
+
+            from_impls.push(quote! {
+                impl From<#inner> for #name {
+                    #conversion
+                    fn from(value: #inner) -> Self {
+                        #name::#ident(#value)
+                    }
+                }
+            });
+        }
+    }
+
+    quote! {
+        impl #name {
+            #(#accessors)*
+        }
+        #(#from_impls)*
+    }
+}
+
+fn unboxed(type_name: &str) -> &str {
+    type_name
+        .strip_prefix("Box<")
+        .and_then(|x| x.strip_suffix('>'))
+        .unwrap_or(type_name)
+}
+
+fn resolve_alias<'a>(type_name: &'a str, aliases: &HashMap<&str, &'a str>) -> &'a str {
+    let mut type_name = type_name;
+    let mut visited = HashSet::new();
+
+    while let Some(target) = aliases.get(type_name) {
+        if !visited.insert(type_name) {
+            break;
+        }
+
+        type_name = target;
+    }
+
+    type_name
+}
+
+#[cfg(test)]
+mod conversions_tests {
+    use crate::conversions::variant_conversions;
+    use crate::generated::{GeneratedType, GeneratedVariant, TypeKind};
+    use crate::options::MethodAttributes;
+
+    fn generated_type(name: &str, kind: TypeKind) -> GeneratedType {
+        GeneratedType {
+            src: String::new(),
+            name: String::from(name),
+            doc_alias: None,
+            description: None,
+            derives: vec![],
+            removed_derives: vec![],
+            kind,
+            constants: vec![],
+            defaults: vec![],
+            deny_unknown_fields: false,
+            properties: vec![],
+            examples: vec![],
+            module: vec![],
+            required_any: vec![],
+        }
+    }
+
+    fn variant(name: &str, variant_type: &str) -> GeneratedVariant {
+        GeneratedVariant {
+            name: String::from(name),
+            variant_type: String::from(variant_type),
+        }
+    }
+
+    #[test]
+    fn should_only_convert_from_unique_variant_types() {
+        let types = vec![
+            generated_type(
+                "Id",
+                TypeKind::UntaggedEnum(vec![
+                    variant("Integer", "i64"),
+                    variant("PositiveInteger", "PositiveInteger"),
+                    variant("Details", "Box<Details>"),
+                    variant("Id", "Box<Id>"),
+                    variant("Null", "()"),
+                ]),
+            ),
+            generated_type("PositiveInteger", TypeKind::Alias(String::from("i64"))),
+        ];
+
+        let code = variant_conversions(&types, MethodAttributes::default())[0].to_string();
+
+        assert!(code.contains("pub fn as_integer (& self) -> Option < & i64 >"));
+        assert!(code.contains("pub fn into_positive_integer (self) -> Option < PositiveInteger >"));
+        assert!(code.contains("Id :: Details (value) => Some (& * * value)"));
+        assert!(code.contains("impl From < Details > for Id"));
+        assert!(code.contains("Id :: Details (Box :: new (value))"));
+        assert!(!code.contains("From < i64 >"));
+        assert!(!code.contains("From < PositiveInteger >"));
+        assert!(!code.contains("From < Id >"));
+        assert!(!code.contains("as_null"));
+    }
+}
@@ -0,0 +1,216 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::schema::{Schema, Types};
+
+/// A `default` which its own subschema rejects
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct DefaultDiagnostic {
+    pub src: String,
+    pub message: String,
+}
+
+/// Checks the `default` of the schema against its type, `enum`, `const` and bounds.
+///
+/// Patterns are not checked and `$ref`s are not followed.
+pub fn default_violation(schema: &Schema) -> Option<String> {
+    let default = schema.default.as_ref()?;
+
+    violation(default, schema).map(|message| format!("default {}", message))
+}
+
+fn violation(value: &Value, schema: &Schema) -> Option<String> {
+    if let Some(type_) = &schema.type_ {
+        let matches = match type_ {
+            Types::Null => value.is_null(),
+            Types::Boolean => value.is_boolean(),
+            Types::Integer => value.is_i64() || value.is_u64(),
+            Types::Number => value.is_number(),
+            Types::String => value.is_string(),
+            Types::Array => value.is_array(),
+            Types::Object => value.is_object(),
+        };
+
+        if !matches {
+            return Some(format!("{} is not of type {}", value, type_name(type_)));
+        }
+    }
+
+    if let Some(values) = &schema.enum_ {
+        if !values.contains(value) {
+            return Some(format!("{} is not one of the enum values", value));
+        }
+    }
+
+    if let Some(constant) = &schema.const_ {
+        if constant != value {
+            return Some(format!("{} is not the const value {}", value, constant));
+        }
+    }
+
+    match value {
+        Value::Number(number) => {
+            let number = number.as_f64()?;
+
+            match (schema.minimum, schema.maximum) {
+                (Some(minimum), _) if number < minimum => {
+                    Some(format!("{} is less than the minimum {}", value, minimum))
+                }
+                (_, Some(maximum)) if number > maximum => {
+                    Some(format!("{} is greater than the maximum {}", value, maximum))
+                }
+                _ => None,
+            }
+        }
+        Value::String(text) => {
+            let length = text.chars().count() as u64;
+
+            bounds_violation(
+                value,
+                length,
+                "characters",
+                schema.min_length,
+                schema.max_length,
+            )
+        }
+        Value::Array(items) => {
+            if let Some(message) = bounds_violation(
+                value,
+                items.len() as u64,
+                "items",
+                schema.min_items,
+                schema.max_items,
+            ) {
+                return Some(message);
+            }
+
+            let item_schema = schema.items.as_ref().as_ref()?;
+
+            items.iter().enumerate().find_map(|(i, item)| {
+                violation(item, item_schema)
+                    .map(|message| format!("has item {}, which {}", i, message))
+            })
+        }
+        Value::Object(properties) => {
+            if let Some(message) = bounds_violation(
+                value,
+                properties.len() as u64,
+                "properties",
+                schema.min_properties,
+                schema.max_properties,
+            ) {
+                return Some(message);
+            }
+
+            for required in schema.required.iter().flatten() {
+                if !properties.contains_key(required) {
+                    return Some(format!(
+                        "{} lacks the required property {}",
+                        value, required
+                    ));
+                }
+            }
+
+            properties.iter().find_map(|(name, property)| {
+                let property_schema = schema.properties.get(name)?;
+
+                violation(property, property_schema)
+                    .map(|message| format!("has property {}, which {}", name, message))
+            })
+        }
+        _ => None,
+    }
+}
+
+fn bounds_violation(
+    value: &Value,
+    count: u64,
+    unit: &str,
+    min: Option<u64>,
+    max: Option<u64>,
+) -> Option<String> {
+    match (min, max) {
+        (Some(min), _) if count < min => Some(format!(
+            "{} has {} {}, fewer than {}",
+            value, count, unit, min
+        )),
+        (_, Some(max)) if count > max => Some(format!(
+            "{} has {} {}, more than {}",
+            value, count, unit, max
+        )),
+        _ => None,
+    }
+}
+
+fn type_name(type_: &Types) -> &'static str {
+    match type_ {
+        Types::Null => "null",
+        Types::Boolean => "boolean",
+        Types::Integer => "integer",
+        Types::Number => "number",
+        Types::String => "string",
+        Types::Array => "array",
+        Types::Object => "object",
+    }
+}
+
+#[cfg(test)]
+mod defaults_tests {
+    use crate::defaults::default_violation;
+    use crate::schema::Schema;
+
+    fn schema(json: &str) -> Schema {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn should_accept_valid_defaults() {
+        assert_eq!(
+            default_violation(&schema(
+                r#"{"type": "integer", "minimum": 1, "maximum": 65535, "default": 8080}"#
+            )),
+            None
+        );
+        assert_eq!(default_violation(&schema(r#"{"type": "string"}"#)), None);
+    }
+
+    #[test]
+    fn should_report_defaults_violating_their_schema() {
+        assert_eq!(
+            default_violation(&schema(
+                r#"{"type": "integer", "maximum": 10, "default": 11}"#
+            )),
+            Some(String::from("default 11 is greater than the maximum 10"))
+        );
+        assert_eq!(
+            default_violation(&schema(r#"{"enum": ["a", "b"], "default": "c"}"#)),
+            Some(String::from("default \"c\" is not one of the enum values"))
+        );
+        assert_eq!(
+            default_violation(&schema(
+                r#"{"type": "string", "minLength": 2, "default": "x"}"#
+            )),
+            Some(String::from("default \"x\" has 1 characters, fewer than 2"))
+        );
+    }
+
+    #[test]
+    fn should_check_nested_values_of_defaults() {
+        assert_eq!(
+            default_violation(&schema(
+                r#"{
+                    "type": "object",
+                    "properties": {"retries": {"type": "integer"}},
+                    "default": {"retries": "3"}
+                }"#
+            )),
+            Some(String::from(
+                "default has property retries, which \"3\" is not of type integer"
+            ))
+        );
+    }
+}
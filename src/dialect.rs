@@ -0,0 +1,110 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use crate::schema::Schema;
+
+/// The drafts of JSON schema, in the order they were published
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Dialect {
+    Draft4,
+    Draft6,
+    Draft7,
+    Draft201909,
+    Draft202012,
+}
+
+impl Dialect {
+    /// The draft of a `$schema` URI like `http://json-schema.org/draft-07/schema#`
+    pub fn from_uri(uri: &str) -> Option<Dialect> {
+        let drafts = [
+            ("/draft-04/", Dialect::Draft4),
+            ("/draft-06/", Dialect::Draft6),
+            ("/draft-07/", Dialect::Draft7),
+            ("/draft/2019-09/", Dialect::Draft201909),
+            ("/draft/2020-12/", Dialect::Draft202012),
+        ];
+
+        drafts
+            .iter()
+            .find(|(path, _)| uri.contains(path))
+            .map(|(_, dialect)| *dialect)
+    }
+}
+
+/// The identifier of the schema, which draft 4 calls `id` and later drafts `$id`
+pub fn schema_id(dialect: Option<Dialect>, schema: &Schema) -> Option<String> {
+    match dialect {
+        Some(Dialect::Draft4) => schema.id.clone(),
+        Some(_) => schema.dollar_id.clone(),
+        None => schema.dollar_id.clone().or_else(|| schema.id.clone()),
+    }
+}
+
+/// Applies the rules of the draft which differ from the latest one to the schema
+/// and all of its subschemas. Schemas without a known draft follow the latest one.
+pub fn apply_dialect(dialect: Option<Dialect>, schema: &mut Schema) {
+    // Before 2019-09 a `$ref` replaces its schema, so keywords next to it don't refine it
+    if dialect.is_some_and(|x| x < Dialect::Draft201909) {
+        strip_ref_siblings(schema);
+    }
+}
+
+fn strip_ref_siblings(schema: &mut Schema) {
+    if schema.ref_.is_some() {
+        schema.properties.clear();
+        schema.required = None;
+    }
+
+    let children = schema
+        .properties
+        .values_mut()
+        .chain(schema.pattern_properties.values_mut())
+        .chain(schema.definitions.values_mut())
+        .chain(schema.defs.values_mut())
+        .chain(schema.one_of.iter_mut())
+        .chain(schema.any_of.iter_mut())
+        .chain(schema.all_of.iter_mut())
+        .chain(schema.items.iter_mut())
+        .chain(schema.prefix_items.iter_mut())
+        .chain(schema.additional_properties.iter_mut())
+        .chain(schema.property_names.iter_mut());
+
+    for child in children {
+        strip_ref_siblings(child);
+    }
+}
+
+#[cfg(test)]
+mod dialect_tests {
+    use crate::dialect::{schema_id, Dialect};
+    use crate::schema::Schema;
+
+    #[test]
+    fn should_detect_the_draft_of_the_uri() {
+        assert_eq!(
+            Dialect::from_uri("http://json-schema.org/draft-04/schema#"),
+            Some(Dialect::Draft4)
+        );
+        assert_eq!(
+            Dialect::from_uri("https://json-schema.org/draft/2020-12/schema"),
+            Some(Dialect::Draft202012)
+        );
+        assert_eq!(Dialect::from_uri("https://example.com/schema"), None);
+    }
+
+    #[test]
+    fn should_read_the_id_of_the_draft() {
+        let schema: Schema =
+            serde_json::from_str(r#"{"id": "old.json", "$id": "new.json"}"#).unwrap();
+
+        assert_eq!(
+            schema_id(Some(Dialect::Draft4), &schema),
+            Some(String::from("old.json"))
+        );
+        assert_eq!(
+            schema_id(Some(Dialect::Draft7), &schema),
+            Some(String::from("new.json"))
+        );
+    }
+}
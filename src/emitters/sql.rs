@@ -0,0 +1,177 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use crate::generated::{GeneratedProperty, GeneratedType, TypeKind};
+use crate::sanitizer::sanitize_property_name;
+use std::collections::HashMap;
+
+/// Emits a `CREATE TABLE` sketch for every struct whose properties are all scalars.
+///
+/// Structs with nested objects, arrays or maps are listed in a comment instead.
+pub fn create_tables(types: &[GeneratedType]) -> String {
+    let aliases: HashMap<&str, &str> = types
+        .iter()
+        .filter_map(|x| match &x.kind {
+            TypeKind::Alias(target)
+            | TypeKind::Newtype(target)
+            | TypeKind::Constrained {
+                value_type: target, ..
+            } => Some((x.name.as_str(), target.as_str())),
+            TypeKind::MapKey(_) | TypeKind::StringEnum(_) => Some((x.name.as_str(), "String")),
+            _ => None,
+        })
+        .collect();
+
+    let mut statements = Vec::new();
+
+    for generated_type in types.iter().filter(|x| x.kind == TypeKind::Struct) {
+        let columns: Result<Vec<String>, &GeneratedProperty> = generated_type
+            .properties
+            .iter()
+            .map(|property| column(property, &aliases).ok_or(property))
+            .collect();
+
+        let statement = match columns {
+            Ok(columns) => format!(
+                "CREATE TABLE {} (\n    {}\n);\n",
+                quote_identifier(
+                    sanitize_property_name(generated_type.name.clone()).trim_end_matches('_')
+                ),
+                columns.join(",\n    ")
+            ),
+            Err(property) => format!(
+                "-- {} is not flat: {} is a {}\n",
+                generated_type.name, property.name, property.property_type
+            ),
+        };
+
+        statements.push(statement);
+    }
+
+    statements.join("\n")
+}
+
+fn column(property: &GeneratedProperty, aliases: &HashMap<&str, &str>) -> Option<String> {
+    let name = match &property.serde_options.rename {
+        Some(rename) => rename.as_str(),
+        None => property.name.as_str(),
+    };
+
+    let (type_name, nullable) = match property
+        .property_type
+        .strip_prefix("Option<")
+        .and_then(|x| x.strip_suffix('>'))
+    {
+        Some(inner) => (inner, true),
+        None => (property.property_type.as_str(), false),
+    };
+
+    let column_type = sql_type(type_name, aliases)?;
+
+    Some(match nullable {
+        true => format!("{} {}", quote_identifier(name), column_type),
+        false => format!("{} {} NOT NULL", quote_identifier(name), column_type),
+    })
+}
+
+fn sql_type(type_name: &str, aliases: &HashMap<&str, &str>) -> Option<&'static str> {
+    match type_name {
+        "String" => Some("TEXT"),
+        "bool" => Some("BOOLEAN"),
+        "u8" | "i8" | "i16" => Some("SMALLINT"),
+        "u16" | "i32" => Some("INTEGER"),
+        "u32" | "i64" => Some("BIGINT"),
+        // Exceeds `BIGINT`
+        "u64" => Some("NUMERIC(20)"),
+        "f64" => Some("DOUBLE PRECISION"),
+        _ => aliases
+            .get(type_name)
+            .and_then(|target| sql_type(target, aliases)),
+    }
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod sql_tests {
+    use crate::emitters::sql::create_tables;
+    use crate::generated::{GeneratedProperty, GeneratedType, SerdeOptions, TypeKind};
+
+    fn property(name: &str, property_type: &str) -> GeneratedProperty {
+        GeneratedProperty {
+            name: String::from(name),
+            property_type: String::from(property_type),
+            serde_options: SerdeOptions {
+                rename: None,
+                skip_serializing_if: None,
+                default: None,
+                with: None,
+                deserialize_with: None,
+                aliases: vec![],
+                flatten: false,
+                skip_serializing: false,
+                skip_deserializing: false,
+            },
+            description: None,
+            map_constraints: None,
+            value_constraints: None,
+            requires: vec![],
+        }
+    }
+
+    fn generated_type(
+        name: &str,
+        kind: TypeKind,
+        properties: Vec<GeneratedProperty>,
+    ) -> GeneratedType {
+        GeneratedType {
+            src: String::new(),
+            name: String::from(name),
+            doc_alias: None,
+            description: None,
+            derives: vec![],
+            removed_derives: vec![],
+            kind,
+            constants: vec![],
+            defaults: vec![],
+            deny_unknown_fields: false,
+            properties,
+            examples: vec![],
+            module: vec![],
+            required_any: vec![],
+        }
+    }
+
+    #[test]
+    fn should_create_tables_for_flat_structs() {
+        let mut renamed = property("created_at", "Option<f64>");
+        renamed.serde_options.rename = Some(String::from("createdAt"));
+
+        let types = vec![
+            generated_type(
+                "UserAccount",
+                TypeKind::Struct,
+                vec![property("id", "UserId"), property("admin", "bool"), renamed],
+            ),
+            generated_type("UserId", TypeKind::Alias(String::from("i64")), vec![]),
+            generated_type(
+                "Team",
+                TypeKind::Struct,
+                vec![property("members", "Vec<UserAccount>")],
+            ),
+        ];
+
+        assert_eq!(
+            create_tables(&types),
+            "CREATE TABLE \"user_account\" (\n    \
+             \"id\" BIGINT NOT NULL,\n    \
+             \"admin\" BOOLEAN NOT NULL,\n    \
+             \"createdAt\" DOUBLE PRECISION\n);\n\
+             \n\
+             -- Team is not flat: members is a Vec<UserAccount>\n"
+        );
+    }
+}
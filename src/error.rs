@@ -0,0 +1,61 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use std::fmt;
+use std::path::PathBuf;
+
+/// Why a schema could not be read or its types could not be generated
+#[derive(Clone, PartialEq, Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The file could not be read, or the file system is disabled
+    Read { file: PathBuf, message: String },
+    /// The file is no valid JSON, or no schema
+    Parse {
+        file: PathBuf,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// A `$ref` points to a missing file or definition, `location` is where the `$ref` is
+    Resolve { location: String, message: String },
+    /// The generated code could not be written to the file
+    Write { file: PathBuf, message: String },
+    /// An option holds code which can't be parsed, `option` is the field of the options
+    Config { option: String, message: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Read { file, message } => {
+                write!(f, "Could not open {}: {}", file.display(), message)
+            }
+            Error::Parse {
+                file,
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "Could not parse {}:{}:{}: {}",
+                file.display(),
+                line,
+                column,
+                message
+            ),
+            Error::Resolve { location, message } => {
+                write!(f, "Could not resolve {}: {}", location, message)
+            }
+            Error::Write { file, message } => {
+                write!(f, "Could not write {}: {}", file.display(), message)
+            }
+            Error::Config { option, message } => {
+                write!(f, "Invalid option {}: {}", option, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
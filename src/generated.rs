@@ -2,77 +2,1109 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
 
-use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use crate::options::{CratePaths, MapType, MethodAttributes, Visibility};
+use crate::parser::{is_integer_type, MapConstraints, ValueConstraints};
+use crate::sanitizer::doc_alias;
+use crate::validation::value_checks;
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{quote, ToTokens};
+use serde_json::Value;
 
 #[derive(Eq, PartialEq, Debug)]
+#[non_exhaustive]
+pub enum TypeKind {
+    Struct,
+    Alias(String),
+    Newtype(String),
+    Wrapper,
+    UntaggedEnum(Vec<GeneratedVariant>),
+    /// An internally tagged enum of objects, told apart by the `const` value of the tag property
+    TaggedEnum {
+        tag: String,
+        variants: Vec<TaggedVariant>,
+    },
+    /// A string map key which has to match the given pattern
+    MapKey(String),
+    /// A unit struct which only accepts `null`
+    Null,
+    /// A newtype around a map, for root schemas which are maps
+    Map {
+        map_type: MapType,
+        key: String,
+        value: String,
+    },
+    /// A fieldless enum of the strings of an `enum`
+    StringEnum(Vec<StringVariant>),
+    /// A string or number whose constructor checks the `pattern`, length or bounds
+    Constrained {
+        value_type: String,
+        constraints: ValueConstraints,
+    },
+}
+
+#[derive(Eq, PartialEq, Debug)]
+#[non_exhaustive]
 pub struct GeneratedType {
     pub src: String,
     pub name: String,
+    pub doc_alias: Option<String>,
+    /// The `description`, shown above the source in the docs
+    pub description: Option<String>,
+    /// Derives in addition to the ones every type of its kind gets
+    pub derives: Vec<String>,
+    /// Derives the type doesn't get, even if every type of its kind gets them
+    pub removed_derives: Vec<String>,
+    pub kind: TypeKind,
+    pub constants: Vec<GeneratedConstant>,
+    /// Functions of the `default` values which aren't the `Default` of their type
+    pub defaults: Vec<GeneratedDefault>,
+    /// Keys without a property fail to deserialize, for `additionalProperties: false`
+    pub deny_unknown_fields: bool,
     pub properties: Vec<GeneratedProperty>,
+    /// JSON documents of the `examples`, the first one is shown in the docs
+    pub examples: Vec<String>,
+    /// Path of the module the type is emitted in, empty for the root module
+    pub module: Vec<String>,
+    /// Sets of properties of which at least one has to be present, from an `anyOf`
+    pub required_any: Vec<Vec<String>>,
 }
 
-impl Into<TokenStream> for GeneratedType {
-    fn into(self) -> TokenStream {
+impl GeneratedType {
+    /// The types of all properties or variants, which a `Box` may be placed around
+    pub fn field_types(&self) -> Vec<&String> {
+        match &self.kind {
+            TypeKind::UntaggedEnum(variants) => variants.iter().map(|x| &x.variant_type).collect(),
+            TypeKind::TaggedEnum { variants, .. } => {
+                variants.iter().map(|x| &x.variant_type).collect()
+            }
+            _ => self.properties.iter().map(|x| &x.property_type).collect(),
+        }
+    }
+
+    /// The identifiers in the types the type refers to, like `Vec` and `Inner` of `Vec<Inner>`
+    pub(crate) fn referenced_names(&self) -> Vec<&str> {
+        let type_names: Vec<&String> = match &self.kind {
+            TypeKind::Alias(target)
+            | TypeKind::Newtype(target)
+            | TypeKind::Constrained {
+                value_type: target, ..
+            } => vec![target],
+            TypeKind::Map { key, value, .. } => vec![key, value],
+            _ => self.field_types(),
+        };
+
+        type_names
+            .into_iter()
+            .flat_map(|x| x.split(|c: char| !c.is_alphanumeric() && c != '_'))
+            .filter(|x| !x.is_empty())
+            .collect()
+    }
+
+    /// Adds the derives which the type doesn't already get from its kind
+    pub(crate) fn add_derives(&mut self, derives: &[String]) {
+        let builtin: &[&str] = match self.kind {
+            TypeKind::Alias(_) => return,
+            TypeKind::Constrained { .. } => rendered_derives(&self.kind),
+            TypeKind::Struct
+            | TypeKind::Newtype(_)
+            | TypeKind::Wrapper
+            | TypeKind::UntaggedEnum(_)
+            | TypeKind::TaggedEnum { .. } => {
+                &["Clone", "PartialEq", "Debug", "Deserialize", "Serialize"]
+            }
+            TypeKind::Null => &[
+                "Clone",
+                "Copy",
+                "PartialEq",
+                "Eq",
+                "Debug",
+                "Default",
+                "Deserialize",
+                "Serialize",
+            ],
+            TypeKind::Map { .. } => &[
+                "Clone",
+                "PartialEq",
+                "Debug",
+                "Default",
+                "Deserialize",
+                "Serialize",
+            ],
+            TypeKind::MapKey(_) | TypeKind::StringEnum(_) => &[
+                "Clone",
+                "Copy",
+                "PartialEq",
+                "Eq",
+                "PartialOrd",
+                "Ord",
+                "Hash",
+                "Debug",
+                "Deserialize",
+                "Serialize",
+            ],
+        };
+
+        for derive in derives {
+            if !builtin.contains(&derive.as_str()) && !self.derives.contains(derive) {
+                self.derives.push(derive.clone());
+            }
+        }
+    }
+
+    pub fn field_types_mut(&mut self) -> Vec<&mut String> {
+        match &mut self.kind {
+            TypeKind::UntaggedEnum(variants) => {
+                variants.iter_mut().map(|x| &mut x.variant_type).collect()
+            }
+            TypeKind::TaggedEnum { variants, .. } => {
+                variants.iter_mut().map(|x| &mut x.variant_type).collect()
+            }
+            _ => self
+                .properties
+                .iter_mut()
+                .map(|x| &mut x.property_type)
+                .collect(),
+        }
+    }
+}
+
+impl From<GeneratedType> for TokenStream {
+    fn from(val: GeneratedType) -> Self {
+        val.into_tokens(
+            &CratePaths::default(),
+            Visibility::default(),
+            MethodAttributes::default(),
+        )
+    }
+}
+
+impl GeneratedType {
+    pub fn into_tokens(
+        self,
+        paths: &CratePaths,
+        visibility: Visibility,
+        attributes: MethodAttributes,
+    ) -> TokenStream {
         let GeneratedType {
             src,
             name,
+            doc_alias,
+            description,
+            derives,
+            removed_derives,
+            kind,
+            constants,
+            defaults,
+            deny_unknown_fields,
             properties,
+            examples,
+            module: _,
+            required_any: _,
         } = self;
 
-        let properties: Vec<TokenStream> = properties.into_iter().map(|x| x.into()).collect();
+        let source = format!("Generated from {}", src);
+        let comment = match examples.first() {
+            Some(example) => {
+                let example = doc_lines(example);
+
+                quote! {
+                    #[doc = #source]
+                    #[doc = ""]
+                    #[doc = "```json"]
+                    #example
+                    #[doc = "```"]
+                }
+            }
+            None => quote! { #[doc = #source] },
+        };
+
+        let comment = match description {
+            Some(description) => {
+                let description = doc_lines(&description);
 
-        let comment = format!("///Generated from {}", src)
-            .parse::<TokenStream>()
-            .unwrap();
+                quote! {
+                    #description
+                    #[doc = ""]
+                    #comment
+                }
+            }
+            None => comment,
+        };
+
+        let comment = match doc_alias {
+            Some(doc_alias) => quote! {
+                #comment
+                #[doc(alias = #doc_alias)]
+            },
+            None => comment,
+        };
 
         let name = proc_macro2::Ident::new(&name, Span::call_site());
+        let vis = visibility.tokens();
+        let method = attributes.method();
+        let conversion = attributes.conversion();
+        let examples = match examples.is_empty() {
+            true => quote! {},
+            false => quote! {
+                pub fn examples() -> &'static [&'static str] {
+                    &[#(#examples),*]
+                }
+            },
+        };
+        let serde = paths.serde();
+        let serde_crate = paths.serde_crate_attribute();
+        let builtin_derives = rendered_derives(&kind);
+        let derive = derive_attribute(builtin_derives, &derives, &removed_derives);
 
-        quote! {
-            #comment
-            #[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
-            pub struct #name {
-                #(#properties),*
+        match kind {
+            TypeKind::Struct => {
+                let mut defaults = defaults;
+
+                // Boxes placed around a property also wrap its default value
+                for default in &mut defaults {
+                    let property_name = default.function.trim_start_matches("default_");
+
+                    if let Some(property) = properties.iter().find(|x| x.name == property_name) {
+                        default.property_type = property.property_type.clone();
+                    }
+                }
+
+                let (derive_default, default_impl) = default_impl(&name, &properties, &defaults);
+                let derive = match derive_default && !derives.iter().any(|x| x == "Default") {
+                    true => derive_attribute(
+                        &[builtin_derives, &["Default"]].concat(),
+                        &derives,
+                        &removed_derives,
+                    ),
+                    false => derive,
+                };
+                let rename_all = field_rename_rule(&properties);
+                let properties: Vec<TokenStream> = properties
+                    .into_iter()
+                    .map(|x| x.into_tokens(rename_all.is_some()))
+                    .collect();
+                let rename_all = match rename_all {
+                    Some(rule) => quote! { #[serde(rename_all = #rule)] },
+                    None => quote! {},
+                };
+
+                let constants =
+                    match constants.is_empty() && defaults.is_empty() && examples.is_empty() {
+                        true => quote! {},
+                        false => {
+                            let serde_json = paths.serde_json();
+                            let constants: Vec<TokenStream> = constants
+                                .into_iter()
+                                .map(|x| x.into_tokens(attributes))
+                                .collect();
+                            let defaults: Vec<TokenStream> = defaults
+                                .into_iter()
+                                .map(|x| x.into_tokens(&serde_json, attributes))
+                                .collect();
+
+                            quote! {
+                                impl #name {
+                                    #(#constants)*
+                                    #(#defaults)*
+                                    #examples
+                                }
+                            }
+                        }
+                    };
+
+                let deny_unknown_fields = match deny_unknown_fields {
+                    true => quote! { #[serde(deny_unknown_fields)] },
+                    false => quote! {},
+                };
+
+                quote! {
+                    #comment
+                    #derive
+                    #serde_crate
+                    #rename_all
+                    #deny_unknown_fields
+                    #vis struct #name {
+                        #(#properties),*
+                    }
+                    #constants
+                    #default_impl
+                }
+            }
+            TypeKind::Alias(target) => {
+                let target = target.parse::<TokenStream>().unwrap();
+
+                quote! {
+                    #comment
+                    #vis type #name = #target;
+                }
+            }
+            TypeKind::Newtype(target) => {
+                let target = target.parse::<TokenStream>().unwrap();
+
+                quote! {
+                    #comment
+                    #derive
+                    #serde_crate
+                    #[serde(transparent)]
+                    #vis struct #name(pub #target);
+                    impl std::fmt::Display for #name {
+                        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                            std::fmt::Display::fmt(&self.0, f)
+                        }
+                    }
+                    impl From<#target> for #name {
+                        #conversion
+                        fn from(value: #target) -> Self {
+                            #name(value)
+                        }
+                    }
+                    impl From<#name> for #target {
+                        #conversion
+                        fn from(value: #name) -> Self {
+                            value.0
+                        }
+                    }
+                    impl AsRef<#target> for #name {
+                        #conversion
+                        fn as_ref(&self) -> &#target {
+                            &self.0
+                        }
+                    }
+                }
+            }
+            TypeKind::Wrapper => {
+                let property = &properties[0];
+                let key = match &property.serde_options.rename {
+                    Some(rename) => rename.clone(),
+                    None => property.name.clone(),
+                };
+                let label = name.to_string();
+                let target = property.property_type.parse::<TokenStream>().unwrap();
+                let examples = match examples.is_empty() {
+                    true => quote! {},
+                    false => quote! {
+                        impl #name {
+                            #examples
+                        }
+                    },
+                };
+
+                let serialize_field = match property.property_type.starts_with("Option<") {
+                    true => quote! {
+                        match &self.0 {
+                            Some(value) => state.serialize_field(#key, value)?,
+                            None => state.skip_field(#key)?,
+                        }
+                    },
+                    false => quote! {
+                        state.serialize_field(#key, &self.0)?;
+                    },
+                };
+
+                quote! {
+                    #comment
+                    #derive
+                    #vis struct #name(pub #target);
+                    #examples
+                    impl std::ops::Deref for #name {
+                        type Target = #target;
+                        #conversion
+                        fn deref(&self) -> &Self::Target {
+                            &self.0
+                        }
+                    }
+                    impl Serialize for #name {
+                        fn serialize<S: #serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                            use #serde::ser::SerializeStruct;
+                            let mut state = serializer.serialize_struct(#label, 1)?;
+                            #serialize_field
+                            state.end()
+                        }
+                    }
+                    impl<'de> Deserialize<'de> for #name {
+                        fn deserialize<D: #serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                            #[derive(Deserialize)]
+                            #serde_crate
+                            struct __Repr {
+                                #[serde(rename = #key)]
+                                value: #target,
+                            }
+                            __Repr::deserialize(deserializer).map(|repr| #name(repr.value))
+                        }
+                    }
+                }
+            }
+            TypeKind::Null => quote! {
+                ///The JSON `null` value
+                #derive
+                #serde_crate
+                #vis struct #name;
+            },
+            TypeKind::Map {
+                map_type,
+                key,
+                value,
+            } => {
+                let map = map_type.ident();
+                let key = key.parse::<TokenStream>().unwrap();
+                let value = value.parse::<TokenStream>().unwrap();
+
+                quote! {
+                    #comment
+                    #derive
+                    #serde_crate
+                    #[serde(transparent)]
+                    #vis struct #name(pub #map<#key, #value>);
+                    impl std::ops::Deref for #name {
+                        type Target = #map<#key, #value>;
+                        #conversion
+                        fn deref(&self) -> &Self::Target {
+                            &self.0
+                        }
+                    }
+                    impl std::ops::DerefMut for #name {
+                        #conversion
+                        fn deref_mut(&mut self) -> &mut Self::Target {
+                            &mut self.0
+                        }
+                    }
+                    impl std::iter::FromIterator<(#key, #value)> for #name {
+                        #conversion
+                        fn from_iter<I: IntoIterator<Item = (#key, #value)>>(iter: I) -> Self {
+                            #name(iter.into_iter().collect())
+                        }
+                    }
+                    impl Extend<(#key, #value)> for #name {
+                        fn extend<I: IntoIterator<Item = (#key, #value)>>(&mut self, iter: I) {
+                            self.0.extend(iter)
+                        }
+                    }
+                    impl IntoIterator for #name {
+                        type Item = (#key, #value);
+                        type IntoIter = <#map<#key, #value> as IntoIterator>::IntoIter;
+                        #conversion
+                        fn into_iter(self) -> Self::IntoIter {
+                            self.0.into_iter()
+                        }
+                    }
+                    impl From<#map<#key, #value>> for #name {
+                        #conversion
+                        fn from(value: #map<#key, #value>) -> Self {
+                            #name(value)
+                        }
+                    }
+                }
+            }
+            TypeKind::StringEnum(variants) => {
+                let idents: Vec<proc_macro2::Ident> = variants
+                    .iter()
+                    .map(|x| proc_macro2::Ident::new(&x.name, Span::call_site()))
+                    .collect();
+                let values: Vec<&String> = variants.iter().map(|x| &x.value).collect();
+                let (rename_all, renames) =
+                    variant_renames(variants.iter().map(|x| (x.name.as_str(), x.value.as_str())));
+                let docs: Vec<TokenStream> = variants
+                    .iter()
+                    .map(|x| x.description.as_deref().map(doc_lines).unwrap_or_default())
+                    .collect();
+
+                quote! {
+                    #comment
+                    #derive
+                    #serde_crate
+                    #rename_all
+                    #vis enum #name {
+                        #(
+                            #docs
+                            #renames
+                            #idents,
+                        )*
+                    }
+                    impl #name {
+                        pub const ALL: &'static [Self] = &[#(#name::#idents),*];
+                        #method
+                        pub fn as_str(&self) -> &'static str {
+                            match self {
+                                #(#name::#idents => #values,)*
+                            }
+                        }
+                        #examples
+                    }
+                    impl std::fmt::Display for #name {
+                        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                            f.write_str(self.as_str())
+                        }
+                    }
+                }
+            }
+            TypeKind::MapKey(pattern) => {
+                let label = name.to_string();
+
+                quote! {
+                    #comment
+                    #derive
+                    #serde_crate
+                    #[serde(try_from = "String", into = "String")]
+                    #vis struct #name(String);
+                    impl #name {
+                        pub const PATTERN: &'static str = #pattern;
+                        #conversion
+                        pub fn new(value: String) -> Result<Self, String> {
+                            static REGEX: std::sync::OnceLock<Result<regex::Regex, String>> = std::sync::OnceLock::new();
+                            match REGEX.get_or_init(|| regex::Regex::new(Self::PATTERN).map_err(|e| e.to_string())) {
+                                Ok(regex) if regex.is_match(&value) => Ok(#name(value)),
+                                Ok(_) => Err(format!("{} '{}' does not match {}", #label, value, Self::PATTERN)),
+                                Err(error) => Err(error.clone()),
+                            }
+                        }
+                        #method
+                        pub fn as_str(&self) -> &str {
+                            &self.0
+                        }
+                    }
+                    impl std::fmt::Display for #name {
+                        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                            f.write_str(&self.0)
+                        }
+                    }
+                    impl std::convert::TryFrom<String> for #name {
+                        type Error = String;
+                        #conversion
+                        fn try_from(value: String) -> Result<Self, Self::Error> {
+                            #name::new(value)
+                        }
+                    }
+                    impl From<#name> for String {
+                        #conversion
+                        fn from(value: #name) -> Self {
+                            value.0
+                        }
+                    }
+                }
+            }
+            TypeKind::Constrained {
+                value_type,
+                constraints,
+            } => {
+                let label = name.to_string();
+                let checks = value_checks(&value_type, &constraints, &|message, _| {
+                    quote! { format!("{} {}", #label, #message) }
+                });
+                let (accessor, display) = match value_type.as_str() {
+                    "String" => (
+                        quote! {
+                            pub fn as_str(&self) -> &str {
+                                &self.0
+                            }
+                        },
+                        quote! { f.write_str(&self.0) },
+                    ),
+                    _ => {
+                        let value_type = value_type.parse::<TokenStream>().unwrap();
+
+                        (
+                            quote! {
+                                pub fn get(&self) -> #value_type {
+                                    self.0
+                                }
+                            },
+                            quote! { std::fmt::Display::fmt(&self.0, f) },
+                        )
+                    }
+                };
+                let serde_type = value_type.as_str();
+                let value_type = value_type.parse::<TokenStream>().unwrap();
+
+                quote! {
+                    #comment
+                    #derive
+                    #serde_crate
+                    #[serde(try_from = #serde_type, into = #serde_type)]
+                    #vis struct #name(#value_type);
+                    impl #name {
+                        #conversion
+                        pub fn new(value: #value_type) -> Result<Self, String> {
+                            {
+                                let value = &value;
+                                #(#checks)*
+                            }
+                            Ok(#name(value))
+                        }
+                        #method
+                        #accessor
+                        #examples
+                    }
+                    impl std::fmt::Display for #name {
+                        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                            #display
+                        }
+                    }
+                    impl std::convert::TryFrom<#value_type> for #name {
+                        type Error = String;
+                        #conversion
+                        fn try_from(value: #value_type) -> Result<Self, Self::Error> {
+                            #name::new(value)
+                        }
+                    }
+                    impl From<#name> for #value_type {
+                        #conversion
+                        fn from(value: #name) -> Self {
+                            value.0
+                        }
+                    }
+                }
+            }
+            TypeKind::UntaggedEnum(variants) => {
+                let variants: Vec<TokenStream> = variants.into_iter().map(|x| x.into()).collect();
+
+                quote! {
+                    #comment
+                    #derive
+                    #serde_crate
+                    #[serde(untagged)]
+                    #vis enum #name {
+                        #(#variants),*
+                    }
+                }
+            }
+            TypeKind::TaggedEnum { tag, variants } => {
+                let idents: Vec<proc_macro2::Ident> = variants
+                    .iter()
+                    .map(|x| proc_macro2::Ident::new(&x.name, Span::call_site()))
+                    .collect();
+                let (rename_all, renames) =
+                    variant_renames(variants.iter().map(|x| (x.name.as_str(), x.value.as_str())));
+                let variant_types: Vec<TokenStream> = variants
+                    .iter()
+                    .map(|x| x.variant_type.parse::<TokenStream>().unwrap())
+                    .collect();
+
+                quote! {
+                    #comment
+                    #derive
+                    #serde_crate
+                    #[serde(tag = #tag)]
+                    #rename_all
+                    #vis enum #name {
+                        #(
+                            #renames
+                            #idents(#variant_types),
+                        )*
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The derives every type of the kind is rendered with
+fn rendered_derives(kind: &TypeKind) -> &'static [&'static str] {
+    match kind {
+        TypeKind::Alias(_) => &[],
+        TypeKind::Wrapper => &["Clone", "PartialEq", "Debug"],
+        TypeKind::Null => &[
+            "Clone",
+            "Copy",
+            "PartialEq",
+            "Eq",
+            "Debug",
+            "Default",
+            "Deserialize",
+            "Serialize",
+        ],
+        TypeKind::Map { .. } => &[
+            "Clone",
+            "PartialEq",
+            "Debug",
+            "Default",
+            "Deserialize",
+            "Serialize",
+        ],
+        TypeKind::StringEnum(_) => &[
+            "Clone",
+            "Copy",
+            "PartialEq",
+            "Eq",
+            "PartialOrd",
+            "Ord",
+            "Hash",
+            "Debug",
+            "Deserialize",
+            "Serialize",
+        ],
+        TypeKind::Constrained { value_type, .. } if value_type == "f64" => &[
+            "Clone",
+            "Copy",
+            "PartialEq",
+            "PartialOrd",
+            "Debug",
+            "Deserialize",
+            "Serialize",
+        ],
+        TypeKind::Constrained { value_type, .. } if value_type != "String" => &[
+            "Clone",
+            "Copy",
+            "PartialEq",
+            "Eq",
+            "PartialOrd",
+            "Ord",
+            "Hash",
+            "Debug",
+            "Deserialize",
+            "Serialize",
+        ],
+        TypeKind::MapKey(_) | TypeKind::Constrained { .. } => &[
+            "Clone",
+            "PartialEq",
+            "Eq",
+            "PartialOrd",
+            "Ord",
+            "Hash",
+            "Debug",
+            "Deserialize",
+            "Serialize",
+        ],
+        TypeKind::Struct
+        | TypeKind::Newtype(_)
+        | TypeKind::UntaggedEnum(_)
+        | TypeKind::TaggedEnum { .. } => {
+            &["Clone", "PartialEq", "Debug", "Deserialize", "Serialize"]
+        }
+    }
+}
+
+/// The built-in derives of the kind and the additional ones, without the removed ones
+fn derive_attribute(builtin: &[&str], derives: &[String], removed: &[String]) -> TokenStream {
+    let derives: Vec<TokenStream> = builtin
+        .iter()
+        .copied()
+        .chain(derives.iter().map(|x| x.as_str()))
+        .filter(|x| !removed.iter().any(|removed| removed == x))
+        .map(|x| x.parse::<TokenStream>().unwrap())
+        .collect();
+
+    match derives.is_empty() {
+        true => quote! {},
+        false => quote! { #[derive(#(#derives),*)] },
+    }
+}
+
+/// The `rename_all` rules of serde, in the order they are tried,
+/// so single words are snake case rather than lowercase
+const RENAME_RULES: [&str; 8] = [
+    "PascalCase",
+    "snake_case",
+    "camelCase",
+    "kebab-case",
+    "SCREAMING_SNAKE_CASE",
+    "SCREAMING-KEBAB-CASE",
+    "lowercase",
+    "UPPERCASE",
+];
+
+/// The value serde derives from the name of a variant under the `rename_all` rule
+fn apply_rename_rule(rule: &str, name: &str) -> String {
+    let mut snake = String::new();
+
+    for (i, ch) in name.char_indices() {
+        if i > 0 && ch.is_uppercase() {
+            snake.push('_');
+        }
+
+        snake.push(ch.to_ascii_lowercase());
+    }
+
+    match rule {
+        "camelCase" => {
+            let mut chars = name.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_lowercase())
+                .into_iter()
+                .chain(chars)
+                .collect()
+        }
+        "lowercase" => name.to_ascii_lowercase(),
+        "UPPERCASE" => name.to_ascii_uppercase(),
+        "snake_case" => snake,
+        "SCREAMING_SNAKE_CASE" => snake.to_ascii_uppercase(),
+        "kebab-case" => snake.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => snake.to_ascii_uppercase().replace('_', "-"),
+        _ => name.to_string(),
+    }
+}
+
+/// A `rename_all` for the enum if one rule gives every variant its value,
+/// else a `rename` for every variant
+fn variant_renames<'a, I>(variants: I) -> (TokenStream, Vec<TokenStream>)
+where
+    I: Iterator<Item = (&'a str, &'a str)> + Clone,
+{
+    let rule = RENAME_RULES.iter().find(|rule| {
+        variants
+            .clone()
+            .all(|(name, value)| apply_rename_rule(rule, name) == value)
+    });
+
+    match rule {
+        Some(&"PascalCase") => (quote! {}, variants.map(|_| quote! {}).collect()),
+        Some(rule) => (
+            quote! { #[serde(rename_all = #rule)] },
+            variants.map(|_| quote! {}).collect(),
+        ),
+        None => (
+            quote! {},
+            variants
+                .map(|(_, value)| quote! { #[serde(rename = #value)] })
+                .collect(),
+        ),
+    }
+}
+
+/// The `rename_all` rules of serde which turn snake case fields into other keys
+const FIELD_RENAME_RULES: [&str; 5] = [
+    "camelCase",
+    "PascalCase",
+    "kebab-case",
+    "SCREAMING_SNAKE_CASE",
+    "SCREAMING-KEBAB-CASE",
+];
+
+/// The key serde derives from the name of a field under the `rename_all` rule
+fn apply_field_rename_rule(rule: &str, name: &str) -> String {
+    let mut pascal = String::new();
+    let mut capitalize = true;
+
+    for ch in name.chars() {
+        match ch {
+            '_' => capitalize = true,
+            ch if capitalize => {
+                pascal.push(ch.to_ascii_uppercase());
+                capitalize = false;
             }
+            ch => pascal.push(ch),
         }
     }
+
+    match rule {
+        "PascalCase" => pascal,
+        "camelCase" => {
+            let mut chars = pascal.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_lowercase())
+                .into_iter()
+                .chain(chars)
+                .collect()
+        }
+        "kebab-case" => name.replace('_', "-"),
+        "SCREAMING_SNAKE_CASE" => name.to_ascii_uppercase(),
+        "SCREAMING-KEBAB-CASE" => name.to_ascii_uppercase().replace('_', "-"),
+        _ => name.to_string(),
+    }
+}
+
+/// The `rename_all` rule which gives every field of a struct its key,
+/// if the fields are renamed at all
+fn field_rename_rule(properties: &[GeneratedProperty]) -> Option<&'static str> {
+    let fields: Vec<(&str, &str)> = properties
+        .iter()
+        .filter(|x| !x.serde_options.flatten && !x.name.is_empty())
+        .map(|x| {
+            let key = x.serde_options.rename.as_deref().unwrap_or(&x.name);
+            (x.name.as_str(), key)
+        })
+        .collect();
+
+    if fields.iter().all(|(name, key)| name == key) {
+        return None;
+    }
+
+    FIELD_RENAME_RULES.iter().copied().find(|rule| {
+        fields
+            .iter()
+            .all(|(name, key)| apply_field_rename_rule(rule, name) == *key)
+    })
 }
 
 #[derive(Eq, PartialEq, Debug)]
+#[non_exhaustive]
 pub struct GeneratedProperty {
     pub name: String,
     pub property_type: String,
     pub serde_options: SerdeOptions,
+    pub description: Option<String>,
+    /// Checked by the generated `validate` method of the struct
+    pub map_constraints: Option<MapConstraints>,
+    /// Also checked by the `validate` method, only those which fit the type are kept
+    pub value_constraints: Option<ValueConstraints>,
+    /// Keys which have to be present if the property is, checked by the `validate` method
+    pub requires: Vec<String>,
+}
+
+/// A doc attribute for every line, as `///` comments would give
+fn doc_lines(text: &str) -> TokenStream {
+    let lines = text.trim().lines().map(|x| x.trim_end());
+
+    quote! {
+        #(#[doc = #lines])*
+    }
+}
+
+fn describe_map_constraints(constraints: &MapConstraints) -> String {
+    let entries = |count: u64| match count {
+        1 => String::from("1 entry"),
+        count => format!("{} entries", count),
+    };
+
+    let count = match (constraints.min_properties, constraints.max_properties) {
+        (Some(min), Some(max)) => Some(format!("Between {} and {}", min, entries(max))),
+        (Some(min), None) => Some(format!("At least {}", entries(min))),
+        (None, Some(max)) => Some(format!("At most {}", entries(max))),
+        (None, None) => None,
+    };
+
+    let mut parts: Vec<String> = count.into_iter().collect();
+
+    if let Some(pattern) = &constraints.key_pattern {
+        parts.push(format!("keys matching `{}`", pattern));
+    }
+
+    if !constraints.required_keys.is_empty() {
+        let keys: Vec<String> = constraints
+            .required_keys
+            .iter()
+            .map(|x| format!("`{}`", x))
+            .collect();
+        parts.push(format!("requiring {}", keys.join(", ")));
+    }
+
+    let description = parts.join(", ");
+    let mut chars = description.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => description,
+    }
+}
+
+impl From<GeneratedProperty> for TokenStream {
+    fn from(val: GeneratedProperty) -> Self {
+        val.into_tokens(false)
+    }
 }
 
-impl Into<TokenStream> for GeneratedProperty {
-    fn into(self) -> TokenStream {
+impl GeneratedProperty {
+    /// The field, without its `rename` if the `rename_all` of the struct gives it its key
+    fn into_tokens(self, renamed_by_struct: bool) -> TokenStream {
         let GeneratedProperty {
             name,
             property_type,
             serde_options,
+            description,
+            map_constraints,
+            value_constraints: _,
+            requires,
         } = self;
 
         let mut attributes: Vec<TokenStream> = Vec::new();
 
-        match serde_options.rename {
-            Some(name) => {
-                attributes.push(quote! {
-                    #[serde(rename = #name)]
-                });
+        if let Some(description) = &description {
+            attributes.push(doc_lines(description));
+        }
+
+        if let Some(map_constraints) = &map_constraints {
+            if description.is_some() {
+                attributes.push(quote! { #[doc = ""] });
             }
-            None => {}
-        };
 
-        match serde_options.skip_serializing_if {
-            Some(option) => {
-                attributes.push(quote! {
-                    #[serde(skip_serializing_if = #option)]
-                });
+            attributes.push(doc_lines(&describe_map_constraints(map_constraints)));
+        }
+
+        if !requires.is_empty() {
+            if description.is_some() || map_constraints.is_some() {
+                attributes.push(quote! { #[doc = ""] });
             }
+
+            let keys: Vec<String> = requires.iter().map(|x| format!("`{}`", x)).collect();
+            let requires = format!("Requires {}", keys.join(", "));
+            attributes.push(quote! { #[doc = #requires] });
+        }
+        let optional = serde_options.skip_serializing_if.is_some();
+        let custom = serde_options.with.is_some() || serde_options.deserialize_with.is_some();
+
+        if let Some(doc_alias) = serde_options
+            .rename
+            .as_ref()
+            .and_then(|rename| doc_alias(rename, &name))
+        {
+            attributes.push(quote! {
+                #[doc(alias = #doc_alias)]
+            });
+        };
+
+        if let Some(name) = serde_options.rename.filter(|_| !renamed_by_struct) {
+            attributes.push(quote! {
+                #[serde(rename = #name)]
+            });
+        };
+
+        for alias in serde_options.aliases {
+            attributes.push(quote! {
+                #[serde(alias = #alias)]
+            });
+        }
+
+        if serde_options.flatten {
+            attributes.push(quote! {
+                #[serde(flatten)]
+            });
+        }
+
+        if serde_options.skip_serializing {
+            attributes.push(quote! {
+                #[serde(skip_serializing)]
+            });
+        }
+
+        if serde_options.skip_deserializing {
+            attributes.push(quote! {
+                #[serde(skip_deserializing)]
+            });
+        }
+
+        if let Some(option) = serde_options.skip_serializing_if {
+            attributes.push(quote! {
+                #[serde(skip_serializing_if = #option)]
+            });
+        };
+
+        match serde_options.default {
+            Some(default) if default.is_empty() => attributes.push(quote! {
+                #[serde(default)]
+            }),
+            Some(default) => attributes.push(quote! {
+                #[serde(default = #default)]
+            }),
+            // Missing optional properties never reach a custom deserializer
+            None if custom && optional => attributes.push(quote! {
+                #[serde(default)]
+            }),
             None => {}
         };
 
+        if let Some(with) = serde_options.with {
+            attributes.push(quote! {
+                #[serde(with = #with)]
+            });
+        };
+
+        if let Some(deserialize_with) = serde_options.deserialize_with {
+            attributes.push(quote! {
+                #[serde(deserialize_with = #deserialize_with)]
+            });
+        };
+
         let name = proc_macro2::Ident::new(&name, Span::call_site());
         let property_type = property_type.parse::<TokenStream>().unwrap();
 
@@ -84,15 +1116,254 @@ impl Into<TokenStream> for GeneratedProperty {
 }
 
 #[derive(Eq, PartialEq, Debug)]
+pub struct GeneratedVariant {
+    pub name: String,
+    pub variant_type: String,
+}
+
+impl From<GeneratedVariant> for TokenStream {
+    fn from(val: GeneratedVariant) -> Self {
+        let name = proc_macro2::Ident::new(&val.name, Span::call_site());
+        let variant_type = val.variant_type.parse::<TokenStream>().unwrap();
+
+        quote! {
+            #name(#variant_type)
+        }
+    }
+}
+
+/// A variant of a [`TypeKind::TaggedEnum`], the object without its tag property
+#[derive(Eq, PartialEq, Debug)]
+pub struct TaggedVariant {
+    pub name: String,
+    /// Value of the tag property
+    pub value: String,
+    pub variant_type: String,
+}
+
+#[derive(Eq, PartialEq, Debug)]
+pub struct StringVariant {
+    pub name: String,
+    pub value: String,
+    pub description: Option<String>,
+}
+
+#[derive(Eq, PartialEq, Debug)]
+#[non_exhaustive]
 pub struct SerdeOptions {
     pub rename: Option<String>,
     pub skip_serializing_if: Option<String>,
+    /// Path of the function of the default value, empty for the `Default` of the type
+    pub default: Option<String>,
+    /// Module of the `serialize` and `deserialize` functions for the property
+    pub with: Option<String>,
+    pub deserialize_with: Option<String>,
+    /// Other keys the property is deserialized from
+    pub aliases: Vec<String>,
+    /// The property is a map of the keys no other property takes
+    pub flatten: bool,
+    /// The property is read but never written, for `readOnly`
+    pub skip_serializing: bool,
+    /// The property is written but never read, for `writeOnly`
+    pub skip_deserializing: bool,
+}
+
+/// `Default` of a struct with `default` values, which is derived if every property takes
+/// the `Default` of its type, and nothing if a property has no default
+fn default_impl(
+    name: &proc_macro2::Ident,
+    properties: &[GeneratedProperty],
+    defaults: &[GeneratedDefault],
+) -> (bool, TokenStream) {
+    let type_defaults = properties
+        .iter()
+        .any(|x| x.serde_options.default.as_deref() == Some(""));
+
+    if defaults.is_empty() && !type_defaults {
+        return (false, quote! {});
+    }
+
+    let mut derivable = true;
+    let mut fields = Vec::new();
+
+    for property in properties {
+        let value = match property.serde_options.default.as_deref() {
+            Some("") => quote! { Default::default() },
+            Some(function) => {
+                derivable = false;
+                let function = function.parse::<TokenStream>().unwrap();
+                quote! { #function() }
+            }
+            None if property.serde_options.flatten => quote! { Default::default() },
+            None if property.property_type.starts_with("Option<") => quote! { None },
+            None => return (false, quote! {}),
+        };
+        let field = proc_macro2::Ident::new(&property.name, Span::call_site());
+        fields.push(quote! { #field: #value });
+    }
+
+    match derivable {
+        true => (true, quote! {}),
+        false => (
+            false,
+            quote! {
+                impl Default for #name {
+                    fn default() -> Self {
+                        #name {
+                            #(#fields),*
+                        }
+                    }
+                }
+            },
+        ),
+    }
+}
+
+#[derive(Eq, PartialEq, Debug)]
+pub struct GeneratedConstant {
+    pub name: String,
+    pub constant_type: String,
+    pub value: Value,
+    pub default_function: String,
+    pub property_type: String,
+}
+
+impl From<GeneratedConstant> for TokenStream {
+    fn from(val: GeneratedConstant) -> Self {
+        val.into_tokens(MethodAttributes::default())
+    }
+}
+
+impl GeneratedConstant {
+    fn into_tokens(self, attributes: MethodAttributes) -> TokenStream {
+        let GeneratedConstant {
+            name,
+            constant_type,
+            value,
+            default_function,
+            property_type,
+        } = self;
+
+        let value = match &value {
+            Value::String(value) => Literal::string(value).into_token_stream(),
+            Value::Number(number) => match (number.as_i64(), number.as_u64()) {
+                (Some(number), _) if is_integer_type(&constant_type) => {
+                    Literal::i64_unsuffixed(number).into_token_stream()
+                }
+                (_, Some(number)) if is_integer_type(&constant_type) => {
+                    Literal::u64_unsuffixed(number).into_token_stream()
+                }
+                _ => {
+                    Literal::f64_unsuffixed(number.as_f64().unwrap_or_default()).into_token_stream()
+                }
+            },
+            _ => value.to_string().parse::<TokenStream>().unwrap(),
+        };
+
+        let (optional, inner_type) = match property_type.strip_prefix("Option<") {
+            Some(inner_type) => (true, inner_type.trim_end_matches('>')),
+            None => (false, property_type.as_str()),
+        };
+
+        let name = proc_macro2::Ident::new(&name, Span::call_site());
+        let default_function = proc_macro2::Ident::new(&default_function, Span::call_site());
+
+        let default_value = match inner_type {
+            "String" => quote! { String::from(Self::#name) },
+            _ if inner_type == constant_type => quote! { Self::#name },
+            // Untyped properties convert the constant into their value type
+            _ => {
+                let inner_type = inner_type.parse::<TokenStream>().unwrap();
+                quote! { #inner_type::from(Self::#name) }
+            }
+        };
+
+        let default_value = match optional {
+            true => quote! { Some(#default_value) },
+            false => default_value,
+        };
+
+        let property_type = property_type.parse::<TokenStream>().unwrap();
+        let constant_type = constant_type.parse::<TokenStream>().unwrap();
+        let method = attributes.method();
+
+        quote! {
+            pub const #name: #constant_type = #value;
+            #method
+            pub fn #default_function() -> #property_type {
+                #default_value
+            }
+        }
+    }
+}
+
+/// A `default` value, returned by the function a property is deserialized with when absent
+#[derive(Eq, PartialEq, Debug)]
+pub struct GeneratedDefault {
+    pub function: String,
+    pub property_type: String,
+    pub value: Value,
+}
+
+impl GeneratedDefault {
+    fn into_tokens(self, serde_json: &TokenStream, attributes: MethodAttributes) -> TokenStream {
+        let GeneratedDefault {
+            function,
+            property_type,
+            value,
+        } = self;
+
+        let (optional, inner_type) = match property_type.strip_prefix("Option<") {
+            Some(inner_type) => (true, &inner_type[..inner_type.len() - 1]),
+            None => (false, property_type.as_str()),
+        };
+
+        let default_value = match (&value, inner_type) {
+            (Value::String(value), "String") => quote! { String::from(#value) },
+            (Value::Bool(value), "bool") => quote! { #value },
+            (Value::Number(number), integer) if is_integer_type(integer) && number.is_i64() => {
+                Literal::i64_unsuffixed(number.as_i64().unwrap_or_default()).into_token_stream()
+            }
+            (Value::Number(number), integer) if is_integer_type(integer) && number.is_u64() => {
+                Literal::u64_unsuffixed(number.as_u64().unwrap_or_default()).into_token_stream()
+            }
+            (Value::Number(number), "f64") => {
+                Literal::f64_unsuffixed(number.as_f64().unwrap_or_default()).into_token_stream()
+            }
+            // The schema checks the value, so it deserializes into the type
+            _ => {
+                let json = value.to_string();
+                quote! { #serde_json::from_str(#json).unwrap() }
+            }
+        };
+
+        let default_value = match optional {
+            true => quote! { Some(#default_value) },
+            false => default_value,
+        };
+
+        let function = proc_macro2::Ident::new(&function, Span::call_site());
+        let property_type = property_type.parse::<TokenStream>().unwrap();
+        let method = attributes.method();
+
+        quote! {
+            #method
+            pub fn #function() -> #property_type {
+                #default_value
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod generated_tests {
-    use crate::generated::{GeneratedProperty, GeneratedType, SerdeOptions};
+    use crate::generated::{
+        GeneratedConstant, GeneratedProperty, GeneratedType, GeneratedVariant, SerdeOptions,
+        StringVariant, TypeKind,
+    };
+    use crate::parser::MapConstraints;
     use proc_macro2::TokenStream;
+    use serde_json::json;
 
     #[test]
     fn should_generate_valid_property_rust_code() {
@@ -104,12 +1375,80 @@ mod generated_tests {
         )
     }
 
+    #[test]
+    fn should_generate_doc_alias_for_renamed_property() {
+        let property = GeneratedProperty {
+            name: String::from("user_name"),
+            property_type: String::from("String"),
+            serde_options: SerdeOptions {
+                rename: Some(String::from("userName")),
+                skip_serializing_if: None,
+                default: None,
+                with: None,
+                deserialize_with: None,
+                aliases: vec![],
+                flatten: false,
+                skip_serializing: false,
+                skip_deserializing: false,
+            },
+            description: None,
+            map_constraints: None,
+            value_constraints: None,
+            requires: vec![],
+        };
+
+        let tokens: TokenStream = property.into();
+
+        assert_eq!(
+            tokens.to_string(),
+            String::from("# [doc (alias = \"userName\")] # [serde (rename = \"userName\")] pub user_name : String")
+        )
+    }
+
+    #[test]
+    fn should_generate_doc_alias_for_renamed_type() {
+        let alias_type = GeneratedType {
+            src: String::from("nirvana"),
+            name: String::from("UserId"),
+            doc_alias: Some(String::from("user-id")),
+            description: None,
+            derives: vec![],
+            removed_derives: vec![],
+            kind: TypeKind::Alias(String::from("String")),
+            constants: vec![],
+            defaults: vec![],
+            deny_unknown_fields: false,
+            properties: vec![],
+            examples: vec![],
+            module: vec![],
+            required_any: vec![],
+        };
+
+        let tokens: TokenStream = alias_type.into();
+
+        assert_eq!(
+            tokens.to_string(),
+            String::from("# [doc = \"Generated from nirvana\"] # [doc (alias = \"user-id\")] pub type UserId = String ;")
+        )
+    }
+
     #[test]
     fn should_generate_valid_struct_rust_code() {
         let struct_type = GeneratedType {
             src: String::from("nirvana"),
             name: String::from("new_name"),
+            doc_alias: None,
+            description: None,
+            derives: vec![],
+            removed_derives: vec![],
+            kind: TypeKind::Struct,
+            constants: vec![],
+            defaults: vec![],
+            deny_unknown_fields: false,
             properties: vec![create_property(), create_property()],
+            examples: vec![],
+            module: vec![],
+            required_any: vec![],
         };
 
         let tokens: TokenStream = struct_type.into();
@@ -120,6 +1459,216 @@ mod generated_tests {
         )
     }
 
+    #[test]
+    fn should_generate_valid_alias_rust_code() {
+        let alias_type = GeneratedType {
+            src: String::from("nirvana"),
+            name: String::from("UserId"),
+            doc_alias: None,
+            description: None,
+            derives: vec![],
+            removed_derives: vec![],
+            kind: TypeKind::Alias(String::from("Vec<String>")),
+            constants: vec![],
+            defaults: vec![],
+            deny_unknown_fields: false,
+            properties: vec![],
+            examples: vec![],
+            module: vec![],
+            required_any: vec![],
+        };
+
+        let tokens: TokenStream = alias_type.into();
+
+        assert_eq!(
+            tokens.to_string(),
+            String::from("# [doc = \"Generated from nirvana\"] pub type UserId = Vec < String > ;")
+        )
+    }
+
+    #[test]
+    fn should_generate_valid_newtype_rust_code() {
+        let newtype = GeneratedType {
+            src: String::from("nirvana"),
+            name: String::from("UserId"),
+            doc_alias: None,
+            description: None,
+            derives: vec![],
+            removed_derives: vec![],
+            kind: TypeKind::Newtype(String::from("String")),
+            constants: vec![],
+            defaults: vec![],
+            deny_unknown_fields: false,
+            properties: vec![],
+            examples: vec![],
+            module: vec![],
+            required_any: vec![],
+        };
+
+        let tokens: TokenStream = newtype.into();
+        let code = tokens.to_string();
+
+        assert!(code.contains("# [serde (transparent)] pub struct UserId (pub String) ;"));
+        assert!(code.contains("impl std :: fmt :: Display for UserId"));
+        assert!(code.contains("impl From < String > for UserId"));
+        assert!(code.contains("impl From < UserId > for String"));
+        assert!(code.contains("impl AsRef < String > for UserId"));
+    }
+
+    #[test]
+    fn should_generate_valid_constant_rust_code() {
+        let constant = GeneratedConstant {
+            name: String::from("KIND"),
+            constant_type: String::from("&str"),
+            value: json!("message"),
+            default_function: String::from("default_kind"),
+            property_type: String::from("Option<String>"),
+        };
+
+        let tokens: TokenStream = constant.into();
+
+        assert_eq!(
+            tokens.to_string(),
+            String::from("pub const KIND : & str = \"message\" ; pub fn default_kind () -> Option < String > { Some (String :: from (Self :: KIND)) }")
+        )
+    }
+
+    #[test]
+    fn should_generate_valid_wrapper_rust_code() {
+        let wrapper = GeneratedType {
+            src: String::from("nirvana"),
+            name: String::from("Envelope"),
+            doc_alias: None,
+            description: None,
+            derives: vec![],
+            removed_derives: vec![],
+            kind: TypeKind::Wrapper,
+            constants: vec![],
+            defaults: vec![],
+            deny_unknown_fields: false,
+            properties: vec![create_property()],
+            examples: vec![],
+            module: vec![],
+            required_any: vec![],
+        };
+
+        let tokens: TokenStream = wrapper.into();
+        let code = tokens.to_string();
+
+        assert!(code.contains("pub struct Envelope (pub String) ;"));
+        assert!(code.contains("impl std :: ops :: Deref for Envelope"));
+        assert!(code.contains("state . serialize_field (\"original name\" , & self . 0) ?"));
+        assert!(code.contains("# [serde (rename = \"original name\")] value : String"));
+    }
+
+    #[test]
+    fn should_generate_valid_untagged_enum_rust_code() {
+        let untagged_enum = GeneratedType {
+            src: String::from("nirvana"),
+            name: String::from("Status"),
+            doc_alias: None,
+            description: None,
+            derives: vec![],
+            removed_derives: vec![],
+            kind: TypeKind::UntaggedEnum(vec![
+                GeneratedVariant {
+                    name: String::from("Detail"),
+                    variant_type: String::from("Detail"),
+                },
+                GeneratedVariant {
+                    name: String::from("String"),
+                    variant_type: String::from("String"),
+                },
+            ]),
+            constants: vec![],
+            defaults: vec![],
+            deny_unknown_fields: false,
+            properties: vec![],
+            examples: vec![],
+            module: vec![],
+            required_any: vec![],
+        };
+
+        let tokens: TokenStream = untagged_enum.into();
+
+        assert_eq!(
+            tokens.to_string(),
+            String::from("# [doc = \"Generated from nirvana\"] # [derive (Clone , PartialEq , Debug , Deserialize , Serialize)] # [serde (untagged)] pub enum Status { Detail (Detail) , String (String) }")
+        )
+    }
+
+    fn string_enum(values: &[(&str, &str)]) -> String {
+        let string_enum = GeneratedType {
+            src: String::from("nirvana"),
+            name: String::from("Level"),
+            doc_alias: None,
+            description: None,
+            derives: vec![],
+            removed_derives: vec![],
+            kind: TypeKind::StringEnum(
+                values
+                    .iter()
+                    .map(|(name, value)| StringVariant {
+                        name: name.to_string(),
+                        value: value.to_string(),
+                        description: None,
+                    })
+                    .collect(),
+            ),
+            constants: vec![],
+            defaults: vec![],
+            deny_unknown_fields: false,
+            properties: vec![],
+            examples: vec![],
+            module: vec![],
+            required_any: vec![],
+        };
+
+        let tokens: TokenStream = string_enum.into();
+        tokens.to_string()
+    }
+
+    #[test]
+    fn should_rename_all_variants_which_follow_one_case() {
+        let code = string_enum(&[("DebugLevel", "DEBUG_LEVEL"), ("Info", "INFO")]);
+
+        assert!(code.contains(
+            "# [serde (rename_all = \"SCREAMING_SNAKE_CASE\")] pub enum Level { DebugLevel , Info , }"
+        ));
+
+        let code = string_enum(&[("DebugLevel", "DebugLevel"), ("Info", "Info")]);
+
+        assert!(code.contains("Serialize)] pub enum Level { DebugLevel , Info , }"));
+    }
+
+    #[test]
+    fn should_rename_each_variant_of_mixed_cases() {
+        let code = string_enum(&[("DebugLevel", "debug-level"), ("Info", "INFO")]);
+
+        assert!(code.contains("pub enum Level { # [serde (rename = \"debug-level\")] DebugLevel , # [serde (rename = \"INFO\")] Info , }"));
+        assert!(!code.contains("rename_all"));
+    }
+
+    #[test]
+    fn should_document_map_constraints_as_doc_attributes() {
+        let mut property = create_property();
+        property.map_constraints = Some(MapConstraints {
+            min_properties: Some(1),
+            max_properties: None,
+            key_pattern: None,
+            required_keys: vec![String::from("a\npub fn injected() {}")],
+        });
+
+        let tokens: TokenStream = property.into();
+
+        assert_eq!(
+            tokens.to_string(),
+            "# [doc = \"At least 1 entry, requiring `a\"] \
+             # [doc = \"pub fn injected() {}`\"] \
+             # [serde (rename = \"original name\")] pub new_name : String"
+        );
+    }
+
     fn create_property() -> GeneratedProperty {
         GeneratedProperty {
             name: String::from("new_name"),
@@ -127,7 +1676,18 @@ mod generated_tests {
             serde_options: SerdeOptions {
                 rename: Some(String::from("original name")),
                 skip_serializing_if: None,
+                default: None,
+                with: None,
+                deserialize_with: None,
+                aliases: vec![],
+                flatten: false,
+                skip_serializing: false,
+                skip_deserializing: false,
             },
+            description: None,
+            map_constraints: None,
+            value_constraints: None,
+            requires: vec![],
         }
     }
 }
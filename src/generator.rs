@@ -2,18 +2,51 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
 
-use crate::generated::{GeneratedProperty, GeneratedType, SerdeOptions};
+use crate::binary::{add_borsh_derives, binary_diagnostics, BinaryDiagnostic};
+use crate::builders::builders;
+use crate::compatibility::{check_compatibility, CompatibilityReport, Snapshot};
+use crate::conversions::variant_conversions;
+use crate::defaults::DefaultDiagnostic;
+use crate::emitters::sql::create_tables;
+use crate::error::Error;
+use crate::generated::{
+    GeneratedConstant, GeneratedDefault, GeneratedProperty, GeneratedType, GeneratedVariant,
+    SerdeOptions, StringVariant, TaggedVariant, TypeKind,
+};
+use crate::layout::box_variants;
+use crate::options::{
+    BytesStyle, CratePaths, EmptyObjectStyle, GeneratorOptions, MapType, MethodAttributes,
+    NamingStrategy, NullStyle, ScalarDefinitionStyle, SetType, SourceStyle, VariantBoxing,
+    Visibility,
+};
+use crate::ordering::{add_eq_hash_derives, add_ord_derives};
+#[cfg(feature = "fs")]
+use crate::output::write_files;
 use crate::parser::{
-    parse_from_file, AllOf, AnyOf, DataType, Object, ObjectProperty, OneOf, PrimitiveType, Ref,
-    Root,
+    integer_range, is_integer_type, try_parse_from_file, AllOf, AnyOf, DataType, EmptyObject,
+    KeyedMap, MapConstraints, MapKey, Object, ObjectProperty, OneOf, PrimitiveType, Ref, Root,
+    StringEnum, ValueConstraints,
 };
+use crate::plan::Plan;
+use crate::ref_parser::{child_src, remote_url, unescape_segment};
+use crate::report::Report;
 use crate::resolver::{ResolveResult, Resolver};
-use crate::sanitizer::{sanitize_property_name, sanitize_struct_name};
+use crate::round_trip::round_trip_tests;
+use crate::sanitizer::{
+    doc_alias, sanitize_module_name, sanitize_property_name, sanitize_struct_name,
+    sanitize_variant_name, sanitize_variant_names,
+};
+use crate::serde_helpers::serde_helpers;
+use crate::standalone::standalone_helpers;
+use crate::statistics::SchemaStatistics;
+use crate::subset::extract_subset;
+use crate::validation::{type_arguments, validate_methods};
 use proc_macro2::TokenStream;
 use quote::quote;
+use serde_json::Value;
 use std::cmp::Ordering;
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
 use std::rc::Rc;
 
 #[derive(Eq, PartialEq, Debug)]
@@ -34,932 +67,6863 @@ impl<T: Eq> PartialOrd for EntryWithPosition<T> {
     }
 }
 
+#[derive(PartialEq, Debug)]
+struct TypeOrigin {
+    file: String,
+    base_name: String,
+}
+
+/// A `oneOf` which can be told apart by the `const` value of a tag property
+struct Discriminated {
+    tag: String,
+    branches: Vec<TaggedBranch>,
+}
+
+struct TaggedBranch {
+    /// Root of the file the object is defined in
+    root: Rc<Root>,
+    src: String,
+    /// Whether the object is defined in the `oneOf` instead of referenced
+    inline: bool,
+    /// The object without the tag property
+    object: Object,
+    value: String,
+}
+
+/// Collects the types of all added schemas.
+///
+/// Types with colliding names get a numeric suffix. The unsuffixed name goes to
+/// the type with the highest precedence: types from files passed to
+/// [`Generator::add_file`] come before types from files which are only reached
+/// through a `$ref`, ties are broken alphabetically by file path and types
+/// within the same file keep the order in which they were discovered. This
+/// makes the names independent of the order in which files are added.
+///
+/// Recursive types are broken up with a `Box` once all types are known. Types
+/// are visited in alphabetical order of their names and their properties in
+/// declaration order. A property is boxed when it points back to a type which
+/// is still being visited, so the boxed edge of a cycle only depends on the
+/// names of the types in it.
 pub struct Generator {
     resolver: Resolver,
     types: HashMap<String, EntryWithPosition<GeneratedType>>,
     next_position: u64,
     known_type_names: HashMap<String, String>,
+    options: GeneratorOptions,
+    roots: Vec<Rc<Root>>,
+    type_origins: HashMap<String, TypeOrigin>,
+    assigned_names: HashMap<String, String>,
+    file_ids: HashMap<String, String>,
+    /// Roots of the properties merged from branches in other files, keyed by property source
+    property_roots: HashMap<String, Rc<Root>>,
+    /// Files other than their own which `$ref`s of the added files were resolved in
+    dependencies: BTreeSet<PathBuf>,
+    /// `$ref`s which could not be resolved, they are emitted as `Value` in the meantime
+    errors: Vec<Error>,
 }
 
-impl Into<Vec<GeneratedType>> for Generator {
-    fn into(self) -> Vec<GeneratedType> {
-        let mut types: Vec<EntryWithPosition<GeneratedType>> =
-            self.types.into_iter().map(|(_, value)| value).collect();
+impl From<Generator> for Vec<GeneratedType> {
+    fn from(val: Generator) -> Self {
+        val.resolve_names().take_types()
+    }
+}
 
-        types.sort();
+impl From<Generator> for TokenStream {
+    fn from(val: Generator) -> Self {
+        let options = val.options.clone();
+        to_token_stream(val.into(), &options)
+    }
+}
 
+fn to_token_stream(types: Vec<GeneratedType>, options: &GeneratorOptions) -> TokenStream {
+    let Rendered {
+        prelude,
+        types,
+        support,
+    } = render(types, options);
+    let types = nest_modules(
         types
             .into_iter()
-            .map(
-                |EntryWithPosition {
-                     payload,
-                     position: _,
-                 }| payload,
-            )
-            .collect()
+            .map(|(module, _, tokens)| (module, tokens))
+            .collect(),
+        &options.visibility.tokens(),
+    );
+
+    options.post_process(quote! {
+        #prelude
+        #types
+        #support
+    })
+}
+
+/// Emits the types of each module path in nested `pub mod`s, whose types are
+/// re-exported so types of other modules can refer to them without a path
+fn nest_modules(types: Vec<(Vec<String>, TokenStream)>, vis: &TokenStream) -> TokenStream {
+    let mut own = Vec::new();
+    let mut children: BTreeMap<String, Vec<(Vec<String>, TokenStream)>> = BTreeMap::new();
+
+    for (mut module, tokens) in types {
+        match module.is_empty() {
+            true => own.push(tokens),
+            false => {
+                let child = module.remove(0);
+                children.entry(child).or_default().push((module, tokens));
+            }
+        }
     }
+
+    let names: Vec<proc_macro2::Ident> = children
+        .keys()
+        .map(|x| proc_macro2::Ident::new(x, proc_macro2::Span::call_site()))
+        .collect();
+    let contents: Vec<TokenStream> = children
+        .into_values()
+        .map(|x| nest_modules(x, vis))
+        .collect();
+
+    quote! {
+        #(#own)*
+        #(
+            #vis mod #names {
+                use super::*;
+                #contents
+            }
+            #vis use #names::*;
+        )*
+    }
+}
+
+struct Rendered {
+    /// The `use` declarations every type relies on
+    prelude: TokenStream,
+    /// Module path, name and code of every type
+    types: Vec<(Vec<String>, String, TokenStream)>,
+    /// Code shared by all types, like helpers and tests
+    support: TokenStream,
 }
 
-impl Into<TokenStream> for Generator {
-    fn into(self) -> TokenStream {
-        let types: Vec<GeneratedType> = self.into();
+fn render(types: Vec<GeneratedType>, options: &GeneratorOptions) -> Rendered {
+    let paths = &options.crate_paths;
+    let tests = match options.round_trip_tests {
+        true => round_trip_tests(&types, paths),
+        false => quote! {},
+    };
+    let serde_helpers = serde_helpers(&types, paths);
+    let standalone_helpers = match options.standalone {
+        true => standalone_helpers(&types),
+        false => quote! {},
+    };
+    let conversions = variant_conversions(&types, options.method_attributes);
+    let builders = match options.builder_threshold {
+        Some(min_fields) => builders(
+            &types,
+            min_fields,
+            &options.visibility.tokens(),
+            options.method_attributes,
+        ),
+        None => vec![],
+    };
+    let validate_methods = validate_methods(&types, &options.visibility.tokens());
+    let helpers = match options.simd_json && !options.standalone {
+        true => simd_json_helpers(&types),
+        false => vec![],
+    };
+    let types = types
+        .into_iter()
+        .map(|x| {
+            (
+                x.module.clone(),
+                x.name.clone(),
+                x.into_tokens(paths, options.visibility, options.method_attributes),
+            )
+        })
+        .collect();
+    let serde = paths.serde();
+    let any_type = options.any_type_path();
+    let map = match options.effective_map_type() {
+        #[cfg(feature = "indexmap")]
+        MapType::IndexMap => quote! { indexmap },
+        _ => paths.collections(),
+    };
+    let map_type = options.effective_map_type().ident();
+    let collections = paths.collections();
+    let set_type = match options.set_type {
+        SetType::Vec => quote! {},
+        set_type => {
+            let set_type = proc_macro2::Ident::new(set_type.name(), proc_macro2::Span::call_site());
+            quote! { use #collections::#set_type; }
+        }
+    };
+
+    let prelude = match &options.header {
+        Some(header) => header.parse().unwrap(),
+        None => quote! {
+            use #serde::{Serialize, Deserialize};
+            use #any_type;
+            use #map::#map_type;
+            #set_type
+        },
+    };
 
-        let tokens: Vec<TokenStream> = types.into_iter().map(|x| x.into()).collect();
+    Rendered {
+        prelude,
+        types,
+        support: quote! {
+            #(#conversions)*
+            #(#builders)*
+            #validate_methods
+            #(#helpers)*
+            #serde_helpers
+            #standalone_helpers
+            #tests
+        },
+    }
+}
 
-        quote! {
-            use serde::{Serialize, Deserialize};
-            use serde_json::Value;
-            use std::collections::BTreeMap;
-            #(#tokens)*
+/// Adds a file for every type of the module and a `mod.rs` re-exporting them,
+/// the types of nested modules go into subdirectories.
+#[cfg(feature = "fs")]
+fn module_files(
+    dir: String,
+    header: TokenStream,
+    footer: TokenStream,
+    types: Vec<(Vec<String>, String, TokenStream)>,
+    vis: &TokenStream,
+    files: &mut Vec<(String, String)>,
+) {
+    let mut modules = Vec::new();
+    let mut children: BTreeMap<String, Vec<(Vec<String>, String, TokenStream)>> = BTreeMap::new();
+
+    for (mut module, name, tokens) in types {
+        if !module.is_empty() {
+            let child = module.remove(0);
+            children
+                .entry(child)
+                .or_default()
+                .push((module, name, tokens));
+            continue;
         }
+
+        let module = proc_macro2::Ident::new(
+            &sanitize_property_name(name),
+            proc_macro2::Span::call_site(),
+        );
+        let content = quote! {
+            use super::*;
+            #tokens
+        };
+        files.push((format!("{}{}.rs", dir, module), content.to_string()));
+        modules.push(module);
+    }
+
+    let mut child_modules = Vec::new();
+
+    for (child, types) in children {
+        let header = quote! { use super::*; };
+        module_files(
+            format!("{}{}/", dir, child),
+            header,
+            quote! {},
+            types,
+            vis,
+            files,
+        );
+        child_modules.push(proc_macro2::Ident::new(
+            &child,
+            proc_macro2::Span::call_site(),
+        ));
+    }
+
+    let content = quote! {
+        #header
+        #(
+            mod #modules;
+            #vis use #modules::*;
+        )*
+        #(
+            #vis mod #child_modules;
+            #vis use #child_modules::*;
+        )*
+        #footer
+    };
+    files.push((format!("{}mod.rs", dir), content.to_string()));
+}
+
+/// `simd_json` parses in place, so it needs a mutable buffer instead of a `&str`
+fn simd_json_helpers(types: &[GeneratedType]) -> Vec<TokenStream> {
+    types
+        .iter()
+        .filter(|x| {
+            matches!(
+                x.kind,
+                TypeKind::Struct
+                    | TypeKind::Wrapper
+                    | TypeKind::UntaggedEnum(_)
+                    | TypeKind::TaggedEnum { .. }
+            )
+        })
+        .map(|x| {
+            let name = proc_macro2::Ident::new(&x.name, proc_macro2::Span::call_site());
+
+            quote! {
+                impl #name {
+                    pub fn from_slice(data: &mut [u8]) -> simd_json::Result<Self> {
+                        simd_json::serde::from_slice(data)
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator::new()
     }
 }
 
 impl Generator {
     pub fn new() -> Self {
+        Generator::new_with_options(GeneratorOptions::default())
+    }
+
+    pub fn new_with_options(options: GeneratorOptions) -> Self {
         Generator {
             resolver: Resolver::new(),
             types: HashMap::new(),
             next_position: 0,
             known_type_names: HashMap::new(),
+            options,
+            roots: Vec::new(),
+            type_origins: HashMap::new(),
+            assigned_names: HashMap::new(),
+            file_ids: HashMap::new(),
+            property_roots: HashMap::new(),
+            dependencies: BTreeSet::new(),
+            errors: vec![],
         }
     }
 
-    pub fn add_file(&mut self, path: &Path) -> String {
-        match path.parent() {
-            Some(base_path) => {
-                let root = Rc::new(parse_from_file(path));
-                self.add(
-                    &base_path.display().to_string(),
-                    root.clone(),
-                    &root.data_type,
-                )
-            }
-            None => panic!("'{}' has no parent", path.display()),
-        }
+    pub fn options(&self) -> &GeneratorOptions {
+        &self.options
     }
 
-    pub fn add(&mut self, base_path: &String, root: Rc<Root>, data_type: &DataType) -> String {
-        self.add_type(base_path, root, None, data_type, false, Vec::new())
+    pub fn set_options(&mut self, options: GeneratorOptions) {
+        self.options = options;
     }
 
-    fn add_object(
-        &mut self,
-        base_path: &String,
-        root: Rc<Root>,
-        src: String,
-        Object {
-            src: _,
-            name,
-            properties,
-        }: &Object,
-        visited_objects: Vec<String>,
-    ) -> String {
-        let cycle_detected = visited_objects.contains(&src);
-        let mut visited_objects = visited_objects;
+    pub fn set_naming_strategy(&mut self, naming_strategy: NamingStrategy) {
+        self.options.naming_strategy = naming_strategy;
+    }
 
-        if cycle_detected {
-            visited_objects.clear();
-        }
+    pub fn set_namespace_by_file(&mut self, namespace_by_file: bool) {
+        self.options.namespace_by_file = namespace_by_file;
+    }
 
-        let name = match self.known_type_names.get(&src) {
-            Some(name) => name.clone(),
-            None => match self.types.get(&src) {
-                Some(EntryWithPosition {
-                    position: _,
-                    payload,
-                }) => payload.name.clone(),
-                None => {
-                    let position = self.next_position;
-                    self.next_position += 1;
-                    let name = self.get_collision_free_name(sanitize_struct_name(name.clone()));
-                    self.known_type_names.insert(src.clone(), name.clone());
-                    visited_objects.push(src.clone());
+    /// Keeps the names of referenced non-object definitions as aliases or newtypes,
+    /// instead of replacing the references by the referenced type
+    pub fn set_scalar_definition_style(&mut self, scalar_definition_style: ScalarDefinitionStyle) {
+        self.options.scalar_definition_style = scalar_definition_style;
+    }
 
-                    let mut new_properties = Vec::new();
+    pub fn set_null_style(&mut self, null_style: NullStyle) {
+        self.options.null_style = null_style;
+    }
 
-                    for property in properties as &Vec<ObjectProperty> {
-                        new_properties.push(self.create_property(
-                            base_path,
-                            root.clone(),
-                            &property,
-                            visited_objects.clone(),
-                        ));
-                    }
+    /// Uses the type at `path`, e.g. `serde_yaml::Value`, for untyped values instead of
+    /// `serde_json::Value`. Constants of untyped properties are converted with its `From`
+    /// implementations of `&str`, `i64`, `f64` and `bool`.
+    pub fn set_any_type(&mut self, path: &str) {
+        self.options.any_type = Some(String::from(path));
+    }
 
-                    let new_type = GeneratedType {
-                        src: src.clone(),
-                        name: name.clone(),
-                        properties: new_properties,
-                    };
+    pub fn set_empty_object_style(&mut self, empty_object_style: EmptyObjectStyle) {
+        self.options.empty_object_style = empty_object_style;
+    }
 
-                    self.types.insert(
-                        src,
-                        EntryWithPosition {
-                            position,
-                            payload: new_type,
-                        },
-                    );
+    /// Appends a `#[cfg(test)]` module checking that minimal documents round-trip
+    pub fn set_round_trip_tests(&mut self, round_trip_tests: bool) {
+        self.options.round_trip_tests = round_trip_tests;
+    }
 
-                    name
-                }
-            },
-        };
+    /// Adds `from_slice` constructors parsing with `simd_json`, and leaves out the flattened
+    /// `extra` map of [`Generator::set_extra_properties`], since `#[serde(flatten)]` buffers
+    /// the whole object. The generated types own their data either way, while untagged enums
+    /// still buffer their value to try the variants.
+    pub fn set_simd_json(&mut self, simd_json: bool) {
+        self.options.simd_json = simd_json;
+    }
 
-        match cycle_detected {
-            true => format!("Box<{}>", name),
-            false => name,
-        }
+    /// Derives `BorshSerialize` and `BorshDeserialize` for every type Borsh can encode
+    pub fn set_borsh(&mut self, borsh: bool) {
+        self.options.borsh = borsh;
     }
 
-    fn get_collision_free_name(&self, name: String) -> String {
-        let mut counter = 1;
-        let mut new_name = name.clone();
+    /// Adds a flattened `extra` map to objects which require names they have no property for,
+    /// which `validate` checks for them
+    pub fn set_extra_properties(&mut self, extra_properties: bool) {
+        self.options.extra_properties = extra_properties;
+    }
 
-        while self.known_type_names.values().any(|val| val == &new_name) {
-            new_name = format!("{}{}", name, counter);
-            counter += 1;
-        }
+    /// Derives `Eq`, `PartialOrd` and `Ord` for enums and for structs which only have ordered fields
+    pub fn set_ordering(&mut self, ordering: bool) {
+        self.options.ordering = ordering;
+    }
 
-        new_name
+    /// Skips serializing `readOnly` properties and deserializing `writeOnly` ones,
+    /// which become optional, so the same types can be sent to and read from an API
+    pub fn set_read_write_only(&mut self, read_write_only: bool) {
+        self.options.read_write_only = read_write_only;
     }
 
-    fn create_property(
-        &mut self,
-        base_path: &String,
-        root: Rc<Root>,
-        ObjectProperty {
-            name,
-            required,
-            data_type,
-        }: &ObjectProperty,
-        visited_objects: Vec<String>,
-    ) -> GeneratedProperty {
-        let property_name = sanitize_property_name(name.clone());
+    /// Emits a `FooBuilder` with a setter for every field and a `build` method,
+    /// which checks that the required fields are set, for structs of at least `min_fields` fields
+    pub fn set_builder_threshold(&mut self, min_fields: Option<usize>) {
+        self.options.builder_threshold = min_fields;
+    }
 
-        let rename = if name == &property_name {
-            None
-        } else {
-            Some(name.clone())
-        };
+    /// Emits strings and numbers with a `pattern`, length or bounds as newtypes,
+    /// like `pub struct Email(String);`, whose `TryFrom` rejects the values the schema forbids
+    pub fn set_constrained_newtypes(&mut self, constrained_newtypes: bool) {
+        self.options.constrained_newtypes = constrained_newtypes;
+    }
 
-        let skip_serializing_if = if *required {
-            None
-        } else {
-            Some(String::from("Option::is_none"))
-        };
+    /// Derives `Eq` and `Hash` for types which don't contain floats or untyped values,
+    /// directly or through other generated types
+    pub fn set_eq_hash(&mut self, eq_hash: bool) {
+        self.options.eq_hash = eq_hash;
+    }
 
-        GeneratedProperty {
-            name: property_name,
-            property_type: self.add_type(
-                base_path,
-                root,
-                None,
-                &*data_type,
-                required.clone(),
-                visited_objects,
-            ),
-            serde_options: SerdeOptions {
-                rename,
-                skip_serializing_if,
-            },
-        }
+    /// Accepts numbers given as strings (`"42"`) for integer and number properties
+    pub fn set_lenient_numbers(&mut self, lenient_numbers: bool) {
+        self.options.lenient_numbers = lenient_numbers;
     }
 
-    fn add_type(
-        &mut self,
-        base_path: &String,
-        root: Rc<Root>,
-        src_override: Option<String>,
-        data_type: &DataType,
-        required: bool,
-        visited_objects: Vec<String>,
-    ) -> String {
-        let type_name = match data_type {
-            DataType::PrimitiveType(primitive_type) => match primitive_type {
-                PrimitiveType::Null => String::from("Value"),
-                PrimitiveType::Boolean => String::from("bool"),
-                PrimitiveType::Integer => String::from("i64"),
-                PrimitiveType::Number => String::from("f64"),
-                PrimitiveType::String => String::from("String"),
-            },
-            DataType::Array(items) => {
-                let type_name =
-                    self.add_type(base_path, root, src_override, &*items, true, Vec::new());
-                format!("Vec<{}>", type_name)
-            }
-            DataType::Object(object) => self.add_object(
-                base_path,
-                root,
-                src_override.unwrap_or(object.src.to_string()),
-                object.clone(),
-                visited_objects,
-            ),
-            DataType::Map(data_type) => {
-                format!(
-                    "BTreeMap<String, {}>",
-                    self.add_type(base_path, root, None, data_type, true, Vec::new())
-                )
-            }
-            DataType::Ref(Ref { ref_path }) => {
-                let ResolveResult {
-                    root,
-                    path,
-                    data_type,
-                } = self.resolver.resolve(root, ref_path.clone());
-                let file = root.file.display().to_string();
+    /// Deserializes empty strings of optional string properties as `None`
+    pub fn set_empty_strings_as_none(&mut self, empty_strings_as_none: bool) {
+        self.options.empty_strings_as_none = empty_strings_as_none;
+    }
 
-                let src = match path {
-                    Some(path) => format!("{}#{}", file, path),
-                    None => file,
-                };
+    pub fn set_bytes_style(&mut self, bytes_style: BytesStyle) {
+        self.options.bytes_style = bytes_style;
+    }
 
-                self.add_type(
-                    &base_path,
-                    root,
-                    Some(src),
-                    &data_type,
-                    true,
-                    visited_objects,
-                )
-            }
-            DataType::OneOf(OneOf { types }) => {
-                for data_type in types {
-                    self.add(base_path, root.clone(), data_type.clone());
-                }
+    pub fn set_variant_boxing(&mut self, variant_boxing: VariantBoxing) {
+        self.options.variant_boxing = variant_boxing;
+    }
 
-                String::from("Value")
-            }
-            DataType::AnyOf(AnyOf { types }) => {
-                for data_type in types {
-                    self.add(base_path, root.clone(), data_type.clone());
-                }
+    /// Emits named string `enum`s as fieldless enums with `as_str` and `ALL`, instead of `String`s
+    pub fn set_string_enums(&mut self, string_enums: bool) {
+        self.options.string_enums = string_enums;
+    }
 
-                String::from("Value")
-            }
-            DataType::AllOf(AllOf { types }) => {
-                for data_type in types {
-                    self.add(base_path, root.clone(), data_type.clone());
-                }
+    /// Adds the [`Generator::binary_diagnostics`] to the [`Generator::report`]
+    pub fn set_binary_audit(&mut self, binary_audit: bool) {
+        self.options.binary_audit = binary_audit;
+    }
 
-                String::from("Value")
-            }
-            DataType::Any => String::from("Value"),
-        };
+    /// Adds the [`Generator::default_diagnostics`] to the [`Generator::report`]
+    pub fn set_verify_defaults(&mut self, verify_defaults: bool) {
+        self.options.verify_defaults = verify_defaults;
+    }
 
-        match required {
-            true => String::from(type_name),
-            false => format!("Option<{}>", type_name),
-        }
+    /// Emits the types of schemas with an `$id` in modules following the path of the `$id`,
+    /// e.g. `https://corp/schemas/billing/invoice.json` in `billing::invoice`
+    pub fn set_modules_by_id(&mut self, modules_by_id: bool) {
+        self.options.modules_by_id = modules_by_id;
     }
-}
 
-#[cfg(test)]
-mod generator_tests {
-    use crate::generator::{
-        EntryWithPosition, GeneratedProperty, GeneratedType, Generator, SerdeOptions,
-    };
-    use crate::parser::{
-        AllOf, AnyOf, DataType, Object, ObjectProperty, OneOf, PrimitiveType, Ref, Root,
-    };
-    use proc_macro2::TokenStream;
-    use std::collections::HashMap;
-    use std::path::Path;
-    use std::rc::Rc;
+    /// Emits the types of every schema file in a module named after the file,
+    /// e.g. `orders.schema.json` in `orders`, unless [`Generator::set_modules_by_id`] placed them
+    pub fn set_modules_by_file(&mut self, modules_by_file: bool) {
+        self.options.modules_by_file = modules_by_file;
+    }
 
-    #[test]
-    fn should_be_ordered_by_position() {
-        let mut list = vec![
-            EntryWithPosition {
-                payload: String::from("a"),
-                position: 3,
-            },
-            EntryWithPosition {
-                payload: String::from("b"),
-                position: 1,
-            },
-            EntryWithPosition {
-                payload: String::from("c"),
-                position: 2,
-            },
-        ];
+    /// Names the type of the schema at the source, like `schemas/user.json#/definitions/a`,
+    /// or at the JSON pointer, like `#/definitions/a` or `#` for the root of every file,
+    /// instead of the name derived from the schema.
+    pub fn set_type_name(&mut self, src: &str, name: &str) {
+        self.options
+            .type_names
+            .insert(String::from(src), String::from(name));
+    }
 
-        list.sort();
+    /// Emits one type for schemas at different locations which give the same fields
+    /// of the same types, instead of numbered copies like `Address` and `Address1`
+    pub fn set_merge_identical_types(&mut self, merge_identical_types: bool) {
+        self.options.merge_identical_types = merge_identical_types;
+    }
 
-        assert_eq!(
-            list,
-            vec![
-                EntryWithPosition {
-                    payload: String::from("b"),
-                    position: 1,
-                },
-                EntryWithPosition {
-                    payload: String::from("c"),
-                    position: 2,
-                },
-                EntryWithPosition {
-                    payload: String::from("a"),
-                    position: 3,
-                },
-            ]
-        );
+    /// Derives the given traits for every generated type,
+    /// e.g. `generator.add_derives(["Eq", "Hash"])`
+    pub fn add_derives<I, S>(&mut self, derives: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options
+            .derives
+            .extend(derives.into_iter().map(|x| x.into()));
     }
 
-    #[test]
-    fn should_serialize_with_serde_json_import() {
-        let generator = Generator::new();
-        let tokens: TokenStream = generator.into();
+    /// Removes derives from every generated type, including the ones every type of its kind
+    /// gets, e.g. `generator.remove_derives(["PartialEq"])`
+    pub fn remove_derives<I, S>(&mut self, derives: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options
+            .removed_derives
+            .extend(derives.into_iter().map(|x| x.into()));
+    }
 
-        assert_eq!(tokens.to_string().contains("use serde_json :: Value"), true)
+    /// Derives the given traits for the generated type with the name
+    pub fn add_type_derives<I, S>(&mut self, type_name: &str, derives: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options
+            .type_derives
+            .entry(String::from(type_name))
+            .or_default()
+            .extend(derives.into_iter().map(|x| x.into()));
     }
 
-    #[test]
-    fn should_serialize_with_btree_import() {
-        let generator = Generator::new();
-        let tokens: TokenStream = generator.into();
+    /// Removes derives from the generated type with the name, see [`Generator::remove_derives`]
+    pub fn remove_type_derives<I, S>(&mut self, type_name: &str, derives: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options
+            .removed_type_derives
+            .entry(String::from(type_name))
+            .or_default()
+            .extend(derives.into_iter().map(|x| x.into()));
+    }
 
-        assert_eq!(
-            tokens
-                .to_string()
-                .contains("use std :: collections :: BTreeMap"),
-            true
-        )
+    pub fn set_map_type(&mut self, map_type: MapType) {
+        self.options.map_type = map_type;
     }
 
-    #[test]
-    fn should_add_object() {
-        let mut generator = Generator::new();
+    /// The type of arrays with `uniqueItems`, which stay `Vec`s if their items can't be in the set
+    pub fn set_unique_items_type(&mut self, set_type: SetType) {
+        self.options.set_type = set_type;
+    }
 
-        let type_name = add_object(&mut generator);
+    pub fn set_visibility(&mut self, visibility: Visibility) {
+        self.options.visibility = visibility;
+    }
 
-        assert_eq!(type_name, "AwesomeFoo");
+    pub fn set_method_attributes(&mut self, method_attributes: MethodAttributes) {
+        self.options.method_attributes = method_attributes;
+    }
 
-        assert_eq!(
-            generator.types.get("correct src"),
-            Some(&EntryWithPosition {
-                position: 0,
-                payload: GeneratedType {
-                    src: String::from("correct src"),
-                    name: String::from("AwesomeFoo"),
-                    properties: vec![GeneratedProperty {
-                        name: String::from("awesome_property"),
-                        property_type: String::from("Option<Value>"),
-                        serde_options: SerdeOptions {
-                            rename: Some(String::from("awesome property")),
-                            skip_serializing_if: Some(String::from("Option::is_none")),
-                        },
-                    }],
-                },
-            })
-        )
+    /// Structs of objects whose `additionalProperties` is `false` accept unknown keys
+    /// instead of failing on them
+    pub fn set_allow_unknown_fields(&mut self, allow_unknown_fields: bool) {
+        self.options.allow_unknown_fields = allow_unknown_fields;
     }
 
-    #[test]
-    fn should_add_known_type() {
-        let mut generator = Generator::new();
+    /// Strings with the `format` get the type, like `uuid::Uuid` for `uuid`
+    pub fn set_format_type(&mut self, format: &str, type_path: &str) {
+        self.options
+            .formats
+            .insert(String::from(format), String::from(type_path));
+    }
 
-        add_object(&mut generator);
+    /// Emits code which only depends on `serde` and `serde_json`, see [`GeneratorOptions::standalone`]
+    pub fn set_standalone(&mut self, standalone: bool) {
+        self.options.standalone = standalone;
+    }
 
-        assert_eq!(
-            generator.known_type_names.get("correct src"),
-            Some(&String::from("AwesomeFoo"))
-        );
+    pub fn set_serialize_none(&mut self, serialize_none: bool) {
+        self.options.serialize_none = serialize_none;
     }
 
-    #[test]
-    fn should_detect_type_cycles() {
-        let mut generator = Generator::new();
-        generator
-            .known_type_names
-            .insert(String::from("correct src"), String::from("some type"));
+    pub fn set_header(&mut self, header: &str) {
+        self.options.header = Some(String::from(header));
+    }
 
-        let type_name = add_object(&mut generator);
+    pub fn set_crate_paths(&mut self, crate_paths: CratePaths) {
+        self.options.crate_paths = crate_paths;
+    }
 
-        assert_eq!(type_name, "some type");
+    pub fn set_collapse_wrappers(&mut self, collapse_wrappers: bool) {
+        self.options.collapse_wrappers = collapse_wrappers;
+    }
 
-        assert_eq!(generator.types.len(), 0)
+    pub fn set_source_style(&mut self, source_style: SourceStyle) {
+        self.options.source_style = source_style;
     }
 
-    #[test]
-    fn should_detect_reference_cycles() {
-        let mut generator = Generator::new();
+    pub fn collapse_wrapper(&mut self, pointer: &str) {
+        self.options
+            .collapsed_wrappers
+            .insert(String::from(pointer));
+    }
 
-        let type_name = generator.add_object(
-            &String::from(""),
-            Rc::new(Root {
-                file: Path::new("").to_path_buf(),
-                data_type: Rc::new(DataType::Any),
-                definitions: HashMap::new(),
-            }),
-            String::from("correct src"),
-            &object_with_property(),
-            vec![String::from("correct src")],
+    /// Refers to the types of a schema, given by file name or `$id`, by their path in
+    /// an existing module instead of generating them, e.g. `common.json#` to `common_models`
+    pub fn external_schema(&mut self, schema: &str, module: &str) {
+        self.options.external_schemas.insert(
+            String::from(schema.trim_end_matches('#')),
+            String::from(module.trim_end_matches("::")),
         );
+    }
 
-        assert_eq!(type_name, "Box<AwesomeFoo>");
-
-        assert_eq!(
-            generator.known_type_names.get("correct src"),
-            Some(&String::from("AwesomeFoo"))
-        );
+    /// Also accepts the alias as the key of the property, e.g. an old name during a migration
+    pub fn alias_property(&mut self, pointer: &str, alias: &str) {
+        self.options
+            .property_aliases
+            .entry(String::from(pointer))
+            .or_default()
+            .push(String::from(alias));
     }
 
-    #[test]
-    fn should_not_add_the_same_type_twice() {
-        let mut generator = Generator::new();
+    pub fn set_inline_object_threshold(&mut self, inline_object_threshold: Option<usize>) {
+        self.options.inline_object_threshold = inline_object_threshold;
+    }
 
-        let type_name = add_object(&mut generator);
-        assert_eq!(type_name, "AwesomeFoo");
+    pub fn inline_object(&mut self, pointer: &str) {
+        self.options.inlined_objects.insert(String::from(pointer));
+    }
 
-        let type_name = add_object(&mut generator);
-        assert_eq!(type_name, "AwesomeFoo");
+    /// Passes the generated code through the function, after the ones added before, e.g. to
+    /// wrap it in a module or to append impls. Only the code of a single file is passed,
+    /// not the files of [`Generator::generate_to_dir`].
+    pub fn add_post_processor(&mut self, post_processor: fn(TokenStream) -> TokenStream) {
+        self.options.post_processors.push(post_processor);
+    }
 
-        assert_eq!(generator.types.len(), 1);
+    /// Generates the code for all added files without consuming the generator,
+    /// so files can be added or removed afterwards.
+    pub fn generate(&mut self) -> TokenStream {
+        let types = self.resolved_types();
+        to_token_stream(types, &self.options)
+    }
 
-        assert_eq!(generator.known_type_names.len(), 1);
+    /// Like [`Generator::generate`], but returns an error instead of panicking if a
+    /// referenced file changed since it was added and a `$ref` no longer resolves,
+    /// or an option like the header can't be parsed.
+    pub fn generate_result(&mut self) -> Result<TokenStream, Error> {
+        let types = self.try_resolved_types()?;
+        Ok(to_token_stream(types, &self.options))
     }
 
-    #[test]
-    fn should_add_types_in_the_correct_order() {
-        let mut generator = Generator::new();
+    /// Writes every type into its own file of the directory, next to a `mod.rs`
+    /// re-exporting all of them.
+    ///
+    /// Files written by a previous run for types which no longer exist are removed
+    /// and returned.
+    #[cfg(feature = "fs")]
+    pub fn generate_to_dir(&mut self, dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+        self.options.validate().map_err(|error| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, error.to_string())
+        })?;
+
+        let Rendered {
+            prelude,
+            types,
+            support,
+        } = render(self.resolved_types(), &self.options);
 
-        generator.add_object(
-            &String::from(""),
-            Rc::new(Root {
-                file: Path::new("").to_path_buf(),
-                data_type: Rc::new(DataType::Any),
-                definitions: HashMap::new(),
-            }),
-            String::from("correct src"),
-            &Object {
-                src: String::from("wrong src"),
-                name: String::from("awesome foo"),
-                properties: vec![ObjectProperty {
-                    name: String::from("awesome property"),
-                    required: false,
-                    data_type: Rc::new(DataType::Object(Object {
-                        src: String::from("nested src"),
-                        name: String::from("awesome foo part 2"),
-                        properties: vec![ObjectProperty {
-                            name: String::from("awesome property part 2"),
-                            required: false,
-                            data_type: Rc::new(DataType::Any),
-                        }],
-                    })),
-                }],
-            },
-            Vec::new(),
-        );
+        let mut files = Vec::new();
+        let vis = self.options.visibility.tokens();
+        module_files(String::new(), prelude, support, types, &vis, &mut files);
 
-        assert_eq!(
-            generator.types.get("correct src").map(|x| x.position),
-            Some(0)
-        );
+        write_files(dir, &files)
+    }
 
-        assert_eq!(
-            generator.types.get("nested src").map(|x| x.position),
-            Some(1)
-        );
+    /// Captures the shape of the generated types, to be saved and passed to
+    /// [`Generator::check_compatibility`] once the schema changed.
+    pub fn snapshot(&mut self) -> Snapshot {
+        Snapshot::from(self.resolved_types().as_slice())
     }
 
-    fn add_object(generator: &mut Generator) -> String {
-        generator.add_object(
-            &String::from(""),
-            Rc::new(Root {
-                file: Path::new("").to_path_buf(),
-                data_type: Rc::new(DataType::Any),
-                definitions: HashMap::new(),
-            }),
-            String::from("correct src"),
-            &object_with_property(),
-            Vec::new(),
-        )
+    /// Classifies the changes between a previous snapshot and the current schemas.
+    pub fn check_compatibility(&mut self, previous: &Snapshot) -> CompatibilityReport {
+        check_compatibility(previous, &self.snapshot())
     }
 
-    fn object_with_property() -> Object {
-        object_with_custom_property(ObjectProperty {
-            name: String::from("awesome property"),
-            required: false,
-            data_type: Rc::new(DataType::Any),
-        })
+    /// Lists the types [`Generator::generate`] would emit with their sources and
+    /// the types they refer to, without generating any code.
+    pub fn plan(&mut self) -> Plan {
+        Plan::from(self.resolved_types().as_slice())
     }
 
-    fn object_with_custom_property(property: ObjectProperty) -> Object {
-        Object {
-            src: String::from("wrong src"),
-            name: String::from("awesome foo"),
-            properties: vec![property],
-        }
+    /// Lists the constructs of the generated types which Borsh, bincode
+    /// or positional CBOR and MessagePack encodings cannot represent.
+    pub fn binary_diagnostics(&mut self) -> Vec<BinaryDiagnostic> {
+        let types = self.resolved_types();
+        binary_diagnostics(&types, self.options.any_type_label())
     }
 
-    #[test]
-    fn should_add_null_type() {
-        let mut generator = Generator::new();
+    /// The added files and the local files they refer to, which the generated code depends on.
+    pub fn files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = self
+            .roots
+            .iter()
+            .map(|root| root.file.clone())
+            .chain(self.dependencies())
+            .filter(|file| remote_url(&file.display().to_string()).is_none())
+            .collect();
+        files.sort();
+        files.dedup();
+        files
+    }
 
-        assert_eq!(
-            add_primitive_type(&mut generator, PrimitiveType::Null, true),
-            String::from("Value")
-        );
+    /// Every file which `$ref`s of the added files, directly or through other files, were
+    /// resolved in, including the URLs of remote ones. Files which are no longer referenced
+    /// since they were loaded are left out.
+    pub fn dependencies(&self) -> Vec<PathBuf> {
+        self.dependencies.iter().cloned().collect()
     }
 
-    #[test]
-    fn should_add_bool_type() {
-        let mut generator = Generator::new();
+    /// Lists every `default` of the added and referenced files which doesn't satisfy its own subschema.
+    pub fn default_diagnostics(&self) -> Vec<DefaultDiagnostic> {
+        let mut roots: Vec<&Rc<Root>> = self.roots.iter().chain(self.resolver.loaded()).collect();
+        roots.sort_by_key(|root| &root.file);
+        roots.dedup_by_key(|root| &root.file);
 
-        assert_eq!(
-            add_primitive_type(&mut generator, PrimitiveType::Boolean, true),
-            String::from("bool")
-        );
+        roots
+            .into_iter()
+            .flat_map(|root| &root.invalid_defaults)
+            .map(|(src, message)| DefaultDiagnostic {
+                src: src.clone(),
+                message: message.clone(),
+            })
+            .collect()
     }
 
-    #[test]
-    fn should_add_integer_type() {
-        let mut generator = Generator::new();
+    /// Summarizes what [`Generator::generate`] would emit, with the findings of the enabled audits.
+    pub fn report(&mut self) -> Report {
+        let types = self.resolved_types();
 
-        assert_eq!(
-            add_primitive_type(&mut generator, PrimitiveType::Integer, true),
-            String::from("i64")
-        );
+        Report {
+            types: Plan::from(types.as_slice()).types,
+            dependencies: self.dependencies(),
+            binary_diagnostics: match self.options.binary_audit {
+                true => binary_diagnostics(&types, self.options.any_type_label()),
+                false => vec![],
+            },
+            default_diagnostics: match self.options.verify_defaults {
+                true => self.default_diagnostics(),
+                false => vec![],
+            },
+        }
     }
 
-    #[test]
-    fn should_add_number_type() {
-        let mut generator = Generator::new();
-
-        assert_eq!(
-            add_primitive_type(&mut generator, PrimitiveType::Number, true),
-            String::from("f64")
-        );
+    /// Sketches `CREATE TABLE` statements for the flat structs [`Generator::generate`] would emit.
+    pub fn sql(&mut self) -> String {
+        create_tables(&self.resolved_types())
     }
 
-    #[test]
-    fn should_add_string_type() {
-        let mut generator = Generator::new();
+    /// Counts the contents of every file added with [`Generator::add_file`].
+    pub fn statistics(&self) -> Vec<SchemaStatistics> {
+        self.roots
+            .iter()
+            .map(|root| SchemaStatistics::from(root.as_ref()))
+            .collect()
+    }
 
-        assert_eq!(
-            add_primitive_type(&mut generator, PrimitiveType::String, true),
-            String::from("String")
-        );
+    fn resolved_types(&mut self) -> Vec<GeneratedType> {
+        self.try_resolved_types()
+            .unwrap_or_else(|error| panic!("{}", error))
     }
 
-    #[test]
-    fn should_add_optional_string_type() {
+    fn try_resolved_types(&mut self) -> Result<Vec<GeneratedType>, Error> {
+        self.options.validate()?;
+
         let mut generator = Generator::new();
+        generator.resolver = std::mem::replace(&mut self.resolver, Resolver::new());
+        generator.options = self.options.clone();
+        generator.roots = self.roots.clone();
 
-        assert_eq!(
-            add_primitive_type(&mut generator, PrimitiveType::String, false),
-            String::from("Option<String>")
-        );
+        for root in &self.roots {
+            generator.add_root(root.clone());
+        }
+
+        if let Some(error) = std::mem::take(&mut generator.errors).into_iter().next() {
+            self.resolver = generator.resolver;
+            return Err(error);
+        }
+
+        let mut generator = generator.resolve_names();
+        let types = generator.take_types();
+        self.resolver = generator.resolver;
+
+        Ok(types)
     }
 
-    fn add_primitive_type(
-        generator: &mut Generator,
-        primitive_type: PrimitiveType,
-        required: bool,
-    ) -> String {
-        add_type(generator, DataType::PrimitiveType(primitive_type), required)
+    /// Forgets all added files but keeps the already loaded referenced files.
+    pub fn reset(&mut self) {
+        self.types.clear();
+        self.next_position = 0;
+        self.known_type_names.clear();
+        self.roots.clear();
+        self.type_origins.clear();
+        self.assigned_names.clear();
+        self.file_ids.clear();
+        self.property_roots.clear();
+        self.dependencies.clear();
+        self.errors.clear();
     }
 
-    #[test]
-    fn should_add_array_type() {
-        let mut generator = Generator::new();
+    /// Removes a file added with [`Generator::add_file`] and the types which
+    /// were only reachable from it. Returns `false` if the file was not added.
+    pub fn remove(&mut self, path: &Path) -> bool {
+        let roots = std::mem::take(&mut self.roots);
+        let (removed, roots): (Vec<Rc<Root>>, Vec<Rc<Root>>) = roots
+            .into_iter()
+            .partition(|root| root.file == path || root.file == path.with_extension("json"));
 
-        let type_name = add_type(
-            &mut generator,
-            DataType::Array(Rc::new(DataType::Any)),
-            true,
-        );
+        self.reset();
 
-        assert_eq!(type_name, "Vec<Value>");
+        for root in roots {
+            self.roots.push(root.clone());
+            self.add_root(root.clone());
+        }
+
+        !removed.is_empty()
     }
 
-    #[test]
-    fn should_add_object_type() {
-        let mut generator = Generator::new();
+    /// Drops a cached referenced file, so it is read again the next time it is referenced.
+    pub fn invalidate(&mut self, path: &Path) {
+        self.resolver.invalidate(path);
+    }
 
-        let type_name = add_type(
-            &mut generator,
-            DataType::Object(object_with_property()),
-            true,
-        );
+    /// Uses `content` instead of the file at `path`, e.g. for unsaved editor buffers.
+    /// Files which were already added have to be removed and added again.
+    pub fn set_overlay(&mut self, path: &Path, content: String) {
+        self.resolver.set_overlay(path, content);
+    }
 
-        assert_eq!(type_name, "AwesomeFoo");
+    pub fn remove_overlay(&mut self, path: &Path) {
+        self.resolver.remove_overlay(path);
     }
 
-    #[test]
-    fn should_add_optional_object_type() {
-        let mut generator = Generator::new();
+    /// Downloads the schemas of `$ref`s to `http(s)://` URLs with `fetch`, e.g. a `ureq` or
+    /// blocking `reqwest` call returning the body. Every URL is downloaded once, an overlay
+    /// of the URL is used instead. Without it, remote references fail to resolve.
+    pub fn set_fetch(&mut self, fetch: impl Fn(&str) -> Result<String, String> + 'static) {
+        self.resolver.set_fetch(Box::new(fetch));
+    }
 
-        let type_name = add_type(
-            &mut generator,
-            DataType::Object(object_with_property()),
-            false,
-        );
+    /// Copies the schema the pointer of the file points to, like `#/definitions/User`, into a
+    /// self-contained schema with every schema it refers to in its `$defs`, e.g. to share a
+    /// single model of a large specification.
+    pub fn extract_subset(&self, path: &Path, pointer: &str) -> Result<Value, Error> {
+        extract_subset(path, pointer, self.resolver.overlay())
+    }
 
-        assert_eq!(type_name, "Option<AwesomeFoo>");
+    /// Adds only the types of the [`Generator::extract_subset`] of the file, as an overlay
+    /// named after the last segment of the pointer next to the file, e.g. `User.subset.json`.
+    pub fn try_add_subset(&mut self, path: &Path, pointer: &str) -> Result<String, Error> {
+        let subset = self.extract_subset(path, pointer)?;
+        let name = match pointer.rsplit('/').next() {
+            Some(segment) if !segment.is_empty() && segment != "#" => unescape_segment(segment),
+            _ => file_stem(path),
+        };
+        let subset_path = path.with_file_name(format!("{}.subset.json", name));
+
+        self.set_overlay(&subset_path, subset.to_string());
+        self.try_add_file(&subset_path)
     }
 
-    #[test]
-    fn should_add_map_type() {
-        let mut generator = Generator::new();
+    pub fn add_file(&mut self, path: &Path) -> String {
+        self.try_add_file(path)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
 
-        let type_name = add_type(&mut generator, DataType::Map(Rc::new(DataType::Any)), true);
+    /// Adds a schema which isn't read from the file system, e.g. one embedded with
+    /// `include_str!`. The name stands in for its path: sources are named after it
+    /// and relative `$ref`s are resolved against it.
+    pub fn add_str(&mut self, name: &str, schema: &str) -> String {
+        self.try_add_str(name, schema)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
 
-        assert_eq!(type_name, "BTreeMap<String, Value>");
+    /// Like [`Generator::add_str`], but returns an error like [`Generator::try_add_file`]
+    pub fn try_add_str(&mut self, name: &str, schema: &str) -> Result<String, Error> {
+        let path = Path::new(name);
+        self.set_overlay(path, String::from(schema));
+        self.try_add_file(path)
     }
 
+    /// Adds a schema built in code, see [`Generator::add_str`]
+    pub fn add_value(&mut self, name: &str, schema: Value) -> String {
+        self.add_str(name, &schema.to_string())
+    }
+
+    /// Like [`Generator::add_value`], but returns an error like [`Generator::try_add_file`]
+    pub fn try_add_value(&mut self, name: &str, schema: Value) -> Result<String, Error> {
+        self.try_add_str(name, &schema.to_string())
+    }
+
+    /// Like [`Generator::add_file`], but returns an error if the file can't be read or parsed,
+    /// or if one of its `$ref`s can't be resolved. The generator is left as if the file was
+    /// never added.
+    pub fn try_add_file(&mut self, path: &Path) -> Result<String, Error> {
+        let root = Rc::new(try_parse_from_file(path, self.resolver.overlay())?);
+        self.errors.clear();
+        self.roots.push(root.clone());
+        let type_name = self.add_root(root.clone());
+
+        match std::mem::take(&mut self.errors).into_iter().next() {
+            Some(error) => {
+                self.remove(&root.file);
+                self.errors.clear();
+                Err(error)
+            }
+            None => Ok(type_name),
+        }
+    }
+
+    fn resolve_names(self) -> Generator {
+        if self.roots.is_empty() {
+            return self;
+        }
+
+        let root_files: HashSet<String> = self
+            .roots
+            .iter()
+            .map(|root| root.file.display().to_string())
+            .collect();
+
+        let mut candidates: Vec<(bool, &String, u64, &String, &String)> = self
+            .types
+            .iter()
+            .filter_map(|(src, entry)| {
+                self.type_origins.get(src).map(|origin| {
+                    (
+                        !root_files.contains(&origin.file),
+                        &origin.file,
+                        entry.position,
+                        src,
+                        &origin.base_name,
+                    )
+                })
+            })
+            .collect();
+
+        candidates.sort();
+
+        let mut assigned_names = HashMap::new();
+        let mut taken_names: HashSet<String> = candidates
+            .iter()
+            .filter_map(|(_, _, _, src, _)| self.options.type_name(src).cloned())
+            .collect();
+
+        for (_, _, _, src, base_name) in candidates {
+            if let Some(name) = self.options.type_name(src) {
+                assigned_names.insert(src.clone(), name.clone());
+                continue;
+            }
+
+            let mut counter = 1;
+            let mut name = base_name.clone();
+
+            while taken_names.contains(&name) || self.options.is_reserved_type_name(&name) {
+                name = format!("{}{}", base_name, counter);
+                counter += 1;
+            }
+
+            taken_names.insert(name.clone());
+            assigned_names.insert(src.clone(), name);
+        }
+
+        let mut generator = Generator::new();
+        generator.resolver = self.resolver;
+        generator.options = self.options;
+        generator.assigned_names = assigned_names;
+
+        for root in self.roots {
+            generator.add_root(root.clone());
+        }
+
+        generator
+    }
+
+    fn take_types(&mut self) -> Vec<GeneratedType> {
+        let mut types: Vec<EntryWithPosition<GeneratedType>> =
+            std::mem::take(&mut self.types).into_values().collect();
+
+        types.sort();
+
+        let mut modules = match self.options.modules_by_id {
+            true => id_modules(&self.file_ids),
+            false => HashMap::new(),
+        };
+
+        if self.options.modules_by_file {
+            for TypeOrigin { file, base_name: _ } in self.type_origins.values() {
+                modules
+                    .entry(file.clone())
+                    .or_insert_with(|| vec![sanitize_module_name(file_stem(Path::new(file)))]);
+            }
+        }
+
+        let mut types: Vec<GeneratedType> = types
+            .into_iter()
+            .map(
+                |EntryWithPosition {
+                     mut payload,
+                     position: _,
+                 }| {
+                    if let Some(TypeOrigin { file, base_name: _ }) =
+                        self.type_origins.get(&payload.src)
+                    {
+                        if let Some(module) = modules.get(file) {
+                            payload.module = module.clone();
+                        }
+                        payload.src = render_source(
+                            &payload.src,
+                            file,
+                            &self.options.source_style,
+                            &self.file_ids,
+                        );
+                    }
+                    payload
+                },
+            )
+            .collect();
+
+        if self.options.merge_identical_types {
+            merge_identical_types(&mut types);
+        }
+
+        place_boxes(&mut types);
+        box_variants(&mut types, self.options.variant_boxing);
+
+        for generated_type in types.iter_mut() {
+            generated_type.add_derives(&self.options.derives);
+
+            if let Some(derives) = self.options.type_derives.get(&generated_type.name) {
+                generated_type.add_derives(derives);
+            }
+
+            let removed = self.options.removed_type_derives.get(&generated_type.name);
+            generated_type.removed_derives = self
+                .options
+                .removed_derives
+                .iter()
+                .chain(removed.into_iter().flatten())
+                .cloned()
+                .collect();
+        }
+
+        if self.options.eq_hash {
+            add_eq_hash_derives(&mut types);
+        }
+
+        if self.options.ordering {
+            add_ord_derives(&mut types);
+        }
+
+        if self.options.borsh && !self.options.standalone {
+            add_borsh_derives(&mut types, self.options.any_type_label());
+        }
+
+        types
+    }
+
+    pub(crate) fn add(&mut self, root: Rc<Root>, data_type: &DataType) -> String {
+        self.add_type(root, None, data_type, false, None)
+    }
+
+    /// Root maps get a newtype, so they have a name like root objects
+    fn add_root(&mut self, root: Rc<Root>) -> String {
+        let empty_object_map = match &*root.data_type {
+            DataType::EmptyObject(empty_object)
+                if self.options.empty_object_style != EmptyObjectStyle::Struct
+                    || empty_object.name.is_none() =>
+            {
+                Some(self.empty_object_map(empty_object))
+            }
+            _ => None,
+        };
+        let (key, values) = match empty_object_map.as_ref().unwrap_or(&root.data_type) {
+            DataType::Map(values) => (None, values),
+            DataType::KeyedMap(KeyedMap { key, values }) => (Some(key), values),
+            _ => return self.add(root.clone(), &root.data_type),
+        };
+        let src = root.file.display().to_string();
+
+        if let Some(name) = self.known_type_names.get(&src) {
+            return name.clone();
+        }
+
+        let position = self.next_position;
+        self.next_position += 1;
+        let base_name = match &root.title {
+            Some(title) => sanitize_struct_name(title.clone()),
+            None => sanitize_struct_name(file_stem(&root.file)),
+        };
+        let type_name = match self.assigned_names.get(&src) {
+            Some(name) => name.clone(),
+            None => self.get_collision_free_name(base_name.clone()),
+        };
+        self.record_origin(&root, &src, base_name);
+        self.known_type_names.insert(src.clone(), type_name.clone());
+
+        let key = match key {
+            Some(key) => self.add_map_key(&root, key),
+            None => String::from("String"),
+        };
+        let name_hint = Some(format!("{} value", type_name));
+        let value = self.add_type(root.clone(), None, values, true, name_hint);
+
+        self.types.insert(
+            src.clone(),
+            EntryWithPosition {
+                position,
+                payload: GeneratedType {
+                    src,
+                    name: type_name.clone(),
+                    doc_alias: None,
+                    description: None,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::Map {
+                        map_type: self.options.effective_map_type(),
+                        key,
+                        value,
+                    },
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
+                    properties: vec![],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
+                },
+            },
+        );
+
+        type_name
+    }
+
+    fn add_object(
+        &mut self,
+        root: Rc<Root>,
+        src: String,
+        Object {
+            src: _,
+            name,
+            title,
+            description,
+            properties,
+            required_any,
+            unmatched_required,
+            deny_unknown_fields,
+        }: &Object,
+        name_hint: Option<String>,
+    ) -> String {
+        match self.known_type_names.get(&src) {
+            Some(name) => name.clone(),
+            None => match self.types.get(&src) {
+                Some(EntryWithPosition {
+                    position: _,
+                    payload,
+                }) => payload.name.clone(),
+                None => {
+                    let position = self.next_position;
+                    self.next_position += 1;
+                    let object_name = name.clone();
+                    let base_name = match (title, name_hint) {
+                        (None, Some(name_hint)) => name_hint,
+                        _ if self.options.namespace_by_file => {
+                            format!("{} {}", file_stem(&root.file), name)
+                        }
+                        _ => name.clone(),
+                    };
+                    let base_name = sanitize_struct_name(base_name);
+                    let name = match self.assigned_names.get(&src) {
+                        Some(name) => name.clone(),
+                        None => self.get_collision_free_name(base_name.clone()),
+                    };
+                    self.record_origin(&root, &src, base_name.clone());
+                    self.known_type_names.insert(src.clone(), name.clone());
+
+                    let mut new_properties = Vec::new();
+                    let mut constants = Vec::new();
+                    let mut defaults = Vec::new();
+
+                    for property in properties as &Vec<ObjectProperty> {
+                        let property_src = child_src(&src, &["properties", &property.name]);
+                        let property_root = match self.property_roots.get(&property_src) {
+                            Some(property_root) => property_root.clone(),
+                            None => root.clone(),
+                        };
+                        let new_property =
+                            self.create_property(property_root, property, &name, &property_src);
+
+                        if let Some(value) = &property.constant {
+                            if let Some(constant_type) = constant_type(
+                                value,
+                                &new_property.property_type,
+                                self.options.any_type_name(),
+                            ) {
+                                constants.push(GeneratedConstant {
+                                    name: new_property.name.to_uppercase(),
+                                    constant_type,
+                                    value: value.clone(),
+                                    default_function: format!("default_{}", new_property.name),
+                                    property_type: new_property.property_type.clone(),
+                                });
+                            }
+                        }
+
+                        if let (None, Some(value), Some(function)) = (
+                            &property.constant,
+                            &property.default,
+                            &new_property.serde_options.default,
+                        ) {
+                            if !function.is_empty() {
+                                defaults.push(GeneratedDefault {
+                                    function: format!("default_{}", new_property.name),
+                                    property_type: new_property.property_type.clone(),
+                                    value: value.clone(),
+                                });
+                            }
+                        }
+
+                        new_properties.push(new_property);
+                    }
+
+                    if self.options.extra_properties
+                        && !self.options.simd_json
+                        && !unmatched_required.is_empty()
+                    {
+                        let extra = self.extra_property(&new_properties, unmatched_required);
+                        new_properties.push(extra);
+                    }
+
+                    // serde rejects every key with `deny_unknown_fields` once a property is flattened
+                    let deny_unknown_fields = *deny_unknown_fields
+                        && !self.options.allow_unknown_fields
+                        && !new_properties.iter().any(|x| x.serde_options.flatten);
+
+                    let collapse = new_properties.len() == 1
+                        && !new_properties[0].serde_options.flatten
+                        && constants.is_empty()
+                        && (self.options.collapse_wrappers
+                            || self.options.collapsed_wrappers.contains(&src));
+
+                    let original_name = match (title, object_name.as_str()) {
+                        (None, "Unknown") => None,
+                        _ => doc_alias(&object_name, &base_name),
+                    };
+
+                    let new_type = GeneratedType {
+                        src: src.clone(),
+                        name: name.clone(),
+                        doc_alias: original_name,
+                        description: description.clone(),
+                        derives: vec![],
+                        removed_derives: vec![],
+                        kind: match collapse {
+                            true => TypeKind::Wrapper,
+                            false => TypeKind::Struct,
+                        },
+                        constants,
+                        defaults,
+                        deny_unknown_fields,
+                        properties: new_properties,
+                        examples: root
+                            .examples
+                            .get(&src)
+                            .iter()
+                            .flat_map(|x| x.iter())
+                            .map(|x| x.to_string())
+                            .collect(),
+                        module: vec![],
+                        required_any: required_any.clone(),
+                    };
+
+                    self.types.insert(
+                        src,
+                        EntryWithPosition {
+                            position,
+                            payload: new_type,
+                        },
+                    );
+
+                    name
+                }
+            },
+        }
+    }
+
+    fn add_alias(
+        &mut self,
+        root: Rc<Root>,
+        src: String,
+        name: String,
+        data_type: &DataType,
+        kind: impl FnOnce(String) -> TypeKind,
+    ) -> String {
+        if let Some(name) = self.known_type_names.get(&src) {
+            return name.clone();
+        }
+
+        let definition_name = name.clone();
+
+        let position = self.next_position;
+        self.next_position += 1;
+        let base_name = match self.options.namespace_by_file {
+            true => format!("{} {}", file_stem(&root.file), name),
+            false => name,
+        };
+        let base_name = sanitize_struct_name(base_name);
+        let name = match self.assigned_names.get(&src) {
+            Some(name) => name.clone(),
+            None => self.get_collision_free_name(base_name.clone()),
+        };
+        self.record_origin(&root, &src, base_name);
+        self.known_type_names.insert(src.clone(), name.clone());
+
+        let target = self.add_type(root, None, data_type, true, None);
+
+        self.types.insert(
+            src.clone(),
+            EntryWithPosition {
+                position,
+                payload: GeneratedType {
+                    src,
+                    name: name.clone(),
+                    doc_alias: doc_alias(&definition_name, &name),
+                    description: None,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: kind(target),
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
+                    properties: vec![],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
+                },
+            },
+        );
+
+        name
+    }
+
+    fn add_null_marker(&mut self, root: &Root) -> String {
+        let src = String::from("null");
+
+        if let Some(name) = self.known_type_names.get(&src) {
+            return name.clone();
+        }
+
+        let position = self.next_position;
+        self.next_position += 1;
+        let base_name = String::from("Null");
+        let name = match self.assigned_names.get(&src) {
+            Some(name) => name.clone(),
+            None => self.get_collision_free_name(base_name.clone()),
+        };
+        self.record_origin(root, &src, base_name);
+        self.known_type_names.insert(src.clone(), name.clone());
+
+        self.types.insert(
+            src.clone(),
+            EntryWithPosition {
+                position,
+                payload: GeneratedType {
+                    src,
+                    name: name.clone(),
+                    doc_alias: None,
+                    description: None,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::Null,
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
+                    properties: vec![],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
+                },
+            },
+        );
+
+        name
+    }
+
+    fn add_map_key(&mut self, root: &Root, MapKey { src, name, pattern }: &MapKey) -> String {
+        if let Some(name) = self.known_type_names.get(src) {
+            return name.clone();
+        }
+
+        let position = self.next_position;
+        self.next_position += 1;
+        let base_name = match self.options.namespace_by_file {
+            true => format!("{} {}", file_stem(&root.file), name),
+            false => name.clone(),
+        };
+        let base_name = sanitize_struct_name(base_name);
+        let type_name = match self.assigned_names.get(src) {
+            Some(name) => name.clone(),
+            None => self.get_collision_free_name(base_name.clone()),
+        };
+        self.record_origin(root, src, base_name);
+        self.known_type_names.insert(src.clone(), type_name.clone());
+
+        self.types.insert(
+            src.clone(),
+            EntryWithPosition {
+                position,
+                payload: GeneratedType {
+                    src: src.clone(),
+                    name: type_name.clone(),
+                    doc_alias: None,
+                    description: None,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::MapKey(pattern.clone()),
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
+                    properties: vec![],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
+                },
+            },
+        );
+
+        type_name
+    }
+
+    fn add_string_enum(
+        &mut self,
+        root: &Root,
+        src: String,
+        StringEnum {
+            name,
+            description,
+            values,
+            descriptions,
+            ..
+        }: &StringEnum,
+    ) -> String {
+        if let Some(name) = self.known_type_names.get(&src) {
+            return name.clone();
+        }
+
+        let position = self.next_position;
+        self.next_position += 1;
+        let base_name = match self.options.namespace_by_file {
+            true => format!("{} {}", file_stem(&root.file), name),
+            false => name.to_string(),
+        };
+        let base_name = sanitize_struct_name(base_name);
+        let type_name = match self.assigned_names.get(&src) {
+            Some(name) => name.clone(),
+            None => self.get_collision_free_name(base_name.clone()),
+        };
+        self.record_origin(root, &src, base_name);
+        self.known_type_names.insert(src.clone(), type_name.clone());
+
+        let variants: Vec<StringVariant> = sanitize_variant_names(values)
+            .into_iter()
+            .zip(values.iter().zip(descriptions))
+            .map(|(name, (value, description))| StringVariant {
+                name,
+                value: value.clone(),
+                description: description.clone(),
+            })
+            .collect();
+
+        self.types.insert(
+            src.clone(),
+            EntryWithPosition {
+                position,
+                payload: GeneratedType {
+                    src,
+                    name: type_name.clone(),
+                    doc_alias: None,
+                    description: description.clone(),
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::StringEnum(variants),
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
+                    properties: vec![],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
+                },
+            },
+        );
+
+        type_name
+    }
+
+    /// The path of the type in the module the schema of the root is mapped to,
+    /// by [`Generator::external_schema`]
+    fn external_type(
+        &self,
+        root: &Root,
+        path: &Option<String>,
+        data_type: &DataType,
+    ) -> Option<String> {
+        let module = self
+            .options
+            .external_schemas
+            .iter()
+            .find(|(schema, _)| {
+                root.id.as_deref().map(|x| x.trim_end_matches('#')) == Some(schema.as_str())
+                    || root.file.ends_with(schema)
+            })
+            .map(|(_, module)| module)?;
+        let name = match (path, data_type) {
+            (Some(path), _) => definition_name(path),
+            (None, DataType::Object(object)) => object.name.clone(),
+            (None, _) => file_stem(&root.file),
+        };
+
+        Some(format!("{}::{}", module, sanitize_struct_name(name)))
+    }
+
+    fn record_origin(&mut self, root: &Root, src: &str, base_name: String) {
+        let file = root.file.display().to_string();
+
+        if let Some(id) = &root.id {
+            self.file_ids.insert(file.clone(), id.clone());
+        }
+
+        self.type_origins
+            .insert(src.to_string(), TypeOrigin { file, base_name });
+    }
+
+    /// Emits a `oneOf` or `anyOf` as an untagged enum with a variant for each alternative
+    fn add_one_of(
+        &mut self,
+        root: Rc<Root>,
+        src: String,
+        name: &str,
+        types: &[DataType],
+        patterns: &[String],
+    ) -> String {
+        if let Some(name) = self.known_type_names.get(&src) {
+            return name.clone();
+        }
+
+        let position = self.next_position;
+        self.next_position += 1;
+        let base_name = match self.options.namespace_by_file {
+            true => format!("{} {}", file_stem(&root.file), name),
+            false => name.to_string(),
+        };
+        let base_name = sanitize_struct_name(base_name);
+        let type_name = match self.assigned_names.get(&src) {
+            Some(type_name) => type_name.clone(),
+            None => self.get_collision_free_name(base_name.clone()),
+        };
+        self.record_origin(&root, &src, base_name.clone());
+        self.known_type_names.insert(src.clone(), type_name.clone());
+
+        let mut variants: Vec<GeneratedVariant> = Vec::new();
+
+        for (i, data_type) in types.iter().enumerate() {
+            let name_hint = Some(format!("{} variant {}", type_name, i + 1));
+            let variant_type = self.add_type(root.clone(), None, data_type, true, name_hint);
+            let variant_name = variant_name(&variant_type, self.options.any_type_name());
+            let mut unique_name = variant_name.clone();
+            let mut counter = 1;
+
+            while variants.iter().any(|x| x.name == unique_name) {
+                unique_name = format!("{}{}", variant_name, counter);
+                counter += 1;
+            }
+
+            variants.push(GeneratedVariant {
+                name: unique_name,
+                variant_type,
+            });
+        }
+
+        let description = match patterns.is_empty() {
+            true => None,
+            false => Some(
+                std::iter::once(String::from("The value of a key matching"))
+                    .chain(patterns.iter().zip(&variants).map(|(pattern, variant)| {
+                        format!("- `{}` is a [`{}::{}`]", pattern, type_name, variant.name)
+                    }))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            ),
+        };
+
+        self.types.insert(
+            src.clone(),
+            EntryWithPosition {
+                position,
+                payload: GeneratedType {
+                    src,
+                    name: type_name.clone(),
+                    doc_alias: doc_alias(name, &base_name),
+                    description,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::UntaggedEnum(variants),
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
+                    properties: vec![],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
+                },
+            },
+        );
+
+        type_name
+    }
+
+    /// The tag property and the branches of a `oneOf` of objects which all require
+    /// the same property with a distinct `const` string, without that property
+    fn discriminate(&mut self, root: &Rc<Root>, types: &[DataType]) -> Option<Discriminated> {
+        let mut objects: Vec<(Rc<Root>, String, bool, Object)> = Vec::new();
+
+        for data_type in types {
+            match data_type {
+                DataType::Object(object) => {
+                    objects.push((root.clone(), object.src.clone(), true, object.clone()))
+                }
+                DataType::Ref(reference) => {
+                    let ResolveResult {
+                        root: target_root,
+                        data_type,
+                        ..
+                    } = self.resolve(root.clone(), reference);
+
+                    match &*data_type {
+                        DataType::Object(object) => objects.push((
+                            target_root,
+                            reference.src.clone(),
+                            false,
+                            object.clone(),
+                        )),
+                        _ => return None,
+                    }
+                }
+                _ => return None,
+            }
+        }
+
+        let tag_value = |object: &Object, tag: &str| {
+            object
+                .properties
+                .iter()
+                .find(|x| x.name == tag && x.required)
+                .and_then(|x| match &x.constant {
+                    Some(Value::String(value)) => Some(value.clone()),
+                    _ => None,
+                })
+        };
+
+        let tag = objects.first()?.3.properties.iter().find_map(|property| {
+            let values: Vec<String> = objects
+                .iter()
+                .filter_map(|(_, _, _, object)| tag_value(object, &property.name))
+                .collect();
+            let distinct = values.iter().collect::<HashSet<&String>>().len();
+
+            match values.len() == objects.len() && distinct == values.len() {
+                true => Some(property.name.clone()),
+                false => None,
+            }
+        })?;
+
+        let branches = objects
+            .into_iter()
+            .map(|(root, src, inline, mut object)| {
+                let value = tag_value(&object, &tag).unwrap_or_default();
+                object.properties.retain(|x| x.name != tag);
+
+                TaggedBranch {
+                    root,
+                    src,
+                    inline,
+                    object,
+                    value,
+                }
+            })
+            .collect();
+
+        Some(Discriminated { tag, branches })
+    }
+
+    fn add_tagged_enum(
+        &mut self,
+        root: Rc<Root>,
+        src: String,
+        name: &str,
+        Discriminated { tag, branches }: Discriminated,
+    ) -> String {
+        if let Some(name) = self.known_type_names.get(&src) {
+            return name.clone();
+        }
+
+        let position = self.next_position;
+        self.next_position += 1;
+        let base_name = match self.options.namespace_by_file {
+            true => format!("{} {}", file_stem(&root.file), name),
+            false => name.to_string(),
+        };
+        let base_name = sanitize_struct_name(base_name);
+        let type_name = match self.assigned_names.get(&src) {
+            Some(type_name) => type_name.clone(),
+            None => self.get_collision_free_name(base_name.clone()),
+        };
+        self.record_origin(&root, &src, base_name.clone());
+        self.known_type_names.insert(src.clone(), type_name.clone());
+
+        let values: Vec<String> = branches.iter().map(|x| x.value.clone()).collect();
+        let mut variants: Vec<TaggedVariant> = Vec::new();
+
+        for (variant_name, branch) in sanitize_variant_names(&values).into_iter().zip(branches) {
+            let TaggedBranch {
+                root,
+                src,
+                inline,
+                object,
+                value,
+            } = branch;
+            // Referenced objects keep the name of their definition
+            let name_hint = match inline {
+                true => Some(format!("{} {}", variant_name, type_name)),
+                false => None,
+            };
+            let variant_type = self.add_object(root, src, &object, name_hint);
+
+            variants.push(TaggedVariant {
+                name: variant_name,
+                value,
+                variant_type,
+            });
+        }
+
+        self.types.insert(
+            src.clone(),
+            EntryWithPosition {
+                position,
+                payload: GeneratedType {
+                    src,
+                    name: type_name.clone(),
+                    doc_alias: doc_alias(name, &base_name),
+                    description: None,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::TaggedEnum { tag, variants },
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
+                    properties: vec![],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
+                },
+            },
+        );
+
+        type_name
+    }
+
+    /// Combines `allOf` branches which are objects, or refs to objects, into one object.
+    /// Properties keep the order in which they first appear, are required if any branch
+    /// requires them and take the first type which is not `Any`. Branches without any
+    /// properties are skipped. The roots to resolve the properties against are returned
+    /// next to them, as they differ for branches of other files. The merged object is named
+    /// like the `allOf` itself, as referenced branches keep their names for their own types.
+    fn merge_objects(
+        &mut self,
+        root: &Rc<Root>,
+        AllOf { src, name, types }: &AllOf,
+    ) -> Option<(Object, Vec<Rc<Root>>)> {
+        let mut branches: Vec<(bool, Object, Vec<Rc<Root>>)> = Vec::new();
+
+        for data_type in types {
+            let (inline, root, data_type) = match data_type {
+                DataType::Ref(reference) => {
+                    let ResolveResult {
+                        root: target_root,
+                        data_type,
+                        ..
+                    } = self.resolve(root.clone(), reference);
+
+                    (false, target_root, data_type)
+                }
+                _ => (true, root.clone(), Rc::new(data_type.clone())),
+            };
+
+            match &*data_type {
+                DataType::Object(object) => {
+                    let roots = vec![root; object.properties.len()];
+                    branches.push((inline, object.clone(), roots));
+                }
+                DataType::AllOf(all_of) => {
+                    let (object, roots) = self.merge_objects(&root, all_of)?;
+                    branches.push((false, object, roots));
+                }
+                DataType::Any => {}
+                DataType::Map(values) if **values == DataType::Any => {}
+                DataType::EmptyObject(_) => {}
+                _ => return None,
+            }
+        }
+
+        let (_, first, _) = branches.first()?;
+        let mut merged = Object {
+            src: String::from(src),
+            name: name.clone().unwrap_or_else(|| String::from("Unknown")),
+            title: None,
+            description: first.description.clone(),
+            properties: Vec::new(),
+            required_any: vec![],
+            unmatched_required: vec![],
+            deny_unknown_fields: false,
+        };
+        let mut roots: Vec<Rc<Root>> = Vec::new();
+        let mut unmatched_required: Vec<String> = Vec::new();
+
+        for (inline, object, object_roots) in branches {
+            if inline {
+                merged.src = object.src.clone();
+
+                if object.title.is_some() || object.name != "Unknown" {
+                    merged.name = object.name.clone();
+                    merged.title = object.title.clone();
+                }
+
+                if object.description.is_some() {
+                    merged.description = object.description.clone();
+                }
+            }
+
+            // The merged struct has the properties of all branches, which a strict branch allows
+            merged.deny_unknown_fields |= object.deny_unknown_fields;
+
+            for name in object.unmatched_required {
+                if !unmatched_required.contains(&name) {
+                    unmatched_required.push(name);
+                }
+            }
+
+            for (property, property_root) in object.properties.into_iter().zip(object_roots) {
+                match merged
+                    .properties
+                    .iter()
+                    .position(|x| x.name == property.name)
+                {
+                    Some(index) => {
+                        let existing = &mut merged.properties[index];
+                        existing.required |= property.required;
+
+                        if *existing.data_type == DataType::Any {
+                            existing.data_type = property.data_type.clone();
+                            roots[index] = property_root;
+                        }
+
+                        if existing.constant.is_none() {
+                            existing.constant = property.constant.clone();
+                        }
+
+                        if existing.description.is_none() {
+                            existing.description = property.description.clone();
+                        }
+                    }
+                    None => {
+                        merged.properties.push(property);
+                        roots.push(property_root);
+                    }
+                }
+            }
+        }
+
+        // Branches may require the properties of other branches
+        for name in unmatched_required {
+            match merged.properties.iter_mut().find(|x| x.name == name) {
+                Some(property) => property.required = true,
+                None => merged.unmatched_required.push(name),
+            }
+        }
+
+        Some((merged, roots))
+    }
+
+    /// A flattened map of the unknown properties, which `validate` checks for the required ones
+    fn extra_property(
+        &self,
+        properties: &[GeneratedProperty],
+        required_keys: &[String],
+    ) -> GeneratedProperty {
+        let mut name = String::from("extra");
+
+        while properties.iter().any(|x| x.name == name) {
+            name.push('_');
+        }
+
+        GeneratedProperty {
+            name,
+            property_type: format!(
+                "{}<String, {}>",
+                self.options.effective_map_type().name(),
+                self.options.any_type_name()
+            ),
+            serde_options: SerdeOptions {
+                rename: None,
+                skip_serializing_if: None,
+                default: None,
+                with: None,
+                deserialize_with: None,
+                aliases: vec![],
+                flatten: true,
+                skip_serializing: false,
+                skip_deserializing: false,
+            },
+            description: None,
+            map_constraints: Some(MapConstraints {
+                min_properties: None,
+                max_properties: None,
+                key_pattern: None,
+                required_keys: required_keys.to_vec(),
+            }),
+            value_constraints: None,
+            requires: vec![],
+        }
+    }
+
+    /// The map an object without properties is emitted as, unless it becomes a struct
+    fn empty_object_map(
+        &self,
+        EmptyObject {
+            key,
+            additional_properties,
+            ..
+        }: &EmptyObject,
+    ) -> DataType {
+        let values = match (self.options.empty_object_style, additional_properties) {
+            (EmptyObjectStyle::AdditionalProperties, Some(values)) => values.clone(),
+            _ => Rc::new(DataType::Any),
+        };
+
+        match key {
+            Some(key) => DataType::KeyedMap(KeyedMap {
+                key: key.clone(),
+                values,
+            }),
+            None => DataType::Map(values),
+        }
+    }
+
+    fn is_inlined(&self, src: &str, object: &Object) -> bool {
+        if self.options.inlined_objects.contains(src) {
+            return true;
+        }
+
+        let anonymous = object.title.is_none() && is_property_src(src);
+
+        match self.options.inline_object_threshold {
+            Some(threshold) => anonymous && object.properties.len() <= threshold,
+            None => false,
+        }
+    }
+
+    fn add_inline_map(
+        &mut self,
+        root: Rc<Root>,
+        Object { properties, .. }: &Object,
+        name_hint: Option<String>,
+    ) -> String {
+        let value_type = match properties.split_first() {
+            Some((first, rest)) if rest.iter().all(|x| x.data_type == first.data_type) => {
+                self.add_type(root, None, &first.data_type, true, name_hint)
+            }
+            _ => String::from(self.options.any_type_name()),
+        };
+
+        format!(
+            "{}<String, {}>",
+            self.options.effective_map_type().name(),
+            value_type
+        )
+    }
+
+    /// Whether the type of the items is `Ord` and `Hash`, so they fit in both kinds of sets
+    fn is_set_item(&mut self, root: Rc<Root>, data_type: &DataType) -> bool {
+        match data_type {
+            DataType::PrimitiveType(
+                PrimitiveType::Boolean
+                | PrimitiveType::Integer
+                | PrimitiveType::SizedInteger(_)
+                | PrimitiveType::String,
+            )
+            | DataType::StringEnum(_) => true,
+            DataType::PrimitiveType(PrimitiveType::Format(format)) => {
+                self.options.format_type(format) == "String"
+            }
+            // Newtypes and types of other crates don't derive the traits
+            DataType::Ref(_)
+                if self.options.scalar_definition_style == ScalarDefinitionStyle::Newtype =>
+            {
+                false
+            }
+            DataType::Ref(reference) => {
+                let ResolveResult {
+                    root,
+                    path,
+                    data_type,
+                } = self.resolve(root, reference);
+
+                self.external_type(&root, &path, &data_type).is_none()
+                    && self.is_set_item(root, &data_type)
+            }
+            _ => false,
+        }
+    }
+
+    fn resolve(&mut self, root: Rc<Root>, Ref { src, ref_path, .. }: &Ref) -> ResolveResult {
+        match self.resolver.resolve(root.clone(), ref_path.clone()) {
+            Ok(result) => {
+                if result.root.file != root.file {
+                    self.dependencies.insert(result.root.file.clone());
+                }
+
+                result
+            }
+            Err(message) => {
+                self.errors.push(Error::Resolve {
+                    location: root.describe(&child_src(src, &["$ref"])),
+                    message,
+                });
+
+                ResolveResult {
+                    root,
+                    path: None,
+                    data_type: Rc::new(DataType::Any),
+                }
+            }
+        }
+    }
+
+    fn get_collision_free_name(&self, name: String) -> String {
+        let mut counter = 1;
+        let mut new_name = name.clone();
+
+        while self.known_type_names.values().any(|val| val == &new_name)
+            || self.options.is_reserved_type_name(&new_name)
+        {
+            new_name = format!("{}{}", name, counter);
+            counter += 1;
+        }
+
+        new_name
+    }
+
+    fn create_property(
+        &mut self,
+        root: Rc<Root>,
+        ObjectProperty {
+            name,
+            required,
+            constant,
+            default,
+            data_type,
+            description,
+            map_constraints,
+            value_constraints,
+            requires,
+            aliases,
+            read_only,
+            write_only,
+        }: &ObjectProperty,
+        parent_name: &str,
+        src: &str,
+    ) -> GeneratedProperty {
+        let property_name = sanitize_property_name(name.clone());
+        let mut aliases = aliases.clone();
+        let read_only = *read_only && self.options.read_write_only;
+        // Values which are never read are absent when the type is deserialized
+        let write_only = *write_only && self.options.read_write_only;
+
+        // Absent properties take a valid `default`, so they are always present
+        let default_value = match (constant, default, &**data_type) {
+            _ if write_only => None,
+            (_, _, DataType::PrimitiveType(PrimitiveType::Bytes)) => None,
+            (None, Some(value), _)
+                if !value.is_null() && !root.invalid_defaults.contains_key(src) =>
+            {
+                Some(value)
+            }
+            _ => None,
+        };
+        let required = (*required || default_value.is_some()) && !write_only;
+
+        for alias in self.options.property_aliases.get(src).into_iter().flatten() {
+            if !aliases.contains(alias) {
+                aliases.push(alias.clone());
+            }
+        }
+
+        let rename = if name == &property_name {
+            None
+        } else {
+            Some(name.clone())
+        };
+
+        let skip_serializing_if = if required || read_only || self.options.serialize_none {
+            None
+        } else {
+            Some(String::from("Option::is_none"))
+        };
+
+        let name_hint = match self.options.naming_strategy {
+            NamingStrategy::PropertyName => None,
+            NamingStrategy::PropertyPath => Some(format!(
+                "{}{}",
+                parent_name,
+                sanitize_struct_name(name.clone())
+            )),
+        };
+
+        let newtype_name = name_hint.clone().unwrap_or_else(|| name.clone());
+        let mut property_type = self.add_type(root.clone(), None, data_type, required, name_hint);
+        let mut with = None;
+        let mut deserialize_with = None;
+        let helper = |name: &str| match required {
+            true => format!("serde_helpers::{}", name),
+            false => format!("serde_helpers::option_{}", name),
+        };
+
+        match &**data_type {
+            DataType::PrimitiveType(PrimitiveType::Bytes) => {
+                let bytes_type = match self.options.effective_bytes_style() {
+                    BytesStyle::Base64 | BytesStyle::SerdeBytes => "Vec<u8>",
+                    BytesStyle::Bytes => "bytes::Bytes",
+                };
+                property_type = match required {
+                    true => String::from(bytes_type),
+                    false => format!("Option<{}>", bytes_type),
+                };
+                with = match self.options.effective_bytes_style() {
+                    BytesStyle::Base64 => Some(helper("base64")),
+                    BytesStyle::SerdeBytes => Some(String::from("serde_bytes")),
+                    BytesStyle::Bytes => None,
+                };
+            }
+            DataType::PrimitiveType(PrimitiveType::Integer | PrimitiveType::SizedInteger(_))
+                if self.options.lenient_numbers =>
+            {
+                deserialize_with = Some(helper("i64_from_string_or_number"));
+            }
+            DataType::PrimitiveType(PrimitiveType::Number) if self.options.lenient_numbers => {
+                deserialize_with = Some(helper("f64_from_string_or_number"));
+            }
+            DataType::PrimitiveType(PrimitiveType::String | PrimitiveType::Format(_))
+            | DataType::StringEnum(_)
+                if property_type == "Option<String>" && self.options.empty_strings_as_none =>
+            {
+                deserialize_with = Some(String::from("serde_helpers::empty_string_as_none"));
+            }
+            _ => {}
+        }
+
+        let default_function = format!("{}::default_{}", parent_name, property_name);
+        let default = match constant
+            .as_ref()
+            .and_then(|value| constant_type(value, &property_type, self.options.any_type_name()))
+        {
+            Some(_) => Some(default_function),
+            None => default_value.map(|value| match is_type_default(value, &property_type) {
+                true => String::new(),
+                false => default_function,
+            }),
+        };
+
+        let mut value_constraints = value_constraints
+            .as_ref()
+            .and_then(|x| self.fitting_constraints(x, data_type, &property_type));
+
+        // The newtype checks the constraints instead of the `validate` method of the struct
+        let checked_by_newtype = self.options.constrained_newtypes
+            && with.is_none()
+            && deserialize_with.is_none()
+            && matches!(&**data_type, DataType::PrimitiveType(_));
+
+        if let (true, Some(constraints)) = (checked_by_newtype, &value_constraints) {
+            let constraints = constraints.clone();
+            let newtype = self.add_alias(
+                root,
+                src.to_string(),
+                newtype_name,
+                data_type,
+                |value_type| TypeKind::Constrained {
+                    value_type,
+                    constraints,
+                },
+            );
+            property_type = match required {
+                true => newtype,
+                false => format!("Option<{}>", newtype),
+            };
+            value_constraints = None;
+        }
+
+        GeneratedProperty {
+            name: property_name,
+            property_type,
+            serde_options: SerdeOptions {
+                rename,
+                skip_serializing_if,
+                default,
+                with,
+                deserialize_with,
+                aliases,
+                flatten: false,
+                skip_serializing: read_only,
+                skip_deserializing: write_only,
+            },
+            description: description.clone(),
+            map_constraints: map_constraints.clone(),
+            value_constraints,
+            requires: requires.clone(),
+        }
+    }
+
+    /// The constraints a newtype of the string or number checks, if it becomes one
+    fn newtype_constraints(
+        &mut self,
+        root: &Rc<Root>,
+        src: &str,
+        data_type: &DataType,
+    ) -> Option<ValueConstraints> {
+        let constraints = match self.options.constrained_newtypes {
+            true => root.constraints.get(src)?,
+            false => return None,
+        };
+
+        match data_type {
+            DataType::PrimitiveType(
+                PrimitiveType::String
+                | PrimitiveType::Format(_)
+                | PrimitiveType::Integer
+                | PrimitiveType::SizedInteger(_)
+                | PrimitiveType::Number,
+            ) => {
+                let type_name = self.add_type(root.clone(), None, data_type, true, None);
+                self.fitting_constraints(constraints, data_type, &type_name)
+            }
+            _ => None,
+        }
+    }
+
+    /// The constraints the `validate` method can check on the type of the property,
+    /// without the bounds an integer type already implies
+    fn fitting_constraints(
+        &self,
+        constraints: &ValueConstraints,
+        data_type: &DataType,
+        property_type: &str,
+    ) -> Option<ValueConstraints> {
+        let value_type = property_type
+            .strip_prefix("Option<")
+            .and_then(|x| x.strip_suffix('>'))
+            .unwrap_or(property_type);
+        let constraints = match data_type {
+            DataType::PrimitiveType(PrimitiveType::String | PrimitiveType::Format(_))
+                if value_type == "String" =>
+            {
+                ValueConstraints {
+                    pattern: constraints.pattern.clone(),
+                    min_length: constraints.min_length,
+                    max_length: constraints.max_length,
+                    ..ValueConstraints::default()
+                }
+            }
+            DataType::PrimitiveType(
+                PrimitiveType::Integer | PrimitiveType::SizedInteger(_) | PrimitiveType::Number,
+            ) => match integer_range(value_type) {
+                // Integers are checked against inclusive integer bounds
+                Some((min, max)) => ValueConstraints {
+                    minimum: constraints
+                        .minimum
+                        .map(|(x, exclusive)| match exclusive {
+                            true => (x.floor() + 1.0, false),
+                            false => (x.ceil(), false),
+                        })
+                        .filter(|(x, _)| *x > min),
+                    maximum: constraints
+                        .maximum
+                        .map(|(x, exclusive)| match exclusive {
+                            true => (x.ceil() - 1.0, false),
+                            false => (x.floor(), false),
+                        })
+                        .filter(|(x, _)| *x < max),
+                    multiple_of: constraints.multiple_of,
+                    ..ValueConstraints::default()
+                },
+                None => ValueConstraints {
+                    minimum: constraints.minimum,
+                    maximum: constraints.maximum,
+                    multiple_of: constraints.multiple_of,
+                    ..ValueConstraints::default()
+                },
+            },
+            DataType::Array(items) | DataType::Set(items) => ValueConstraints {
+                min_items: constraints.min_items.filter(|x| *x > 0),
+                max_items: constraints.max_items,
+                items: constraints.items.as_ref().and_then(|constraints| {
+                    let item_type = type_arguments(value_type).pop()?;
+                    self.fitting_constraints(constraints, items, item_type)
+                        .map(Box::new)
+                }),
+                ..ValueConstraints::default()
+            },
+            _ => return None,
+        };
+
+        match constraints.is_empty() {
+            true => None,
+            false => Some(constraints),
+        }
+    }
+
+    fn add_type(
+        &mut self,
+        root: Rc<Root>,
+        src_override: Option<String>,
+        data_type: &DataType,
+        required: bool,
+        name_hint: Option<String>,
+    ) -> String {
+        let type_name = match data_type {
+            DataType::PrimitiveType(primitive_type) => match primitive_type {
+                PrimitiveType::Null => match self.options.null_style {
+                    NullStyle::Value => String::from(self.options.any_type_name()),
+                    NullStyle::Unit => String::from("()"),
+                    NullStyle::Marker => self.add_null_marker(&root),
+                },
+                PrimitiveType::Boolean => String::from("bool"),
+                PrimitiveType::Integer => String::from("i64"),
+                // The lenient helpers read `i64`s
+                PrimitiveType::SizedInteger(_) if self.options.lenient_numbers => {
+                    String::from("i64")
+                }
+                PrimitiveType::SizedInteger(type_name) => String::from(*type_name),
+                PrimitiveType::Number => String::from("f64"),
+                PrimitiveType::String => String::from("String"),
+                // Only properties decode the base64 text, see `create_property`
+                PrimitiveType::Bytes => String::from("String"),
+                PrimitiveType::Format(format) => self.options.format_type(format),
+            },
+            DataType::Array(items) => {
+                let type_name = self.add_type(root, src_override, items, true, name_hint);
+                format!("Vec<{}>", type_name)
+            }
+            DataType::Custom(type_path) => type_path.clone(),
+            DataType::Set(items) => {
+                let type_name = self.add_type(root.clone(), src_override, items, true, name_hint);
+
+                match self.options.set_type != SetType::Vec && self.is_set_item(root, items) {
+                    true => format!("{}<{}>", self.options.set_type.name(), type_name),
+                    false => format!("Vec<{}>", type_name),
+                }
+            }
+            DataType::Tuple(items) => {
+                let type_names: Vec<String> = items
+                    .iter()
+                    .map(|item| self.add_type(root.clone(), None, item, true, None))
+                    .collect();
+
+                match &type_names[..] {
+                    [type_name] => format!("({},)", type_name),
+                    _ => format!("({})", type_names.join(", ")),
+                }
+            }
+            DataType::Object(object) => {
+                let src = src_override.unwrap_or(object.src.to_string());
+
+                if self.is_inlined(&src, object) {
+                    self.add_inline_map(root, object, name_hint)
+                } else {
+                    self.add_object(root, src, object, name_hint)
+                }
+            }
+            DataType::Map(data_type) => {
+                format!(
+                    "{}<String, {}>",
+                    self.options.effective_map_type().name(),
+                    self.add_type(root, None, data_type, true, name_hint)
+                )
+            }
+            DataType::KeyedMap(KeyedMap { key, values }) => {
+                let key_type = self.add_map_key(&root, key);
+                format!(
+                    "{}<{}, {}>",
+                    self.options.effective_map_type().name(),
+                    key_type,
+                    self.add_type(root, None, values, true, name_hint)
+                )
+            }
+            DataType::EmptyObject(EmptyObject {
+                src,
+                name: Some(name),
+                title,
+                ..
+            }) if self.options.empty_object_style == EmptyObjectStyle::Struct => {
+                let object = Object {
+                    src: src.clone(),
+                    name: name.clone(),
+                    title: title.clone(),
+                    description: None,
+                    properties: vec![],
+                    required_any: vec![],
+                    unmatched_required: vec![],
+                    deny_unknown_fields: false,
+                };
+
+                self.add_object(
+                    root,
+                    src_override.unwrap_or(src.clone()),
+                    &object,
+                    name_hint,
+                )
+            }
+            DataType::EmptyObject(empty_object) => {
+                let map = self.empty_object_map(empty_object);
+                return self.add_type(root, None, &map, required, name_hint);
+            }
+            DataType::StringEnum(string_enum) if self.options.string_enums => {
+                let src = src_override.unwrap_or(string_enum.src.clone());
+                self.add_string_enum(&root, src, string_enum)
+            }
+            DataType::StringEnum(_) => String::from("String"),
+            DataType::Ref(reference) => {
+                let ResolveResult {
+                    root,
+                    path,
+                    data_type,
+                } = self.resolve(root, reference);
+                let external = self.external_type(&root, &path, &data_type);
+                let file = root.file.display().to_string();
+
+                let src = match &path {
+                    Some(path) => format!("{}#{}", file, path),
+                    None => file,
+                };
+
+                let newtype = match (&*data_type, self.options.scalar_definition_style) {
+                    (_, ScalarDefinitionStyle::Inline) => None,
+                    (DataType::PrimitiveType(_), style) | (DataType::Any, style) => {
+                        Some(style == ScalarDefinitionStyle::Newtype)
+                    }
+                    (DataType::StringEnum(_), style) if !self.options.string_enums => {
+                        Some(style == ScalarDefinitionStyle::Newtype)
+                    }
+                    (DataType::EmptyObject(_), _)
+                        if self.options.empty_object_style == EmptyObjectStyle::Struct =>
+                    {
+                        None
+                    }
+                    (DataType::Array(_), _)
+                    | (DataType::Set(_), _)
+                    | (DataType::Tuple(_), _)
+                    | (DataType::Map(_), _)
+                    | (DataType::KeyedMap(_), _)
+                    | (DataType::EmptyObject(_), _) => Some(false),
+                    _ => None,
+                };
+
+                let constraints = match &path {
+                    Some(path) if is_definition(path) => {
+                        self.newtype_constraints(&root, &src, &data_type)
+                    }
+                    _ => None,
+                };
+
+                match (&path, newtype, external) {
+                    (_, _, Some(external)) => external,
+                    (Some(path), _, None) if constraints.is_some() => {
+                        self.add_alias(root, src, definition_name(path), &data_type, |value_type| {
+                            TypeKind::Constrained {
+                                value_type,
+                                constraints: constraints.unwrap(),
+                            }
+                        })
+                    }
+                    (Some(path), Some(newtype), None) if is_definition(path) => {
+                        self.add_alias(root, src, definition_name(path), &data_type, |target| {
+                            match newtype {
+                                true => TypeKind::Newtype(target),
+                                false => TypeKind::Alias(target),
+                            }
+                        })
+                    }
+                    _ => self.add_type(root, Some(src), &data_type, true, None),
+                }
+            }
+            DataType::OneOf(OneOf { types, .. }) | DataType::AnyOf(AnyOf { types, .. })
+                if nullable_alternative(types).is_some() =>
+            {
+                let inner = nullable_alternative(types).unwrap();
+                self.add_type(root, src_override, inner, true, name_hint)
+            }
+            DataType::OneOf(OneOf { src, name, types })
+            | DataType::AnyOf(AnyOf {
+                src, name, types, ..
+            }) => {
+                let src = src_override.unwrap_or(src.clone());
+                let patterns: &[String] = match data_type {
+                    DataType::AnyOf(AnyOf { patterns, .. }) => patterns,
+                    _ => &[],
+                };
+
+                match (name.clone().or(name_hint), self.discriminate(&root, types)) {
+                    (Some(name), Some(branches)) if patterns.is_empty() => {
+                        self.add_tagged_enum(root, src, &name, branches)
+                    }
+                    (Some(name), _) => self.add_one_of(root, src, &name, types, patterns),
+                    (None, _) => {
+                        for data_type in types {
+                            self.add(root.clone(), data_type);
+                        }
+
+                        String::from(self.options.any_type_name())
+                    }
+                }
+            }
+            DataType::AllOf(all_of) => match self.merge_objects(&root, all_of) {
+                Some((object, roots)) => {
+                    let src = src_override.unwrap_or(object.src.to_string());
+
+                    for (property, property_root) in object.properties.iter().zip(roots) {
+                        if property_root.file != root.file {
+                            let property_src = child_src(&src, &["properties", &property.name]);
+                            self.property_roots.insert(property_src, property_root);
+                        }
+                    }
+
+                    self.add_object(root, src, &object, name_hint)
+                }
+                None => {
+                    for data_type in &all_of.types {
+                        self.add(root.clone(), data_type);
+                    }
+
+                    String::from(self.options.any_type_name())
+                }
+            },
+            DataType::Any => String::from(self.options.any_type_name()),
+        };
+
+        let nullable = match data_type {
+            DataType::OneOf(OneOf { types, .. }) | DataType::AnyOf(AnyOf { types, .. }) => {
+                nullable_alternative(types).is_some()
+            }
+            _ => false,
+        };
+
+        match required && !nullable {
+            true => type_name,
+            false => format!("Option<{}>", type_name),
+        }
+    }
+}
+
+/// The other alternative of exactly two, one of which only accepts `null`
+fn nullable_alternative(types: &[DataType]) -> Option<&DataType> {
+    match types {
+        [DataType::PrimitiveType(PrimitiveType::Null), other]
+        | [other, DataType::PrimitiveType(PrimitiveType::Null)] => Some(other),
+        _ => None,
+    }
+}
+
+/// The type of the constant of a property, untyped values have to implement `From` of it
+fn constant_type(value: &Value, property_type: &str, any_type: &str) -> Option<String> {
+    let property_type = match property_type.strip_prefix("Option<") {
+        Some(inner_type) => inner_type.trim_end_matches('>'),
+        None => property_type,
+    };
+    let untyped = property_type == any_type;
+
+    let constant_type = match (value, property_type) {
+        (Value::String(_), "String") => "&str",
+        (Value::String(_), _) if untyped => "&str",
+        (Value::Number(number), "i64") if number.is_i64() => "i64",
+        (Value::Number(number), integer) if is_integer_type(integer) && number.is_u64() => integer,
+        (Value::Number(number), _) if untyped && number.is_i64() => "i64",
+        (Value::Number(_), "f64") => "f64",
+        (Value::Number(_), _) if untyped => "f64",
+        (Value::Bool(_), "bool") => "bool",
+        (Value::Bool(_), _) if untyped => "bool",
+        _ => return None,
+    };
+
+    Some(String::from(constant_type))
+}
+
+/// Whether the value is the `Default` of the type, which `#[serde(default)]` produces
+fn is_type_default(value: &Value, property_type: &str) -> bool {
+    match value {
+        Value::Bool(value) => !value && property_type == "bool",
+        Value::Number(number) => {
+            number.as_f64() == Some(0.0)
+                && (is_integer_type(property_type) || property_type == "f64")
+        }
+        Value::String(value) => value.is_empty() && property_type == "String",
+        Value::Array(values) => {
+            values.is_empty()
+                && (property_type.starts_with("Vec<") || SetType::is_set(property_type))
+        }
+        Value::Object(map) => map.is_empty() && MapType::is_map(property_type),
+        Value::Null => false,
+    }
+}
+
+/// Replaces every type which has the same fields of the same types as an earlier one
+/// by the earlier one. Types which only differ in types merged before are merged too.
+fn merge_identical_types(types: &mut Vec<GeneratedType>) {
+    loop {
+        let duplicate = (1..types.len()).find_map(|index| {
+            types[..index]
+                .iter()
+                .find(|x| same_structure(x, &types[index]))
+                .map(|x| (index, x.name.clone()))
+        });
+        let (index, name) = match duplicate {
+            Some(duplicate) => duplicate,
+            None => return,
+        };
+        let duplicate = types.remove(index);
+
+        for generated_type in types.iter_mut() {
+            let targets: Vec<&mut String> = match &mut generated_type.kind {
+                TypeKind::Alias(target) | TypeKind::Newtype(target) => vec![target],
+                TypeKind::Map { key, value, .. } => vec![key, value],
+                _ => generated_type.field_types_mut(),
+            };
+
+            for target in targets {
+                *target = replace_type_name(target, &duplicate.name, &name);
+            }
+        }
+    }
+}
+
+/// Everything but the names, sources and docs is the same
+fn same_structure(a: &GeneratedType, b: &GeneratedType) -> bool {
+    let same_properties = a.properties.len() == b.properties.len()
+        && a.properties.iter().zip(&b.properties).all(|(a, b)| {
+            a.name == b.name
+                && a.property_type == b.property_type
+                && a.serde_options == b.serde_options
+                && a.map_constraints == b.map_constraints
+        });
+
+    a.kind == b.kind
+        && same_properties
+        && a.derives == b.derives
+        && a.constants == b.constants
+        && a.defaults == b.defaults
+        && a.deny_unknown_fields == b.deny_unknown_fields
+        && a.module == b.module
+        && a.required_any == b.required_any
+}
+
+/// Replaces the identifier in a type like `Option<Vec<Inner>>`, but not identifiers containing it
+fn replace_type_name(type_name: &str, from: &str, to: &str) -> String {
+    let mut result = String::new();
+    let mut identifier = String::new();
+
+    for c in type_name.chars().chain(std::iter::once(' ')) {
+        if c.is_alphanumeric() || c == '_' {
+            identifier.push(c);
+            continue;
+        }
+
+        match identifier == from {
+            true => result.push_str(to),
+            false => result.push_str(&identifier),
+        }
+
+        identifier.clear();
+        result.push(c);
+    }
+
+    result.pop();
+    result
+}
+
+fn place_boxes(types: &mut [GeneratedType]) {
+    let indices: HashMap<String, usize> = types
+        .iter()
+        .enumerate()
+        .filter(|(_, x)| {
+            matches!(
+                x.kind,
+                TypeKind::Struct
+                    | TypeKind::Wrapper
+                    | TypeKind::UntaggedEnum(_)
+                    | TypeKind::TaggedEnum { .. }
+            )
+        })
+        .map(|(index, x)| (x.name.clone(), index))
+        .collect();
+
+    let mut order: Vec<usize> = indices.values().copied().collect();
+    order.sort_by(|a, b| types[*a].name.cmp(&types[*b].name));
+
+    let mut finished = HashSet::new();
+    let mut boxed = Vec::new();
+
+    for index in order {
+        visit_type(
+            types,
+            &indices,
+            index,
+            &mut Vec::new(),
+            &mut finished,
+            &mut boxed,
+        );
+    }
+
+    for (index, field) in boxed {
+        let mut field_types = types[index].field_types_mut();
+        let field_type = &mut field_types[field];
+
+        **field_type = match field_type.strip_prefix("Option<") {
+            Some(inner_type) => format!("Option<Box<{}>", inner_type),
+            None => format!("Box<{}>", field_type),
+        };
+    }
+}
+
+fn visit_type(
+    types: &[GeneratedType],
+    indices: &HashMap<String, usize>,
+    index: usize,
+    stack: &mut Vec<usize>,
+    finished: &mut HashSet<usize>,
+    boxed: &mut Vec<(usize, usize)>,
+) {
+    if finished.contains(&index) {
+        return;
+    }
+
+    stack.push(index);
+
+    for (field, field_type) in types[index].field_types().into_iter().enumerate() {
+        let target = match field_type.strip_prefix("Option<") {
+            Some(inner_type) => inner_type.trim_end_matches('>'),
+            None => field_type.as_str(),
+        };
+
+        if let Some(target) = indices.get(target) {
+            match stack.contains(target) {
+                true => boxed.push((index, field)),
+                false => visit_type(types, indices, *target, stack, finished, boxed),
+            }
+        }
+    }
+
+    stack.pop();
+    finished.insert(index);
+}
+
+fn render_source(
+    src: &str,
+    file: &str,
+    source_style: &SourceStyle,
+    file_ids: &HashMap<String, String>,
+) -> String {
+    let rest = match src.strip_prefix(file) {
+        Some(rest) => rest,
+        None => return src.to_string(),
+    };
+
+    let file = match source_style {
+        SourceStyle::Path => return src.to_string(),
+        SourceStyle::RelativeTo(base) => relative_path(Path::new(file), base),
+        SourceStyle::Id => match file_ids.get(file) {
+            Some(id) => id.trim_end_matches('#').to_string(),
+            None => return src.to_string(),
+        },
+    };
+
+    format!("{}{}", file, rest)
+}
+
+/// Module paths of the files with an `$id`, from the path of the `$id` URI
+/// without the directories all of them share, e.g. `billing::invoice`
+fn id_modules(file_ids: &HashMap<String, String>) -> HashMap<String, Vec<String>> {
+    let paths: Vec<(&String, Vec<&str>)> = file_ids
+        .iter()
+        .map(|(file, id)| {
+            let id = id.split(['#', '?']).next().unwrap_or_default();
+            let path = match id.split_once("://") {
+                Some((_, rest)) => rest.split_once('/').map(|(_, path)| path).unwrap_or(""),
+                None => id,
+            };
+            let mut segments: Vec<&str> = path.split('/').filter(|x| !x.is_empty()).collect();
+
+            if let Some(last) = segments.last_mut() {
+                *last = last.split('.').next().unwrap_or_default();
+            }
+
+            (file, segments)
+        })
+        .collect();
+
+    let shared = match paths.first() {
+        Some((_, first)) => (0..first.len().saturating_sub(1))
+            .take_while(|&i| {
+                paths
+                    .iter()
+                    .all(|(_, x)| x.len() > i + 1 && x[i] == first[i])
+            })
+            .count(),
+        None => 0,
+    };
+
+    paths
+        .into_iter()
+        .map(|(file, segments)| {
+            let module = segments[shared.min(segments.len())..]
+                .iter()
+                .map(|x| sanitize_module_name(x.to_string()))
+                .collect();
+
+            (file.clone(), module)
+        })
+        .collect()
+}
+
+fn relative_path(file: &Path, base: &Path) -> String {
+    let relative = match file.strip_prefix(base) {
+        Ok(relative) => Some(relative.to_path_buf()),
+        Err(_) => canonical_relative_path(file, base),
+    };
+
+    match relative {
+        Some(relative) => relative
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+                _ => None,
+            })
+            .collect::<Vec<String>>()
+            .join("/"),
+        None => file.display().to_string(),
+    }
+}
+
+#[cfg(feature = "fs")]
+fn canonical_relative_path(file: &Path, base: &Path) -> Option<PathBuf> {
+    match (file.canonicalize(), base.canonicalize()) {
+        (Ok(file), Ok(base)) => file.strip_prefix(base).ok().map(|x| x.to_path_buf()),
+        _ => None,
+    }
+}
+
+#[cfg(not(feature = "fs"))]
+fn canonical_relative_path(_file: &Path, _base: &Path) -> Option<PathBuf> {
+    None
+}
+
+fn variant_name(variant_type: &str, any_type: &str) -> String {
+    let name = match variant_type {
+        "bool" => "Bool",
+        _ if is_integer_type(variant_type) => "Integer",
+        "f64" => "Number",
+        _ if variant_type == any_type => "Any",
+        _ if variant_type.starts_with("Vec<") => "Array",
+        _ if SetType::is_set(variant_type) => "Set",
+        _ if MapType::is_map(variant_type) => "Map",
+        _ if variant_type.starts_with('(') => "Tuple",
+        // Paths of external types like `chrono::DateTime<chrono::Utc>` are named after the type
+        _ if variant_type.contains("::") => {
+            let path = variant_type.split('<').next().unwrap_or(variant_type);
+            path.rsplit("::").next().unwrap_or(path)
+        }
+        _ => variant_type,
+    };
+
+    match !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        true => name.to_string(),
+        false => sanitize_variant_name(name),
+    }
+}
+
+fn is_property_src(src: &str) -> bool {
+    match src.rsplit('/').nth(1) {
+        Some(parent) => parent == "properties",
+        None => false,
+    }
+}
+
+/// Whether the pointer points to a definition, rather than into a schema
+fn is_definition(path: &str) -> bool {
+    let parts: Vec<&str> = path.split('/').filter(|x| !x.is_empty()).collect();
+    matches!(parts.as_slice(), ["definitions", _] | ["$defs", _])
+}
+
+fn definition_name(path: &str) -> String {
+    path.rsplit('/').next().unwrap_or_default().to_string()
+}
+
+pub(crate) fn file_stem(file: &Path) -> String {
+    match file.file_name() {
+        Some(file_name) => file_name
+            .to_string_lossy()
+            .split('.')
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod generator_tests {
+    use crate::compatibility::Compatibility;
+    use crate::defaults::DefaultDiagnostic;
+    use crate::error::Error;
+    use crate::generator::{
+        place_boxes, replace_type_name, CratePaths, EmptyObjectStyle, EntryWithPosition,
+        GeneratedProperty, GeneratedType, GeneratedVariant, Generator, GeneratorOptions, MapType,
+        MethodAttributes, ScalarDefinitionStyle, SerdeOptions, SetType, TypeKind, Visibility,
+    };
+    #[cfg(feature = "fs")]
+    use crate::generator::{
+        BytesStyle, GeneratedConstant, NamingStrategy, NullStyle, SourceStyle, StringVariant,
+        TaggedVariant,
+    };
+    use crate::locations::Locations;
+    #[cfg(feature = "fs")]
+    use crate::parser::{parse_from_file, Overlay};
+    use crate::parser::{
+        AllOf, AnyOf, DataType, EmptyObject, Object, ObjectProperty, OneOf, PrimitiveType, Ref,
+        Root,
+    };
+    #[cfg(feature = "fs")]
+    use crate::plan::{PlannedField, PlannedType};
+    use crate::schema::Schema;
+    use proc_macro2::TokenStream;
+    use serde_json::json;
+    use std::cell::RefCell;
+    use std::collections::{BTreeMap, HashMap};
+    use std::path::{Path, PathBuf};
+    use std::rc::Rc;
+
+    #[test]
+    fn should_be_ordered_by_position() {
+        let mut list = vec![
+            EntryWithPosition {
+                payload: String::from("a"),
+                position: 3,
+            },
+            EntryWithPosition {
+                payload: String::from("b"),
+                position: 1,
+            },
+            EntryWithPosition {
+                payload: String::from("c"),
+                position: 2,
+            },
+        ];
+
+        list.sort();
+
+        assert_eq!(
+            list,
+            vec![
+                EntryWithPosition {
+                    payload: String::from("b"),
+                    position: 1,
+                },
+                EntryWithPosition {
+                    payload: String::from("c"),
+                    position: 2,
+                },
+                EntryWithPosition {
+                    payload: String::from("a"),
+                    position: 3,
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_add_simd_json_constructors() {
+        let mut generator = Generator::new();
+        generator.set_simd_json(true);
+        generator.add_file(Path::new("src/examples/generator/wrapper.schema.json"));
+        let code = generator.generate().to_string();
+
+        for name in ["Envelope", "Payload", "Meta"] {
+            assert!(code.contains(&format!(
+                "impl {} {{ pub fn from_slice (data : & mut [u8]) -> simd_json :: Result < Self >",
+                name
+            )));
+        }
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_sketch_tables_for_flat_structs() {
+        let mut generator = Generator::new();
+        generator.add_file(Path::new("src/examples/generator/wrapper.schema.json"));
+
+        assert_eq!(
+            generator.sql(),
+            "-- Envelope is not flat: data is a Payload\n\
+             \n\
+             -- Payload is not flat: meta is a Option<Meta>\n\
+             \n\
+             CREATE TABLE \"meta\" (\n    \"createdAt\" TEXT\n);\n"
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_refer_to_serde_helpers() {
+        let mut generator = Generator::new();
+        generator.set_lenient_numbers(true);
+        generator.set_empty_strings_as_none(true);
+        generator.add_file(Path::new(
+            "src/examples/generator/serde.helpers.schema.json",
+        ));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(
+            types[0]
+                .properties
+                .iter()
+                .map(|x| (
+                    x.name.as_str(),
+                    x.property_type.as_str(),
+                    x.serde_options
+                        .with
+                        .as_deref()
+                        .or(x.serde_options.deserialize_with.as_deref())
+                ))
+                .collect::<Vec<(&str, &str, Option<&str>)>>(),
+            vec![
+                (
+                    "comment",
+                    "Option<String>",
+                    Some("serde_helpers::empty_string_as_none")
+                ),
+                ("data", "Vec<u8>", Some("serde_helpers::base64")),
+                (
+                    "ratio",
+                    "Option<f64>",
+                    Some("serde_helpers::option_f64_from_string_or_number")
+                ),
+                (
+                    "size",
+                    "i64",
+                    Some("serde_helpers::i64_from_string_or_number")
+                ),
+                (
+                    "thumbnail",
+                    "Option<Vec<u8>>",
+                    Some("serde_helpers::option_base64")
+                ),
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_only_emit_used_serde_helpers() {
+        let mut generator = Generator::new();
+        generator.add_file(Path::new(
+            "src/examples/generator/serde.helpers.schema.json",
+        ));
+
+        let code = generator.generate().to_string();
+
+        assert_eq!(code.matches("mod serde_helpers").count(), 1);
+        assert!(code.contains("pub mod base64"));
+        assert!(!code.contains("from_string_or_number"));
+        assert!(!code.contains("empty_string_as_none"));
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_use_the_configured_bytes_style() {
+        let file = "src/examples/generator/bytes.schema.json";
+        let properties = |bytes_style| {
+            let mut generator = Generator::new();
+            generator.set_bytes_style(bytes_style);
+            generator.add_file(Path::new(file));
+
+            let types: Vec<GeneratedType> = generator.into();
+            types[0]
+                .properties
+                .iter()
+                .map(|x| (x.property_type.clone(), x.serde_options.with.clone()))
+                .collect::<Vec<(String, Option<String>)>>()
+        };
+
+        assert_eq!(
+            properties(BytesStyle::SerdeBytes),
+            vec![
+                (
+                    String::from("Option<Vec<u8>>"),
+                    Some(String::from("serde_bytes"))
+                ),
+                (String::from("Vec<u8>"), Some(String::from("serde_bytes"))),
+            ]
+        );
+        assert_eq!(
+            properties(BytesStyle::Bytes),
+            vec![
+                (String::from("Option<bytes::Bytes>"), None),
+                (String::from("bytes::Bytes"), None),
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_report_binary_findings_when_auditing() {
+        let file = "src/examples/generator/one.of.property.schema.json";
+
+        let mut generator = Generator::new();
+        generator.add_file(Path::new(file));
+        assert!(generator.report().binary_diagnostics.is_empty());
+
+        generator.set_binary_audit(true);
+        let report = generator.report();
+
+        assert_eq!(report.types.len(), generator.plan().types.len());
+        assert!(report
+            .binary_diagnostics
+            .iter()
+            .any(|x| x.message.starts_with("is an untagged enum")));
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_refer_to_re_exported_crates() {
+        let mut generator = Generator::new();
+        generator.set_crate_paths(CratePaths {
+            serde: String::from("facade::serde"),
+            serde_json: String::from("facade::serde_json"),
+            collections: String::from("facade::collections"),
+        });
+        generator.add_file(Path::new("src/examples/generator/wrapper.schema.json"));
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("use facade :: serde :: { Serialize , Deserialize } ;"));
+        assert!(code.contains("use facade :: serde_json :: Value ;"));
+        assert!(code.contains("use facade :: collections :: BTreeMap ;"));
+        assert_eq!(
+            code.matches("# [serde (crate = \"facade::serde\")]")
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn should_serialize_with_serde_json_import() {
+        let generator = Generator::new();
+        let tokens: TokenStream = generator.into();
+
+        assert!(tokens.to_string().contains("use serde_json :: Value"))
+    }
+
+    #[test]
+    fn should_use_the_configured_any_type() {
+        let file = Path::new("document.schema.json");
+        let mut generator = Generator::new();
+        generator.set_any_type("crate::AnyValue");
+        generator.set_overlay(
+            file,
+            String::from(
+                r#"{"title": "Document", "type": "object", "required": ["kind"], "properties": {"kind": {"const": "doc"}, "meta": {}}}"#,
+            ),
+        );
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("use crate :: AnyValue ;"));
+        assert!(!code.contains("serde_json :: Value"));
+        assert!(code.contains("pub kind : AnyValue"));
+        assert!(code.contains("pub meta : Option < AnyValue >"));
+        assert!(code.contains("AnyValue :: from (Self :: KIND)"));
+        assert_eq!(
+            generator.binary_diagnostics()[0].message,
+            "refers to crate::AnyValue, which only self-describing formats can represent"
+        );
+    }
+
+    #[test]
+    fn should_document_types_and_properties_with_their_description() {
+        let file = Path::new("user.json");
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r#"{"title": "User", "description": "A registered user.\n\nUsers can log in.", "type": "object", "properties": {"name": {"type": "string", "description": "The display name"}, "age": {"type": "integer"}}}"#,
+            ),
+        );
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("# [doc = \"A registered user.\"] # [doc = \"\"] # [doc = \"Users can log in.\"] # [doc = \"\"] # [doc = \"Generated from user.json\"]"));
+        assert!(code.contains("# [doc = \"The display name\"] # [serde (skip_serializing_if = \"Option::is_none\")] pub name"));
+        assert!(code.contains("{ # [serde (skip_serializing_if = \"Option::is_none\")] pub age"));
+    }
+
+    #[test]
+    fn should_fill_in_absent_properties_with_their_default() {
+        let file = Path::new("settings.json");
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r#"{"title": "Settings", "type": "object", "properties": {"retries": {"type": "integer", "default": 3}, "verbose": {"type": "boolean", "default": false}, "invalid": {"type": "integer", "default": "3"}, "note": {"type": "string"}}}"#,
+            ),
+        );
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(
+            code.contains("# [serde (default = \"Settings::default_retries\")] pub retries : i64")
+        );
+        assert!(code.contains("# [serde (default)] pub verbose : bool"));
+        assert!(code.contains("pub invalid : Option < i64 >"));
+        assert!(code.contains("pub fn default_retries () -> i64 { 3 }"));
+        assert!(code.contains("impl Default for Settings { fn default () -> Self { Settings { invalid : None , note : None , retries : Settings :: default_retries () , verbose : Default :: default () } } }"));
+    }
+
+    #[test]
+    fn should_derive_default_when_the_defaults_are_the_ones_of_the_types() {
+        let file = Path::new("flags.json");
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r#"{"title": "Flags", "type": "object", "required": ["name"], "properties": {"enabled": {"type": "boolean", "default": false}, "tags": {"type": "array", "items": {"type": "string"}, "default": []}, "name": {"type": "string"}}}"#,
+            ),
+        );
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("# [serde (default)] pub tags : Vec < String >"));
+        assert!(!code.contains("Default"));
+
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r#"{"title": "Flags", "type": "object", "properties": {"enabled": {"type": "boolean", "default": false}, "name": {"type": "string"}}}"#,
+            ),
+        );
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("Serialize , Default)] pub struct Flags"));
+        assert!(!code.contains("impl Default"));
+    }
+
+    #[test]
+    fn should_generate_the_normalized_shorthands() {
+        let file = Path::new("pet.json");
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r#"{"title": "Pet", "type": "object", "required": ["name", "owner"], "properties": {"name": {"type": "string", "nullable": true}, "owner": {"title": "Owner", "oneOf": [{"type": "object", "properties": {"id": {"type": "integer"}}}]}}}"#,
+            ),
+        );
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub name : Option < String > ,"));
+        assert!(code.contains("pub owner : Owner }"));
+        assert!(code.contains("# [doc = \"Generated from pet.json#/properties/owner/oneOf/0\"] # [derive (Clone , PartialEq , Debug , Deserialize , Serialize)] pub struct Owner {"));
+    }
+
+    #[test]
+    fn should_name_enums_of_array_items_after_the_array() {
+        let file = Path::new("log.json");
+        let schema = r#"{"title": "Log", "type": "object", "properties": {"events": {"type": "array", "items": {"oneOf": [{"type": "object", "title": "Click", "properties": {"x": {"type": "integer"}}}, {"type": "object", "title": "Press", "properties": {"key": {"type": "string"}}}]}}, "batches": {"type": "array", "items": {"type": "array", "items": {"anyOf": [{"type": "string"}, {"type": "integer"}]}}}}}"#;
+        let mut generator = Generator::new();
+        generator.set_overlay(file, String::from(schema));
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub events : Option < Vec < Event >> }"));
+        assert!(code.contains("pub enum Event { Click (Click) , Press (Press) }"));
+        assert!(code.contains("pub batches : Option < Vec < Vec < Batch >>> ,"));
+        assert!(code.contains("pub enum Batch { String (String) , Integer (i64) }"));
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_only_depend_on_serde_when_standalone() {
+        let mut generator = Generator::new();
+        generator.set_standalone(true);
+        generator.set_bytes_style(BytesStyle::Bytes);
+        generator.set_any_type("serde_yaml::Value");
+        #[cfg(feature = "indexmap")]
+        generator.set_map_type(MapType::IndexMap);
+        generator.add_file(Path::new("src/examples/generator/map.key.schema.json"));
+        generator.add_file(Path::new(
+            "src/examples/generator/serde.helpers.schema.json",
+        ));
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("mod regex"));
+        assert!(code.contains("mod serde_helpers"));
+        assert!(code.contains("Vec < u8 >"));
+        assert!(code.contains("use std :: collections :: BTreeMap ;"));
+        assert!(code.contains("use serde_json :: Value ;"));
+        assert!(!code.contains("IndexMap"));
+        assert!(!code.contains("bytes :: Bytes"));
+    }
+
+    #[test]
+    fn should_add_schemas_from_strings_and_values() {
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            Path::new("schemas/id.json"),
+            String::from(r#"{"type": "string"}"#),
+        );
+
+        generator.add_str(
+            "schemas/user.json",
+            r#"{"title": "User", "type": "object", "required": ["id"], "properties": {"id": {"$ref": "id.json"}}}"#,
+        );
+        generator.add_value(
+            "schemas/group.json",
+            json!({"title": "Group", "type": "object", "properties": {"size": {"type": "integer"}}}),
+        );
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub struct User { pub id : String }"));
+        assert!(code.contains("Generated from schemas/group.json"));
+        assert_eq!(
+            generator
+                .try_add_str("broken.json", "{")
+                .map_err(|x| x.to_string()),
+            Err(String::from(
+                "Could not parse broken.json:1:1: EOF while parsing an object"
+            ))
+        );
+    }
+
+    #[test]
+    fn should_emit_unique_items_as_sets() {
+        let file = Path::new("post.json");
+        let schema = r##"{"title": "Post", "type": "object", "properties": {"tags": {"type": "array", "uniqueItems": true, "items": {"type": "string"}}, "scores": {"type": "array", "uniqueItems": true, "items": {"type": "number"}}, "ids": {"type": "array", "uniqueItems": true, "items": {"$ref": "#/definitions/id"}}}, "definitions": {"id": {"type": "integer", "minimum": 0}}}"##;
+        let mut generator = Generator::new();
+        generator.set_scalar_definition_style(ScalarDefinitionStyle::Alias);
+        generator.set_unique_items_type(SetType::BTreeSet);
+        generator.set_overlay(file, String::from(schema));
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("use std :: collections :: BTreeSet ;"));
+        assert!(code.contains("pub tags : Option < BTreeSet < String >>"));
+        assert!(code.contains("pub ids : Option < BTreeSet < Id >>"));
+        assert!(code.contains("pub scores : Option < Vec < f64 >>"));
+
+        let mut generator = Generator::new();
+
+        generator.set_scalar_definition_style(ScalarDefinitionStyle::Alias);
+        generator.set_overlay(file, String::from(schema));
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub tags : Option < Vec < String >>"));
+    }
+
+    #[test]
+    fn should_run_post_processors_in_order() {
+        let file = Path::new("point.json");
+        let schema =
+            r#"{"title": "Point", "type": "object", "properties": {"x": {"type": "number"}}}"#;
+        let mut generator = Generator::new();
+        generator.set_overlay(file, String::from(schema));
+        generator.add_file(file);
+        generator
+            .add_post_processor(|tokens| quote::quote! { #tokens impl Point { fn origin() {} } });
+        generator.add_post_processor(|tokens| quote::quote! { pub mod shapes { #tokens } });
+
+        let code = generator.generate().to_string();
+
+        assert!(code.starts_with("pub mod shapes { use serde"));
+        assert!(code.ends_with("impl Point { fn origin () { } } }"));
+    }
+
+    #[test]
+    fn should_generate_tuples_of_prefix_items() {
+        let file = Path::new("shape.json");
+        let schema = r#"{"title": "Shape", "type": "object", "required": ["point"], "properties": {"point": {"type": "array", "items": [{"type": "number"}, {"type": "number"}], "additionalItems": false, "minItems": 2}, "entries": {"type": "array", "prefixItems": [{"type": "string"}, {"type": "object", "properties": {"x": {"type": "integer"}}}], "items": false, "minItems": 2}, "single": {"type": "array", "prefixItems": [{"type": "string"}], "items": false, "minItems": 1}, "open": {"type": "array", "prefixItems": [{"type": "string"}], "items": {"type": "integer"}, "minItems": 1}, "short": {"type": "array", "prefixItems": [{"type": "string"}, {"type": "integer"}], "items": false, "minItems": 1}}}"#;
+        let mut generator = Generator::new();
+        generator.set_overlay(file, String::from(schema));
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub point : (f64 , f64)"));
+        assert!(code.contains("pub entries : Option < (String , Entry2) >"));
+        assert!(code.contains("pub single : Option < (String ,) >"));
+        assert!(code.contains("pub open : Option < Vec < Value >>"));
+        assert!(code.contains("pub short : Option < Vec < Value >>"));
+        assert!(code.contains("Generated from shape.json#/properties/entries/prefixItems/1"));
+    }
+
+    #[test]
+    fn should_size_integers_by_their_bounds() {
+        let file = Path::new("page.json");
+        let schema = r#"{"title": "Page", "type": "object", "required": ["number"], "properties": {"number": {"type": "integer", "minimum": 1, "default": 1}, "size": {"type": "integer", "minimum": 0, "maximum": 100, "default": 0}, "offset": {"type": "integer"}}}"#;
+        let mut generator = Generator::new();
+        generator.set_overlay(file, String::from(schema));
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub number : u64"));
+        assert!(code.contains("pub size : u8"));
+        assert!(code.contains("pub offset : Option < i64 >"));
+        assert!(code.contains("pub fn default_number () -> u64 { 1 }"));
+        assert!(code.contains("# [serde (default)] pub size : u8"));
+    }
+
+    #[test]
+    fn should_map_formats_to_their_types() {
+        let file = Path::new("event.json");
+        let schema = r#"{"title": "Event", "type": "object", "required": ["id"], "properties": {"id": {"type": "string", "format": "uuid"}, "at": {"type": "string", "format": "date-time"}, "contact": {"type": "string", "format": "email"}, "moment": {"oneOf": [{"type": "string", "format": "date-time"}, {"type": "integer"}]}}}"#;
+        let mut generator = Generator::new();
+        generator.set_format_type("uuid", "uuid::Uuid");
+        generator.set_format_type("date-time", "time::OffsetDateTime");
+        generator.set_overlay(file, String::from(schema));
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub id : uuid :: Uuid"));
+        assert!(code.contains("pub at : Option < time :: OffsetDateTime >"));
+        assert!(code.contains("pub contact : Option < String >"));
+        assert!(code.contains("OffsetDateTime (time :: OffsetDateTime) , Integer (i64)"));
+    }
+
+    #[test]
+    fn should_deny_unknown_fields_without_additional_properties() {
+        let file = Path::new("point.json");
+        let schema = r#"{"title": "Point", "type": "object", "additionalProperties": false, "properties": {"x": {"type": "number"}, "label": {"type": "object", "title": "Label", "additionalProperties": true, "properties": {"text": {"type": "string"}}}}}"#;
+        let mut generator = Generator::new();
+        generator.set_overlay(file, String::from(schema));
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("# [serde (deny_unknown_fields)] pub struct Point"));
+        assert!(code.contains("Serialize)] pub struct Label"));
+
+        generator.set_allow_unknown_fields(true);
+
+        assert!(!generator
+            .generate()
+            .to_string()
+            .contains("deny_unknown_fields"));
+    }
+
+    #[test]
+    fn should_annotate_constructors_and_conversions_on_request() {
+        let file = Path::new("id.json");
+        let schema = r#"{"title": "Id", "oneOf": [{"type": "integer"}, {"type": "object", "title": "Details", "properties": {"retries": {"type": "integer", "default": 3}}}]}"#;
+        let mut generator = Generator::new();
+        generator.set_overlay(file, String::from(schema));
+        generator.add_file(file);
+
+        assert!(!generator.generate().to_string().contains("# [inline]"));
+
+        generator.set_method_attributes(MethodAttributes {
+            must_use: true,
+            inline: true,
+        });
+
+        let code = generator.generate().to_string();
+
+        assert!(
+            code.contains("# [must_use] # [inline] pub fn as_integer (& self) -> Option < & i64 >")
+        );
+        assert!(code.contains("# [must_use] # [inline] pub fn default_retries () -> i64"));
+        assert!(code.contains("impl From < Details > for Id { # [inline] fn from"));
+    }
+
+    #[test]
+    fn should_apply_the_output_options() {
+        let file = Path::new("settings.schema.json");
+        let mut generator = Generator::new_with_options(
+            GeneratorOptions::default()
+                .with_derives(["Eq"])
+                .with_map_type(MapType::HashMap)
+                .with_visibility(Visibility::Crate)
+                .with_serialize_none(true)
+                .with_header(
+                    "use serde::{Deserialize, Serialize}; use serde_json::Value; use std::collections::HashMap;",
+                ),
+        );
+        generator.set_overlay(
+            file,
+            String::from(
+                r#"{"title": "Settings", "type": "object", "properties": {"name": {"type": "string"}, "labels": {"type": "object", "additionalProperties": {"type": "string"}}}}"#,
+            ),
+        );
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.starts_with("use serde :: { Deserialize , Serialize } ; use serde_json :: Value ; use std :: collections :: HashMap ;"));
+        assert!(!code.contains("Serialize , Deserialize }"));
+        assert!(code.contains("Serialize , Eq)]"));
+        assert!(code.contains("pub (crate) struct Settings {"));
+        assert!(code.contains("pub labels : Option < HashMap < String , Value >>"));
+        assert!(!code.contains("skip_serializing_if"));
+    }
+
+    #[test]
+    fn should_serialize_with_btree_import() {
+        let generator = Generator::new();
+        let tokens: TokenStream = generator.into();
+
+        assert!(tokens
+            .to_string()
+            .contains("use std :: collections :: BTreeMap"))
+    }
+
+    #[test]
+    fn should_add_object() {
+        let mut generator = Generator::new();
+
+        let type_name = add_object(&mut generator);
+
+        assert_eq!(type_name, "AwesomeFoo");
+
+        assert_eq!(
+            generator.types.get("correct src"),
+            Some(&EntryWithPosition {
+                position: 0,
+                payload: GeneratedType {
+                    src: String::from("correct src"),
+                    name: String::from("AwesomeFoo"),
+                    doc_alias: None,
+                    description: None,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::Struct,
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
+                    properties: vec![GeneratedProperty {
+                        name: String::from("awesome_property"),
+                        property_type: String::from("Option<Value>"),
+                        serde_options: SerdeOptions {
+                            rename: Some(String::from("awesome property")),
+                            skip_serializing_if: Some(String::from("Option::is_none")),
+                            default: None,
+                            with: None,
+                            deserialize_with: None,
+                            aliases: vec![],
+                            flatten: false,
+                            skip_serializing: false,
+                            skip_deserializing: false,
+                        },
+                        description: None,
+                        map_constraints: None,
+                        value_constraints: None,
+                        requires: vec![],
+                    }],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
+                },
+            })
+        )
+    }
+
+    #[test]
+    fn should_add_known_type() {
+        let mut generator = Generator::new();
+
+        add_object(&mut generator);
+
+        assert_eq!(
+            generator.known_type_names.get("correct src"),
+            Some(&String::from("AwesomeFoo"))
+        );
+    }
+
+    #[test]
+    fn should_detect_type_cycles() {
+        let mut generator = Generator::new();
+        generator
+            .known_type_names
+            .insert(String::from("correct src"), String::from("some type"));
+
+        let type_name = add_object(&mut generator);
+
+        assert_eq!(type_name, "some type");
+
+        assert_eq!(generator.types.len(), 0)
+    }
+
+    #[test]
+    fn should_box_self_references() {
+        let mut types = vec![GeneratedType {
+            src: String::from("correct src"),
+            name: String::from("AwesomeFoo"),
+            doc_alias: None,
+            description: None,
+            derives: vec![],
+            removed_derives: vec![],
+            kind: TypeKind::Struct,
+            constants: vec![],
+            defaults: vec![],
+            deny_unknown_fields: false,
+            properties: vec![GeneratedProperty {
+                name: String::from("foo"),
+                property_type: String::from("Option<AwesomeFoo>"),
+                serde_options: SerdeOptions {
+                    rename: None,
+                    skip_serializing_if: Some(String::from("Option::is_none")),
+                    default: None,
+                    with: None,
+                    deserialize_with: None,
+                    aliases: vec![],
+                    flatten: false,
+                    skip_serializing: false,
+                    skip_deserializing: false,
+                },
+                description: None,
+                map_constraints: None,
+                value_constraints: None,
+                requires: vec![],
+            }],
+            examples: vec![],
+            module: vec![],
+            required_any: vec![],
+        }];
+
+        place_boxes(&mut types);
+
+        assert_eq!(
+            types[0].properties[0].property_type,
+            "Option<Box<AwesomeFoo>>"
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_box_the_same_property_regardless_of_the_entry_point() {
+        let dir = "src/examples/generator";
+
+        for files in [
+            vec!["loop3"],
+            vec!["loop1", "loop3"],
+            vec!["loop3", "loop1"],
+        ] {
+            let mut generator = Generator::new();
+
+            for file in &files {
+                generator.add_file(Path::new(&format!("{}/{}.schema.json", dir, file)));
+            }
+
+            let types: Vec<GeneratedType> = generator.into();
+            let property_types: HashMap<String, String> = types
+                .into_iter()
+                .flat_map(|x| {
+                    let name = x.name;
+                    x.properties
+                        .into_iter()
+                        .map(move |p| (format!("{}.{}", name, p.name), p.property_type))
+                })
+                .collect();
+
+            assert_eq!(property_types["B.c"], "Option<C>");
+            assert_eq!(property_types["C.b"], "Option<Box<B>>");
+        }
+    }
+
+    #[test]
+    fn should_not_add_the_same_type_twice() {
+        let mut generator = Generator::new();
+
+        let type_name = add_object(&mut generator);
+        assert_eq!(type_name, "AwesomeFoo");
+
+        let type_name = add_object(&mut generator);
+        assert_eq!(type_name, "AwesomeFoo");
+
+        assert_eq!(generator.types.len(), 1);
+
+        assert_eq!(generator.known_type_names.len(), 1);
+    }
+
+    #[test]
+    fn should_add_types_in_the_correct_order() {
+        let mut generator = Generator::new();
+
+        generator.add_object(
+            Rc::new(Root {
+                file: Path::new("").to_path_buf(),
+                dialect: None,
+                id: None,
+                title: None,
+                data_type: Rc::new(DataType::Any),
+                definitions: HashMap::new(),
+                enums: BTreeMap::new(),
+                invalid_defaults: BTreeMap::new(),
+                examples: BTreeMap::new(),
+                constraints: BTreeMap::new(),
+                schema: Schema::default(),
+                locations: Locations::default(),
+            }),
+            String::from("correct src"),
+            &Object {
+                src: String::from("wrong src"),
+                name: String::from("awesome foo"),
+                title: None,
+                description: None,
+                properties: vec![ObjectProperty {
+                    name: String::from("awesome property"),
+                    required: false,
+                    constant: None,
+                    default: None,
+                    data_type: Rc::new(DataType::Object(Object {
+                        src: String::from("nested src"),
+                        name: String::from("awesome foo part 2"),
+                        title: None,
+                        description: None,
+                        properties: vec![ObjectProperty {
+                            name: String::from("awesome property part 2"),
+                            required: false,
+                            constant: None,
+                            default: None,
+                            data_type: Rc::new(DataType::Any),
+                            description: None,
+                            map_constraints: None,
+                            aliases: vec![],
+                            read_only: false,
+                            write_only: false,
+                            value_constraints: None,
+                            requires: vec![],
+                        }],
+                        required_any: vec![],
+                        unmatched_required: vec![],
+                        deny_unknown_fields: false,
+                    })),
+                    description: None,
+                    map_constraints: None,
+                    aliases: vec![],
+                    read_only: false,
+                    write_only: false,
+                    value_constraints: None,
+                    requires: vec![],
+                }],
+                required_any: vec![],
+                unmatched_required: vec![],
+                deny_unknown_fields: false,
+            },
+            None,
+        );
+
+        assert_eq!(
+            generator.types.get("correct src").map(|x| x.position),
+            Some(0)
+        );
+
+        assert_eq!(
+            generator.types.get("nested src").map(|x| x.position),
+            Some(1)
+        );
+    }
+
+    fn add_object(generator: &mut Generator) -> String {
+        generator.add_object(
+            Rc::new(Root {
+                file: Path::new("").to_path_buf(),
+                dialect: None,
+                id: None,
+                title: None,
+                data_type: Rc::new(DataType::Any),
+                definitions: HashMap::new(),
+                enums: BTreeMap::new(),
+                invalid_defaults: BTreeMap::new(),
+                examples: BTreeMap::new(),
+                constraints: BTreeMap::new(),
+                schema: Schema::default(),
+                locations: Locations::default(),
+            }),
+            String::from("correct src"),
+            &object_with_property(),
+            None,
+        )
+    }
+
+    fn object_with_property() -> Object {
+        object_with_custom_property(ObjectProperty {
+            name: String::from("awesome property"),
+            required: false,
+            constant: None,
+            default: None,
+            data_type: Rc::new(DataType::Any),
+            description: None,
+            map_constraints: None,
+            aliases: vec![],
+            read_only: false,
+            write_only: false,
+            value_constraints: None,
+            requires: vec![],
+        })
+    }
+
+    fn object_with_custom_property(property: ObjectProperty) -> Object {
+        Object {
+            src: String::from("wrong src"),
+            name: String::from("awesome foo"),
+            title: None,
+            description: None,
+            properties: vec![property],
+            required_any: vec![],
+            unmatched_required: vec![],
+            deny_unknown_fields: false,
+        }
+    }
+
+    #[test]
+    fn should_add_null_type() {
+        let mut generator = Generator::new();
+
+        assert_eq!(
+            add_primitive_type(&mut generator, PrimitiveType::Null, true),
+            String::from("Value")
+        );
+    }
+
+    #[test]
+    fn should_add_bool_type() {
+        let mut generator = Generator::new();
+
+        assert_eq!(
+            add_primitive_type(&mut generator, PrimitiveType::Boolean, true),
+            String::from("bool")
+        );
+    }
+
+    #[test]
+    fn should_add_integer_type() {
+        let mut generator = Generator::new();
+
+        assert_eq!(
+            add_primitive_type(&mut generator, PrimitiveType::Integer, true),
+            String::from("i64")
+        );
+    }
+
+    #[test]
+    fn should_add_number_type() {
+        let mut generator = Generator::new();
+
+        assert_eq!(
+            add_primitive_type(&mut generator, PrimitiveType::Number, true),
+            String::from("f64")
+        );
+    }
+
+    #[test]
+    fn should_add_string_type() {
+        let mut generator = Generator::new();
+
+        assert_eq!(
+            add_primitive_type(&mut generator, PrimitiveType::String, true),
+            String::from("String")
+        );
+    }
+
+    #[test]
+    fn should_add_optional_string_type() {
+        let mut generator = Generator::new();
+
+        assert_eq!(
+            add_primitive_type(&mut generator, PrimitiveType::String, false),
+            String::from("Option<String>")
+        );
+    }
+
+    fn add_primitive_type(
+        generator: &mut Generator,
+        primitive_type: PrimitiveType,
+        required: bool,
+    ) -> String {
+        add_type(generator, DataType::PrimitiveType(primitive_type), required)
+    }
+
+    #[test]
+    fn should_add_array_type() {
+        let mut generator = Generator::new();
+
+        let type_name = add_type(
+            &mut generator,
+            DataType::Array(Rc::new(DataType::Any)),
+            true,
+        );
+
+        assert_eq!(type_name, "Vec<Value>");
+    }
+
+    #[test]
+    fn should_add_object_type() {
+        let mut generator = Generator::new();
+
+        let type_name = add_type(
+            &mut generator,
+            DataType::Object(object_with_property()),
+            true,
+        );
+
+        assert_eq!(type_name, "AwesomeFoo");
+    }
+
+    #[test]
+    fn should_add_optional_object_type() {
+        let mut generator = Generator::new();
+
+        let type_name = add_type(
+            &mut generator,
+            DataType::Object(object_with_property()),
+            false,
+        );
+
+        assert_eq!(type_name, "Option<AwesomeFoo>");
+    }
+
+    #[test]
+    fn should_add_map_type() {
+        let mut generator = Generator::new();
+
+        let type_name = add_type(&mut generator, DataType::Map(Rc::new(DataType::Any)), true);
+
+        assert_eq!(type_name, "BTreeMap<String, Value>");
+    }
+
+    #[test]
+    fn should_add_ref_type() {
+        let mut generator = Generator::new();
+
+        let type_name = add_type(
+            &mut generator,
+            DataType::Ref(Ref {
+                src: String::new(),
+                ref_path: String::from("#/$defs/foo"),
+                description: None,
+                default: None,
+            }),
+            true,
+        );
+
+        assert_eq!(type_name, "AwesomeFoo");
+    }
+
+    #[test]
+    fn should_add_nullable_ref_type() {
+        let mut generator = Generator::new();
+
+        let type_name = add_type(
+            &mut generator,
+            DataType::AnyOf(AnyOf {
+                src: String::new(),
+                name: None,
+                types: vec![
+                    DataType::Ref(Ref {
+                        src: String::new(),
+                        ref_path: String::from("#/$defs/foo"),
+                        description: Some(String::from("Overrides the description of foo")),
+                        default: None,
+                    }),
+                    DataType::PrimitiveType(PrimitiveType::Null),
+                ],
+                patterns: vec![],
+            }),
+            true,
+        );
+
+        assert_eq!(type_name, "Option<AwesomeFoo>");
+    }
+
+    #[test]
+    fn should_add_optional_ref_type() {
+        let mut generator = Generator::new();
+
+        let type_name = add_type(
+            &mut generator,
+            DataType::Ref(Ref {
+                src: String::new(),
+                ref_path: String::from("#/$defs/foo"),
+                description: None,
+                default: None,
+            }),
+            false,
+        );
+
+        assert_eq!(type_name, "Option<AwesomeFoo>");
+    }
+
+    #[test]
+    fn should_add_one_of_type() {
+        let mut generator = Generator::new();
+
+        let type_name = add_type(
+            &mut generator,
+            DataType::OneOf(OneOf {
+                src: String::from("nirvana"),
+                name: None,
+                types: vec![DataType::Any],
+            }),
+            true,
+        );
+
+        assert_eq!(type_name, "Value");
+    }
+
+    #[test]
+    fn should_name_tuple_variants() {
+        for schema in [
+            r#"{"title": "Root", "type": "object", "properties": {"p": {"oneOf": [{"type": "array", "prefixItems": [{"type": "string"}, {"type": "integer"}], "items": false, "minItems": 2}, {"type": "string"}]}}}"#,
+            r#"{"title": "Root", "type": "object", "properties": {"p": {"type": ["array", "string"], "items": [{"type": "string"}, {"type": "integer"}], "additionalItems": false, "minItems": 2}}}"#,
+        ] {
+            let mut generator = Generator::new();
+            generator.add_str("root.json", schema);
+
+            let code = generator.generate_result().unwrap().to_string();
+
+            assert!(code.contains("Tuple ((String , i64))"));
+        }
+    }
+
+    #[test]
+    fn should_name_items_of_arrays_without_a_singular() {
+        let mut generator = Generator::new();
+        generator.add_str(
+            "root.json",
+            r#"{"title": "Root", "type": "object", "properties": {"s": {"type": "array", "items": {"oneOf": [{"type": "object", "properties": {"a": {"type": "string"}}}, {"type": "object", "properties": {"b": {"type": "integer"}}}]}}}}"#,
+        );
+
+        let code = generator.generate_result().unwrap().to_string();
+
+        assert!(code.contains("pub s : Option < Vec < S >>"));
+        assert!(code.contains("pub enum S {"));
+    }
+
+    #[test]
+    fn should_add_any_of_type() {
+        let mut generator = Generator::new();
+
+        let type_name = add_type(
+            &mut generator,
+            DataType::AnyOf(AnyOf {
+                src: String::from("nirvana"),
+                name: None,
+                types: vec![DataType::Any],
+                patterns: vec![],
+            }),
+            true,
+        );
+
+        assert_eq!(type_name, "Value");
+    }
+
+    #[test]
+    fn should_add_named_any_of_type_as_untagged_enum() {
+        let mut generator = Generator::new();
+
+        let type_name = add_type(
+            &mut generator,
+            DataType::AnyOf(AnyOf {
+                src: String::from("nirvana"),
+                name: Some(String::from("id")),
+                types: vec![
+                    DataType::PrimitiveType(PrimitiveType::Integer),
+                    DataType::PrimitiveType(PrimitiveType::String),
+                ],
+                patterns: vec![],
+            }),
+            true,
+        );
+
+        assert_eq!(type_name, "Id");
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(
+            types[0].kind,
+            TypeKind::UntaggedEnum(vec![
+                GeneratedVariant {
+                    name: String::from("Integer"),
+                    variant_type: String::from("i64"),
+                },
+                GeneratedVariant {
+                    name: String::from("String"),
+                    variant_type: String::from("String"),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn should_emit_objects_without_properties_in_the_configured_style() {
+        let empty_object = DataType::EmptyObject(EmptyObject {
+            src: String::from("nirvana"),
+            name: Some(String::from("settings")),
+            title: None,
+            key: None,
+            additional_properties: Some(Rc::new(DataType::PrimitiveType(PrimitiveType::Boolean))),
+        });
+        let mut generator = Generator::new();
+
+        assert_eq!(
+            add_type(&mut generator, empty_object.clone(), true),
+            "BTreeMap<String, Value>"
+        );
+
+        generator.set_empty_object_style(EmptyObjectStyle::AdditionalProperties);
+
+        assert_eq!(
+            add_type(&mut generator, empty_object.clone(), true),
+            "BTreeMap<String, bool>"
+        );
+
+        generator.set_empty_object_style(EmptyObjectStyle::Struct);
+
+        assert_eq!(add_type(&mut generator, empty_object, true), "Settings");
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(types[0].kind, TypeKind::Struct);
+        assert!(types[0].properties.is_empty());
+    }
+
+    #[test]
+    fn should_add_all_of_type() {
+        let mut generator = Generator::new();
+
+        let type_name = add_type(
+            &mut generator,
+            DataType::AllOf(AllOf {
+                src: String::from("nirvana"),
+                name: None,
+                types: vec![DataType::Any],
+            }),
+            true,
+        );
+
+        assert_eq!(type_name, "Value");
+    }
+
+    #[test]
+    fn should_add_any_type() {
+        let mut generator = Generator::new();
+
+        let type_name = add_type(&mut generator, DataType::Any, true);
+
+        assert_eq!(type_name, "Value");
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_detect_loops() {
+        let file = "src/examples/generator/loop1.schema.json";
+
+        let mut generator = Generator::new();
+        generator.add_file(Path::new(file));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(
+            types,
+            vec![
+                GeneratedType {
+                    src: String::from("src/examples/generator/loop1.schema.json"),
+                    name: String::from("Loop"),
+                    doc_alias: None,
+                    description: None,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::Struct,
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
+                    properties: vec![GeneratedProperty {
+                        name: String::from("a"),
+                        serde_options: SerdeOptions {
+                            rename: None,
+                            skip_serializing_if: Some(String::from("Option::is_none")),
+                            default: None,
+                            with: None,
+                            deserialize_with: None,
+                            aliases: vec![],
+                            flatten: false,
+                            skip_serializing: false,
+                            skip_deserializing: false,
+                        },
+                        property_type: String::from("Option<B>"),
+                        description: None,
+                        map_constraints: None,
+                        value_constraints: None,
+                        requires: vec![],
+                    }],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
+                },
+                GeneratedType {
+                    src: String::from("src/examples/generator/loop1.schema.json#/definitions/b"),
+                    name: String::from("B"),
+                    doc_alias: None,
+                    description: None,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::Struct,
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
+                    properties: vec![GeneratedProperty {
+                        name: String::from("c"),
+                        serde_options: SerdeOptions {
+                            rename: None,
+                            skip_serializing_if: Some(String::from("Option::is_none")),
+                            default: None,
+                            with: None,
+                            deserialize_with: None,
+                            aliases: vec![],
+                            flatten: false,
+                            skip_serializing: false,
+                            skip_deserializing: false,
+                        },
+                        property_type: String::from("Option<C>"),
+                        description: None,
+                        map_constraints: None,
+                        value_constraints: None,
+                        requires: vec![],
+                    }],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
+                },
+                GeneratedType {
+                    src: String::from("src/examples/generator/loop2.schema.json#/definitions/c"),
+                    name: String::from("C"),
+                    doc_alias: None,
+                    description: None,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::Struct,
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
+                    properties: vec![GeneratedProperty {
+                        name: String::from("b"),
+                        serde_options: SerdeOptions {
+                            rename: None,
+                            skip_serializing_if: Some(String::from("Option::is_none")),
+                            default: None,
+                            with: None,
+                            deserialize_with: None,
+                            aliases: vec![],
+                            flatten: false,
+                            skip_serializing: false,
+                            skip_deserializing: false,
+                        },
+                        property_type: String::from("Option<Box<B>>"),
+                        description: None,
+                        map_constraints: None,
+                        value_constraints: None,
+                        requires: vec![],
+                    }],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
+                }
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_create_referenced_types_once() {
+        let file = "src/examples/generator/reference.twice.schema.json";
+
+        let mut generator = Generator::new();
+        generator.add_file(Path::new(file));
+
+        let mut types: Vec<EntryWithPosition<GeneratedType>> =
+            generator.types.into_values().collect();
+
+        types.sort();
+
+        let types: Vec<GeneratedType> = types.into_iter().map(|x| x.payload).collect();
+
+        assert_eq!(
+            types,
+            vec![
+                GeneratedType {
+                    src: String::from(file),
+                    name: String::from("Twice"),
+                    doc_alias: None,
+                    description: None,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::Struct,
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
+                    properties: vec![
+                        GeneratedProperty {
+                            name: String::from("a"),
+                            serde_options: SerdeOptions {
+                                rename: None,
+                                skip_serializing_if: Some(String::from("Option::is_none")),
+                                default: None,
+                                with: None,
+                                deserialize_with: None,
+                                aliases: vec![],
+                                flatten: false,
+                                skip_serializing: false,
+                                skip_deserializing: false,
+                            },
+                            property_type: String::from("Option<C>"),
+                            description: None,
+                            map_constraints: None,
+                            value_constraints: None,
+                            requires: vec![],
+                        },
+                        GeneratedProperty {
+                            name: String::from("b"),
+                            serde_options: SerdeOptions {
+                                rename: None,
+                                skip_serializing_if: Some(String::from("Option::is_none")),
+                                default: None,
+                                with: None,
+                                deserialize_with: None,
+                                aliases: vec![],
+                                flatten: false,
+                                skip_serializing: false,
+                                skip_deserializing: false,
+                            },
+                            property_type: String::from("Option<C>"),
+                            description: None,
+                            map_constraints: None,
+                            value_constraints: None,
+                            requires: vec![],
+                        }
+                    ],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
+                },
+                GeneratedType {
+                    src: format!("{}#/definitions/c", file),
+                    name: String::from("C"),
+                    doc_alias: None,
+                    description: None,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::Struct,
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
+                    properties: vec![GeneratedProperty {
+                        name: String::from("foo"),
+                        serde_options: SerdeOptions {
+                            rename: None,
+                            skip_serializing_if: Some(String::from("Option::is_none")),
+                            default: None,
+                            with: None,
+                            deserialize_with: None,
+                            aliases: vec![],
+                            flatten: false,
+                            skip_serializing: false,
+                            skip_deserializing: false,
+                        },
+                        property_type: String::from("Option<Value>"),
+                        description: None,
+                        map_constraints: None,
+                        value_constraints: None,
+                        requires: vec![],
+                    }],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
+                }
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_prevent_name_collisions() {
+        let file = "src/examples/generator/name.collision.schema.json";
+
+        let mut generator = Generator::new();
+        generator.add_file(Path::new(file));
+
+        let mut types: Vec<EntryWithPosition<GeneratedType>> =
+            generator.types.into_values().collect();
+
+        types.sort();
+
+        let types: Vec<GeneratedType> = types.into_iter().map(|x| x.payload).collect();
+
+        assert_eq!(
+            types,
+            vec![
+                GeneratedType {
+                    src: String::from(file),
+                    name: String::from("Collision"),
+                    doc_alias: None,
+                    description: None,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::Struct,
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
+                    properties: vec![
+                        GeneratedProperty {
+                            name: String::from("a"),
+                            serde_options: SerdeOptions {
+                                rename: None,
+                                skip_serializing_if: Some(String::from("Option::is_none")),
+                                default: None,
+                                with: None,
+                                deserialize_with: None,
+                                aliases: vec![],
+                                flatten: false,
+                                skip_serializing: false,
+                                skip_deserializing: false,
+                            },
+                            property_type: String::from("Option<A>"),
+                            description: None,
+                            map_constraints: None,
+                            value_constraints: None,
+                            requires: vec![],
+                        },
+                        GeneratedProperty {
+                            name: String::from("b"),
+                            serde_options: SerdeOptions {
+                                rename: None,
+                                skip_serializing_if: Some(String::from("Option::is_none")),
+                                default: None,
+                                with: None,
+                                deserialize_with: None,
+                                aliases: vec![],
+                                flatten: false,
+                                skip_serializing: false,
+                                skip_deserializing: false,
+                            },
+                            property_type: String::from("Option<A1>"),
+                            description: None,
+                            map_constraints: None,
+                            value_constraints: None,
+                            requires: vec![],
+                        },
+                        GeneratedProperty {
+                            name: String::from("c"),
+                            serde_options: SerdeOptions {
+                                rename: None,
+                                skip_serializing_if: Some(String::from("Option::is_none")),
+                                default: None,
+                                with: None,
+                                deserialize_with: None,
+                                aliases: vec![],
+                                flatten: false,
+                                skip_serializing: false,
+                                skip_deserializing: false,
+                            },
+                            property_type: String::from("Option<A2>"),
+                            description: None,
+                            map_constraints: None,
+                            value_constraints: None,
+                            requires: vec![],
+                        }
+                    ],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
+                },
+                GeneratedType {
+                    src: format!("{}#/properties/a", file),
+                    name: String::from("A"),
+                    doc_alias: None,
+                    description: None,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::Struct,
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
+                    properties: vec![GeneratedProperty {
+                        name: String::from("foo"),
+                        serde_options: SerdeOptions {
+                            rename: None,
+                            skip_serializing_if: Some(String::from("Option::is_none")),
+                            default: None,
+                            with: None,
+                            deserialize_with: None,
+                            aliases: vec![],
+                            flatten: false,
+                            skip_serializing: false,
+                            skip_deserializing: false,
+                        },
+                        property_type: String::from("Option<Value>"),
+                        description: None,
+                        map_constraints: None,
+                        value_constraints: None,
+                        requires: vec![],
+                    }],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
+                },
+                GeneratedType {
+                    src: format!("{}#/properties/b", file),
+                    name: String::from("A1"),
+                    doc_alias: None,
+                    description: None,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::Struct,
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
+                    properties: vec![GeneratedProperty {
+                        name: String::from("foo"),
+                        serde_options: SerdeOptions {
+                            rename: None,
+                            skip_serializing_if: Some(String::from("Option::is_none")),
+                            default: None,
+                            with: None,
+                            deserialize_with: None,
+                            aliases: vec![],
+                            flatten: false,
+                            skip_serializing: false,
+                            skip_deserializing: false,
+                        },
+                        property_type: String::from("Option<Value>"),
+                        description: None,
+                        map_constraints: None,
+                        value_constraints: None,
+                        requires: vec![],
+                    }],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
+                },
+                GeneratedType {
+                    src: format!("{}#/properties/c", file),
+                    name: String::from("A2"),
+                    doc_alias: None,
+                    description: None,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::Struct,
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
+                    properties: vec![GeneratedProperty {
+                        name: String::from("foo"),
+                        serde_options: SerdeOptions {
+                            rename: None,
+                            skip_serializing_if: Some(String::from("Option::is_none")),
+                            default: None,
+                            with: None,
+                            deserialize_with: None,
+                            aliases: vec![],
+                            flatten: false,
+                            skip_serializing: false,
+                            skip_deserializing: false,
+                        },
+                        property_type: String::from("Option<Value>"),
+                        description: None,
+                        map_constraints: None,
+                        value_constraints: None,
+                        requires: vec![],
+                    }],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
+                }
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_name_nested_objects_by_property_name() {
+        let mut generator = Generator::new();
+        generator.add_file(Path::new(
+            "src/examples/generator/property.path.naming.schema.json",
+        ));
+
+        let types: Vec<GeneratedType> = generator.into();
+        let names: Vec<String> = types.into_iter().map(|x| x.name).collect();
+
+        assert_eq!(names, vec!["Config", "Network", "Resolver", "Proxy"]);
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_name_nested_objects_by_property_path() {
+        let mut generator = Generator::new();
+        generator.set_naming_strategy(NamingStrategy::PropertyPath);
+        generator.add_file(Path::new(
+            "src/examples/generator/property.path.naming.schema.json",
+        ));
+
+        let types: Vec<GeneratedType> = generator.into();
+        let names: Vec<String> = types.into_iter().map(|x| x.name).collect();
+
+        assert_eq!(
+            names,
+            vec!["Config", "ConfigNetwork", "Resolver", "ConfigNetworkProxy"]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_add_doc_aliases_for_renamed_types() {
+        let mut generator = Generator::new();
+        generator.set_naming_strategy(NamingStrategy::PropertyPath);
+        generator.add_file(Path::new(
+            "src/examples/generator/property.path.naming.schema.json",
+        ));
+
+        let types: Vec<GeneratedType> = generator.into();
+        let doc_aliases: Vec<Option<&str>> = types.iter().map(|x| x.doc_alias.as_deref()).collect();
+
+        assert_eq!(
+            doc_aliases,
+            vec![None, Some("network"), None, Some("proxy")]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_prefer_root_files_in_alphabetical_order_on_name_collisions() {
+        let dir = "src/examples/generator/precedence";
+
+        for files in [["b", "a"], ["a", "b"]] {
+            let mut generator = Generator::new();
+
+            for file in &files {
+                generator.add_file(Path::new(&format!("{}/{}.schema.json", dir, file)));
+            }
+
+            let types: Vec<GeneratedType> = generator.into();
+            let names: HashMap<String, String> =
+                types.into_iter().map(|x| (x.src, x.name)).collect();
+
+            assert_eq!(
+                names.get(&format!("{}/a.schema.json", dir)),
+                Some(&String::from("Error"))
+            );
+            assert_eq!(
+                names.get(&format!("{}/b.schema.json", dir)),
+                Some(&String::from("Error1"))
+            );
+            assert_eq!(
+                names.get(&format!("{}/c.schema.json#/definitions/error", dir)),
+                Some(&String::from("Error2"))
+            );
+        }
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_update_references_to_renamed_types() {
+        let dir = "src/examples/generator/precedence";
+
+        let mut generator = Generator::new();
+        generator.add_file(Path::new(&format!("{}/b.schema.json", dir)));
+        generator.add_file(Path::new(&format!("{}/a.schema.json", dir)));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(types[0].name, "Error1");
+        assert_eq!(types[0].properties[0].property_type, "Option<Error2>");
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_namespace_types_by_file() {
+        let dir = "src/examples/generator/precedence";
+
+        let mut generator = Generator::new();
+        generator.set_namespace_by_file(true);
+        generator.add_file(Path::new(&format!("{}/b.schema.json", dir)));
+        generator.add_file(Path::new(&format!("{}/a.schema.json", dir)));
+
+        let types: Vec<GeneratedType> = generator.into();
+        let names: Vec<String> = types.into_iter().map(|x| x.name).collect();
+
+        assert_eq!(names, vec!["BError", "CError", "AError"]);
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_render_sources_relative_to_directory() {
+        let mut generator = Generator::new();
+        generator.set_source_style(SourceStyle::RelativeTo(PathBuf::from("src/examples")));
+        generator.add_file(Path::new("src/examples/generator/id.schema.json"));
+
+        let types: Vec<GeneratedType> = generator.into();
+        let sources: Vec<String> = types.into_iter().map(|x| x.src).collect();
+
+        assert_eq!(
+            sources,
+            vec![
+                "generator/id.schema.json",
+                "generator/id.schema.json#/properties/address"
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_render_sources_as_ids() {
+        let mut generator = Generator::new();
+        generator.set_scalar_definition_style(ScalarDefinitionStyle::Alias);
+        generator.set_source_style(SourceStyle::Id);
+        generator.add_file(Path::new("src/examples/generator/id.schema.json"));
+        generator.add_file(Path::new("src/examples/generator/alias.schema.json"));
+
+        let types: Vec<GeneratedType> = generator.into();
+        let sources: Vec<String> = types.into_iter().map(|x| x.src).collect();
+
+        assert_eq!(
+            sources,
+            vec![
+                "https://example.com/schemas/user.json",
+                "https://example.com/schemas/user.json#/properties/address",
+                "src/examples/generator/alias.schema.json",
+                "src/examples/generator/alias.schema.json#/definitions/UserId",
+                "src/examples/generator/alias.schema.json#/definitions/tags",
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_generate_repeatedly_without_consuming_the_generator() {
+        let file = Path::new("src/examples/generator/loop1.schema.json");
+
+        let mut generator = Generator::new();
+        generator.add_file(file);
+
+        let first = generator.generate().to_string();
+        let second = generator.generate().to_string();
+
+        let mut consumed = Generator::new();
+        consumed.add_file(file);
+        let consumed: TokenStream = consumed.into();
+
+        assert_eq!(first, second);
+        assert_eq!(first, consumed.to_string());
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_remove_added_files() {
+        let mut generator = Generator::new();
+        generator.add_file(Path::new("src/examples/generator/loop1.schema.json"));
+        generator.add_file(Path::new("src/examples/generator/alias.schema.json"));
+
+        assert!(generator.remove(Path::new("src/examples/generator/alias.schema.json")));
+        assert!(!generator.remove(Path::new("src/examples/generator/alias.schema.json")));
+
+        let types: Vec<GeneratedType> = generator.into();
+        let names: Vec<String> = types.into_iter().map(|x| x.name).collect();
+
+        assert_eq!(names, vec!["Loop", "B", "C"]);
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_forget_added_files_on_reset() {
+        let mut generator = Generator::new();
+        generator.set_scalar_definition_style(ScalarDefinitionStyle::Alias);
+        generator.add_file(Path::new("src/examples/generator/loop1.schema.json"));
+        generator.reset();
+
+        assert!(generator.types.is_empty());
+        assert!(generator.roots.is_empty());
+
+        generator.add_file(Path::new("src/examples/generator/alias.schema.json"));
+
+        let types: Vec<GeneratedType> = generator.into();
+        let names: Vec<String> = types.into_iter().map(|x| x.name).collect();
+
+        assert_eq!(names, vec!["User", "UserId", "Tags"]);
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_prefer_overlays_over_files() {
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            Path::new("src/examples/generator/unsaved.schema.json"),
+            String::from(
+                r#"{"type": "object", "title": "Unsaved", "properties": {"a": {"type": "string"}}}"#,
+            ),
+        );
+        generator.set_overlay(
+            Path::new("src/examples/generator/loop2.schema.json"),
+            String::from(
+                r#"{"definitions": {"c": {"type": "object", "properties": {"d": {"type": "string"}}}}}"#,
+            ),
+        );
+        generator.add_file(Path::new("src/examples/generator/unsaved.schema.json"));
+        generator.add_file(Path::new("src/examples/generator/loop1.schema.json"));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(types[0].name, "Unsaved");
+        assert_eq!(types[3].name, "C");
+        assert_eq!(types[3].properties[0].name, "d");
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_remove_files_of_renamed_types() {
+        let file = Path::new("src/examples/generator/renamed.schema.json");
+        let dir = std::env::temp_dir().join("jsonschema_code_generator_renamed_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        let schema = |title: &str| {
+            format!(
+                r#"{{"type": "object", "title": "{}", "properties": {{"a": {{"type": "string"}}}}}}"#,
+                title
+            )
+        };
+
+        let mut generator = Generator::new();
+        generator.set_overlay(file, schema("Old Name"));
+        generator.add_file(file);
+        assert!(generator.generate_to_dir(&dir).unwrap().is_empty());
+
+        generator.set_overlay(file, schema("New Name"));
+        generator.reset();
+        generator.add_file(file);
+        let removed = generator.generate_to_dir(&dir).unwrap();
+
+        assert_eq!(removed, vec![dir.join("old_name.rs")]);
+        assert!(std::fs::read_to_string(dir.join("mod.rs"))
+            .unwrap()
+            .contains("mod new_name ; pub use new_name :: * ;"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_emit_modules_following_the_ids() {
+        let dir = std::env::temp_dir().join("jsonschema_code_generator_modules_test");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut generator = Generator::new();
+        generator.set_modules_by_id(true);
+        generator.add_file(Path::new(
+            "src/examples/generator/modules/invoice.schema.json",
+        ));
+
+        let types = generator.resolved_types();
+
+        assert_eq!(
+            types
+                .iter()
+                .map(|x| (x.name.as_str(), x.module.join("::")))
+                .collect::<Vec<(&str, String)>>(),
+            vec![
+                ("Invoice", String::from("billing::invoice")),
+                ("Customer", String::from("crm::customer")),
+            ]
+        );
+
+        generator.generate_to_dir(&dir).unwrap();
+
+        assert!(std::fs::read_to_string(dir.join("mod.rs"))
+            .unwrap()
+            .contains("pub mod billing ; pub use billing :: * ;"));
+        assert!(
+            std::fs::read_to_string(dir.join("billing/invoice/invoice.rs"))
+                .unwrap()
+                .contains("pub struct Invoice")
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn should_use_the_rust_types_of_the_extension() {
+        let file = Path::new("rust.type.schema.json");
+
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r##"{
+  "type": "object",
+  "title": "Invoice",
+  "required": ["total"],
+  "properties": {
+    "total": { "$ref": "#/definitions/amount" },
+    "discounts": { "type": "array", "items": { "$ref": "#/definitions/amount" } },
+    "customer": {
+      "type": "object",
+      "x-rust-type": "crate::crm::Customer",
+      "properties": { "name": { "type": "string" } }
+    }
+  },
+  "definitions": {
+    "amount": { "type": "string", "x-rust-type": "crate::money::Amount" }
+  }
+}"##,
+            ),
+        );
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub total : crate :: money :: Amount"));
+        assert!(code.contains("pub discounts : Option < Vec < crate :: money :: Amount >>"));
+        assert!(code.contains("pub customer : Option < crate :: crm :: Customer >"));
+        assert!(!code.contains("pub struct Customer"));
+        assert!(!code.contains("pub type Amount"));
+    }
+
+    #[test]
+    fn should_name_types_by_source_and_pointer() {
+        let file = Path::new("names.schema.json");
+
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r##"{
+  "type": "object",
+  "properties": {
+    "a": { "$ref": "#/definitions/a" },
+    "b": { "type": "object", "properties": { "x": { "type": "string" } } },
+    "c": { "type": "object", "properties": { "y": { "type": "string" } } }
+  },
+  "definitions": {
+    "a": { "type": "object", "properties": { "z": { "type": "string" } } }
+  }
+}"##,
+            ),
+        );
+        generator.set_type_name("#", "Order");
+        generator.set_type_name("#/definitions/a", "Address");
+        generator.set_type_name("names.schema.json#/properties/b", "C");
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub struct Order {"));
+        assert!(code.contains("pub a : Option < Address >"));
+        assert!(code.contains("pub b : Option < C >"));
+        assert!(code.contains("pub c : Option < C1 >"));
+    }
+
+    #[test]
+    fn should_merge_identical_types() {
+        let file = Path::new("identical.types.schema.json");
+
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r#"{
+  "type": "object",
+  "title": "Order",
+  "properties": {
+    "billing": {
+      "type": "object",
+      "properties": {
+        "street": { "type": "string" },
+        "geo": { "type": "object", "properties": { "lat": { "type": "number" } } }
+      }
+    },
+    "shipping": {
+      "type": "object",
+      "properties": {
+        "street": { "type": "string" },
+        "geo": { "type": "object", "properties": { "lat": { "type": "number" } } }
+      }
+    },
+    "other": { "type": "object", "properties": { "geo": { "type": "string" } } }
+  }
+}"#,
+            ),
+        );
+        generator.set_merge_identical_types(true);
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub billing : Option < Billing >"));
+        assert!(code.contains("pub shipping : Option < Billing >"));
+        assert!(code.contains("pub geo : Option < Geo >"));
+        assert!(code.contains("pub struct Other"));
+        assert!(!code.contains("pub struct Shipping"));
+        assert!(!code.contains("pub struct Geo1"));
+    }
+
+    #[test]
+    fn should_replace_whole_type_names() {
+        assert_eq!(
+            replace_type_name("Option<Vec<Geo1>>", "Geo1", "Geo"),
+            "Option<Vec<Geo>>"
+        );
+        assert_eq!(replace_type_name("Geo10", "Geo1", "Geo"), "Geo10");
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_emit_modules_per_file() {
+        let mut generator = Generator::new();
+        generator.set_modules_by_file(true);
+        generator.add_file(Path::new(
+            "src/examples/generator/external/order.schema.json",
+        ));
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub mod order { use super :: * ;"));
+        assert!(code.contains("pub use order :: * ;"));
+        assert!(code.contains("pub mod common { use super :: * ;"));
+        assert!(code.contains("pub shipping : Address"));
+    }
+
+    #[test]
+    fn should_not_shadow_crates_with_modules_per_file() {
+        let mut generator = Generator::new();
+        generator.set_modules_by_file(true);
+        generator.add_str(
+            "serde.helpers.schema.json",
+            r#"{"title": "Helper", "type": "object", "properties": {"a": {"type": "string"}}}"#,
+        );
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub mod serde_ { use super :: * ;"));
+        assert!(code.contains("pub use serde_ :: * ;"));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "broken.ref.schema.json:4:24 at #/properties/owner/$ref: No local definition for /definitions/user found"
+    )]
+    fn should_cite_the_location_of_unresolvable_refs() {
+        let file = Path::new("broken.ref.schema.json");
+
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r##"{
+  "type": "object",
+  "properties": {
+    "owner": { "$ref": "#/definitions/user" }
+  }
+}"##,
+            ),
+        );
+        generator.add_file(file);
+    }
+
+    #[test]
+    fn should_return_unresolvable_refs_as_errors() {
+        let file = Path::new("order.schema.json");
+        let customer = Path::new("customer.schema.json");
+
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r##"{"title": "Order", "type": "object", "properties": {"customer": {"$ref": "customer.schema.json#/definitions/customer"}}}"##,
+            ),
+        );
+
+        match generator.try_add_file(file) {
+            Err(Error::Resolve { location, message }) => {
+                assert_eq!(
+                    location,
+                    "order.schema.json:1:74 at #/properties/customer/$ref"
+                );
+                assert!(message.starts_with("Could not open customer.schema.json"));
+            }
+            result => panic!("Expected a resolve error, got {:?}", result),
+        }
+        assert!(generator.plan().types.is_empty());
+
+        generator.set_overlay(
+            customer,
+            String::from(r#"{"definitions": {"customer": {"type": "object", "properties": {"name": {"type": "string"}}}}}"#),
+        );
+
+        assert!(generator.try_add_file(file).is_ok());
+
+        generator.set_overlay(customer, String::from(r#"{"definitions": {}}"#));
+
+        match generator.generate_result() {
+            Err(Error::Resolve { message, .. }) => {
+                assert_eq!(
+                    message,
+                    "No local definition for /definitions/customer found"
+                )
+            }
+            result => panic!("Expected a resolve error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_return_options_which_are_no_code_as_errors() {
+        let mut generator = Generator::new();
+        generator.add_str(
+            "user.json",
+            r#"{"title": "User", "type": "object", "properties": {"id": {"type": "string", "format": "uuid"}}}"#,
+        );
+
+        generator.set_header("use serde::{Serialize, Deserialize;");
+        match generator.generate_result() {
+            Err(Error::Config { option, .. }) => assert_eq!(option, "header"),
+            result => panic!("Expected a config error, got {:?}", result),
+        }
+
+        generator.set_header("use serde::{Serialize, Deserialize};");
+        generator.set_crate_paths(CratePaths {
+            serde_json: String::from("\"my_facade::serde_json"),
+            ..CratePaths::default()
+        });
+        match generator.generate_result() {
+            Err(Error::Config { option, .. }) => assert_eq!(option, "crate_paths.serde_json"),
+            result => panic!("Expected a config error, got {:?}", result),
+        }
+
+        generator.set_crate_paths(CratePaths::default());
+        generator.set_format_type("uuid", "uuid::Uuid)");
+        match generator.generate_result() {
+            Err(Error::Config { option, .. }) => assert_eq!(option, "formats"),
+            result => panic!("Expected a config error, got {:?}", result),
+        }
+
+        generator.set_format_type("uuid", "uuid::Uuid");
+        assert!(generator.generate_result().is_ok());
+    }
+
+    #[test]
+    fn should_report_defaults_violating_their_schema_when_verifying() {
+        let file = Path::new("src/examples/generator/invalid.default.schema.json");
+
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r#"{"type": "object", "title": "Server", "properties": {
+                    "port": {"type": "integer", "maximum": 65535, "default": 80800},
+                    "host": {"type": "string", "default": "localhost"}}}"#,
+            ),
+        );
+        generator.add_file(file);
+
+        assert!(generator.report().default_diagnostics.is_empty());
+
+        generator.set_verify_defaults(true);
+
+        assert_eq!(
+            generator.report().default_diagnostics,
+            vec![DefaultDiagnostic {
+                src: format!("{}#/properties/port", file.display()),
+                message: String::from("default 80800 is greater than the maximum 65535"),
+            }]
+        );
+    }
+
+    #[test]
+    fn should_check_compatibility_against_a_saved_snapshot() {
+        let file = Path::new("src/examples/generator/evolving.schema.json");
+        let schema = |required: &str| {
+            format!(
+                r#"{{"type": "object", "title": "Event", "required": [{}],
+                    "properties": {{"id": {{"type": "string"}}, "at": {{"type": "integer"}}}}}}"#,
+                required
+            )
+        };
+
+        let mut generator = Generator::new();
+        generator.set_overlay(file, schema(r#""id""#));
+        generator.add_file(file);
+        let saved = serde_json::to_string(&generator.snapshot()).unwrap();
+
+        generator.set_overlay(file, schema(r#""id", "at""#));
+        generator.reset();
+        generator.add_file(file);
+        let report = generator.check_compatibility(&serde_json::from_str(&saved).unwrap());
+
+        assert_eq!(report.compatibility, Compatibility::Forward);
+        assert_eq!(report.changes[0].property, "at");
+        assert_eq!(report.changes[0].description, "made property required");
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_read_files_again_after_removing_overlays() {
+        let file = Path::new("src/examples/generator/loop2.schema.json");
+
+        let mut generator = Generator::new();
+        generator.set_overlay(file, String::from(r#"{"definitions": {"c": {}}}"#));
+        generator.add_file(Path::new("src/examples/generator/loop1.schema.json"));
+        generator.remove_overlay(file);
+        generator.reset();
+        generator.add_file(Path::new("src/examples/generator/loop1.schema.json"));
+
+        let types: Vec<GeneratedType> = generator.into();
+        let names: Vec<String> = types.into_iter().map(|x| x.name).collect();
+
+        assert_eq!(names, vec!["Loop", "B", "C"]);
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_plan_types_with_references() {
+        let mut generator = Generator::new();
+        generator.set_scalar_definition_style(ScalarDefinitionStyle::Alias);
+        generator.add_file(Path::new("src/examples/generator/alias.schema.json"));
+
+        let plan = generator.plan();
+
+        assert_eq!(
+            plan.types,
+            vec![
+                PlannedType {
+                    name: String::from("User"),
+                    src: String::from("src/examples/generator/alias.schema.json"),
+                    kind: String::from("struct"),
+                    target: None,
+                    references: vec![String::from("UserId"), String::from("Tags")],
+                    fields: vec![
+                        PlannedField {
+                            name: String::from("id"),
+                            json_name: String::from("id"),
+                            rust_type: String::from("UserId"),
+                            required: true,
+                        },
+                        PlannedField {
+                            name: String::from("tags"),
+                            json_name: String::from("tags"),
+                            rust_type: String::from("Option<Tags>"),
+                            required: false,
+                        },
+                    ],
+                },
+                PlannedType {
+                    name: String::from("UserId"),
+                    src: String::from(
+                        "src/examples/generator/alias.schema.json#/definitions/UserId"
+                    ),
+                    kind: String::from("alias"),
+                    target: Some(String::from("String")),
+                    references: vec![],
+                    fields: vec![],
+                },
+                PlannedType {
+                    name: String::from("Tags"),
+                    src: String::from("src/examples/generator/alias.schema.json#/definitions/tags"),
+                    kind: String::from("alias"),
+                    target: Some(String::from("Vec<String>")),
+                    references: vec![],
+                    fields: vec![],
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_refine_refs_with_sibling_properties_and_required() {
+        let mut generator = Generator::new();
+        generator.add_file(Path::new(
+            "src/examples/generator/ref.refinement.schema.json",
+        ));
+
+        let types: Vec<GeneratedType> = generator.into();
+        let summary: Vec<(String, Vec<(String, String)>)> = types
+            .into_iter()
+            .map(|x| {
+                (
+                    x.name,
+                    x.properties
+                        .into_iter()
+                        .map(|p| (p.name, p.property_type))
+                        .collect(),
+                )
+            })
+            .collect();
+
+        let pair =
+            |name: &str, property_type: &str| (String::from(name), String::from(property_type));
+
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    String::from("Order"),
+                    vec![pair("other", "Option<Pet>"), pair("pet", "Option<Pet1>")]
+                ),
+                (
+                    String::from("Pet"),
+                    vec![
+                        pair("name", "Option<String>"),
+                        pair("tag", "Option<String>")
+                    ]
+                ),
+                (
+                    String::from("Pet1"),
+                    vec![
+                        pair("name", "String"),
+                        pair("tag", "Option<String>"),
+                        pair("age", "Option<i64>")
+                    ]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_not_add_properties_for_names_a_ref_refinement_only_requires() {
+        let mut generator = Generator::new();
+        generator.add_value(
+            "order.json",
+            json!({
+                "title": "Order",
+                "type": "object",
+                "properties": {
+                    "pet": {"$ref": "#/definitions/pet", "required": ["name", "chip"]}
+                },
+                "definitions": {
+                    "pet": {"type": "object", "properties": {"name": {"type": "string"}}}
+                }
+            }),
+        );
+
+        let types: Vec<GeneratedType> = generator.into();
+        let pet = types.iter().find(|x| x.name == "Pet").unwrap();
+
+        assert_eq!(
+            pet.properties
+                .iter()
+                .map(|x| (x.name.as_str(), x.property_type.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("name", "String")]
+        );
+    }
+
+    #[test]
+    fn should_name_merged_all_of_types_after_their_own_schema() {
+        let mut generator = Generator::new();
+        generator.add_value(
+            "order.json",
+            json!({
+                "title": "Order",
+                "type": "object",
+                "properties": {
+                    "item": {"allOf": [{"$ref": "#/definitions/base"}, {"required": ["id"]}]},
+                    "base": {"$ref": "#/definitions/base"},
+                    "extended": {
+                        "allOf": [
+                            {"$ref": "#/definitions/base"},
+                            {"type": "object", "properties": {"extra": {"type": "string"}}}
+                        ]
+                    }
+                },
+                "definitions": {
+                    "base": {"type": "object", "properties": {"id": {"type": "string"}}}
+                }
+            }),
+        );
+
+        let types: Vec<GeneratedType> = generator.into();
+        let summary: Vec<(&str, Vec<(&str, &str)>)> = types
+            .iter()
+            .map(|x| {
+                (
+                    x.name.as_str(),
+                    x.properties
+                        .iter()
+                        .map(|p| (p.name.as_str(), p.property_type.as_str()))
+                        .collect(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "Order",
+                    vec![
+                        ("base", "Option<Base>"),
+                        ("extended", "Option<Extended>"),
+                        ("item", "Option<Item>")
+                    ]
+                ),
+                ("Base", vec![("id", "Option<String>")]),
+                (
+                    "Extended",
+                    vec![("id", "Option<String>"), ("extra", "Option<String>")]
+                ),
+                ("Item", vec![("id", "String")]),
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_dedupe_referenced_and_directly_added_definitions() {
+        let file = Path::new("src/examples/generator/ref.refinement.schema.json");
+        let root = Rc::new(parse_from_file(file, &Overlay::new()));
+
+        let mut generator = Generator::new();
+        generator.add_file(file);
+        let name = generator.add(root.clone(), &root.definitions["pet"]);
+
+        assert_eq!(name, "Option<Pet>");
+        assert_eq!(generator.types.len(), 3);
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_create_untagged_enums_for_one_of_properties() {
+        let mut generator = Generator::new();
+        generator.add_file(Path::new(
+            "src/examples/generator/one.of.property.schema.json",
+        ));
+
+        let types: Vec<GeneratedType> = generator.into();
+        let names: Vec<&str> = types.iter().map(|x| x.name.as_str()).collect();
+
+        assert_eq!(names, vec!["Job", "Status", "Detail", "StatusVariant2"]);
+        assert_eq!(types[0].properties[1].property_type, "Option<Status>");
+        assert_eq!(
+            types[1].kind,
+            TypeKind::UntaggedEnum(vec![
+                GeneratedVariant {
+                    name: String::from("Detail"),
+                    variant_type: String::from("Detail"),
+                },
+                GeneratedVariant {
+                    name: String::from("StatusVariant2"),
+                    variant_type: String::from("StatusVariant2"),
+                },
+                GeneratedVariant {
+                    name: String::from("String"),
+                    variant_type: String::from("String"),
+                },
+            ])
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_create_aliases_for_scalar_definitions() {
+        let file = "src/examples/generator/alias.schema.json";
+
+        let mut generator = Generator::new();
+
+        generator.set_scalar_definition_style(ScalarDefinitionStyle::Alias);
+        generator.set_format_type("uuid", "String");
+        generator.add_file(Path::new(file));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(
+            types
+                .iter()
+                .map(|x| (x.name.as_str(), &x.kind))
+                .collect::<Vec<(&str, &TypeKind)>>(),
+            vec![
+                ("User", &TypeKind::Struct),
+                ("UserId", &TypeKind::Alias(String::from("String"))),
+                ("Tags", &TypeKind::Alias(String::from("Vec<String>"))),
+            ]
+        );
+
+        assert_eq!(
+            types[0]
+                .properties
+                .iter()
+                .map(|x| x.property_type.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["UserId", "Option<Tags>"]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_create_newtypes_for_primitive_definitions() {
+        let file = "src/examples/generator/alias.schema.json";
+
+        let mut generator = Generator::new();
+        generator.set_scalar_definition_style(ScalarDefinitionStyle::Newtype);
+        generator.set_format_type("uuid", "String");
+        generator.add_file(Path::new(file));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(
+            types
+                .iter()
+                .map(|x| (x.name.as_str(), &x.kind))
+                .collect::<Vec<(&str, &TypeKind)>>(),
+            vec![
+                ("User", &TypeKind::Struct),
+                ("UserId", &TypeKind::Newtype(String::from("String"))),
+                ("Tags", &TypeKind::Alias(String::from("Vec<String>"))),
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_create_constants_for_single_values() {
+        let file = "src/examples/generator/constant.schema.json";
+
+        let mut generator = Generator::new();
+        generator.add_file(Path::new(file));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(
+            types[0].constants,
+            vec![
+                GeneratedConstant {
+                    name: String::from("KIND"),
+                    constant_type: String::from("&str"),
+                    value: json!("message"),
+                    default_function: String::from("default_kind"),
+                    property_type: String::from("Option<String>"),
+                },
+                GeneratedConstant {
+                    name: String::from("RATIO"),
+                    constant_type: String::from("f64"),
+                    value: json!(0.5),
+                    default_function: String::from("default_ratio"),
+                    property_type: String::from("Option<Value>"),
+                },
+                GeneratedConstant {
+                    name: String::from("VERSION"),
+                    constant_type: String::from("i64"),
+                    value: json!(2),
+                    default_function: String::from("default_version"),
+                    property_type: String::from("i64"),
+                },
+            ]
+        );
+
+        assert_eq!(
+            types[0]
+                .properties
+                .iter()
+                .map(|x| x.serde_options.default.as_deref())
+                .collect::<Vec<Option<&str>>>(),
+            vec![
+                Some("Message::default_kind"),
+                Some("Message::default_ratio"),
+                Some("Message::default_version"),
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_collapse_wrappers_by_pointer() {
+        let file = "src/examples/generator/wrapper.schema.json";
+
+        let mut generator = Generator::new();
+        generator.collapse_wrapper(file);
+        generator.add_file(Path::new(file));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(
+            types
+                .iter()
+                .map(|x| (x.name.as_str(), &x.kind))
+                .collect::<Vec<(&str, &TypeKind)>>(),
+            vec![
+                ("Envelope", &TypeKind::Wrapper),
+                ("Payload", &TypeKind::Struct),
+                ("Meta", &TypeKind::Struct),
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_collapse_all_wrappers() {
+        let file = "src/examples/generator/wrapper.schema.json";
+
+        let mut generator = Generator::new();
+        generator.set_collapse_wrappers(true);
+        generator.add_file(Path::new(file));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(
+            types
+                .iter()
+                .map(|x| (x.name.as_str(), &x.kind))
+                .collect::<Vec<(&str, &TypeKind)>>(),
+            vec![
+                ("Envelope", &TypeKind::Wrapper),
+                ("Payload", &TypeKind::Struct),
+                ("Meta", &TypeKind::Wrapper),
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_inline_small_anonymous_objects_as_maps() {
+        let file = "src/examples/generator/inline.objects.schema.json";
+
+        let mut generator = Generator::new();
+        generator.set_inline_object_threshold(Some(2));
+        generator.add_file(Path::new(file));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(
+            types.iter().map(|x| x.name.as_str()).collect::<Vec<&str>>(),
+            vec!["Widget", "Point", "Style"]
+        );
+        assert_eq!(
+            types[0]
+                .properties
+                .iter()
+                .map(|x| (x.name.as_str(), x.property_type.as_str()))
+                .collect::<Vec<(&str, &str)>>(),
+            vec![
+                ("label", "Option<BTreeMap<String, Value>>"),
+                ("origin", "Option<Point>"),
+                ("size", "Option<BTreeMap<String, i64>>"),
+                ("style", "Option<Style>"),
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_inline_objects_by_pointer() {
+        let file = "src/examples/generator/inline.objects.schema.json";
+
+        let mut generator = Generator::new();
+        generator.inline_object(&format!("{}#/properties/style", file));
+        generator.add_file(Path::new(file));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(
+            types.iter().map(|x| x.name.as_str()).collect::<Vec<&str>>(),
+            vec!["Widget", "Label", "Point", "Size"]
+        );
+        assert_eq!(
+            types[0].properties[3].property_type,
+            "Option<BTreeMap<String, Value>>"
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_create_key_types_for_property_names_patterns() {
+        let file = "src/examples/generator/map.key.schema.json";
+
+        let mut generator = Generator::new();
+        generator.add_file(Path::new(file));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(
+            types
+                .iter()
+                .map(|x| (x.name.as_str(), &x.kind))
+                .collect::<Vec<(&str, &TypeKind)>>(),
+            vec![
+                ("Service", &TypeKind::Struct),
+                ("LabelsKey", &TypeKind::MapKey(String::from("^[a-z]+$"))),
+                (
+                    "PortName",
+                    &TypeKind::MapKey(String::from("^[a-z][a-z0-9-]*$"))
+                ),
+                ("Port", &TypeKind::Struct),
+            ]
+        );
+        assert_eq!(
+            types[0]
+                .properties
+                .iter()
+                .map(|x| x.property_type.as_str())
+                .collect::<Vec<&str>>(),
+            vec![
+                "Option<BTreeMap<LabelsKey, Value>>",
+                "Option<BTreeMap<PortName, Port>>",
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_create_value_enums_for_several_pattern_properties() {
+        let mut generator = Generator::new();
+        generator.add_file(Path::new("src/examples/generator/map.patterns.schema.json"));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(
+            types[0].properties[0].property_type,
+            "Option<BTreeMap<String, FlagsValue>>"
+        );
+        assert_eq!(types[1].name, "FlagsValue");
+        assert_eq!(
+            types[1].kind,
+            TypeKind::UntaggedEnum(vec![
+                GeneratedVariant {
+                    name: String::from("Bool"),
+                    variant_type: String::from("bool"),
+                },
+                GeneratedVariant {
+                    name: String::from("Integer"),
+                    variant_type: String::from("i64"),
+                },
+            ])
+        );
+        assert_eq!(
+            types[1].description.as_deref(),
+            Some(
+                "The value of a key matching\n\
+                 - `^enable_` is a [`FlagsValue::Bool`]\n\
+                 - `^limit_` is a [`FlagsValue::Integer`]"
+            )
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_create_string_enums_on_request() {
+        let file = "src/examples/generator/string.enum.schema.json";
+
+        let mut generator = Generator::new();
+
+        generator.set_scalar_definition_style(ScalarDefinitionStyle::Alias);
+        generator.add_file(Path::new(file));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(
+            types[0]
+                .properties
+                .iter()
+                .map(|x| x.property_type.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["Option<Level>", "String", "Option<String>"]
+        );
+
+        let mut generator = Generator::new();
+
+        generator.set_scalar_definition_style(ScalarDefinitionStyle::Alias);
+        generator.set_string_enums(true);
+        generator.add_file(Path::new(file));
+
+        let types: Vec<GeneratedType> = generator.into();
+        let variant = |name: &str, value: &str| StringVariant {
+            name: String::from(name),
+            value: String::from(value),
+            description: None,
+        };
+
+        assert_eq!(
+            types
+                .iter()
+                .map(|x| (x.name.as_str(), &x.kind))
+                .collect::<Vec<(&str, &TypeKind)>>(),
+            vec![
+                ("Request", &TypeKind::Struct),
+                (
+                    "Level",
+                    &TypeKind::StringEnum(vec![variant("Debug", "debug"), variant("Info", "info")])
+                ),
+                (
+                    "Method",
+                    &TypeKind::StringEnum(vec![
+                        variant("Get", "GET"),
+                        variant("Post", "POST"),
+                        variant("Post2", "post"),
+                        variant("Value2Xx", "2xx"),
+                    ])
+                ),
+            ]
+        );
+        assert_eq!(
+            types[0]
+                .properties
+                .iter()
+                .map(|x| x.property_type.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["Option<Level>", "Method", "Option<String>"]
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_refer_to_types_of_external_schemas() {
+        let file = "src/examples/generator/external/order.schema.json";
+
+        for schema in ["common.schema.json#", "https://example.com/common.json"] {
+            let mut generator = Generator::new();
+            generator.external_schema(schema, "common_models::");
+            generator.add_file(Path::new(file));
+
+            let types: Vec<GeneratedType> = generator.into();
+
+            assert_eq!(
+                types
+                    .iter()
+                    .flat_map(|x| x.properties.iter().map(move |y| (x.name.as_str(), y)))
+                    .map(|(name, property)| (name, property.property_type.as_str()))
+                    .collect::<Vec<(&str, &str)>>(),
+                vec![
+                    ("Order", "Option<Vec<Item>>"),
+                    ("Order", "common_models::Address"),
+                    ("Item", "Option<common_models::Address>"),
+                ]
+            );
+        }
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_wrap_root_maps_in_a_newtype() {
+        let mut generator = Generator::new();
+        let name = generator.add_file(Path::new("src/examples/generator/root.map.schema.json"));
+
+        assert_eq!(name, "FeatureFlags");
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(
+            types
+                .iter()
+                .map(|x| (x.name.as_str(), &x.kind))
+                .collect::<Vec<(&str, &TypeKind)>>(),
+            vec![
+                (
+                    "FeatureFlags",
+                    &TypeKind::Map {
+                        map_type: MapType::BTreeMap,
+                        key: String::from("String"),
+                        value: String::from("FeatureFlagsValue"),
+                    }
+                ),
+                ("FeatureFlagsValue", &TypeKind::Struct),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_wrap_root_objects_without_properties_in_a_newtype() {
+        let path = Path::new("src/examples/generator/counters.schema.json");
+        let value_types: Vec<TypeKind> = [
+            EmptyObjectStyle::Map,
+            EmptyObjectStyle::AdditionalProperties,
+        ]
+        .iter()
+        .map(|style| {
+            let mut generator = Generator::new();
+            generator.set_empty_object_style(*style);
+            generator.set_overlay(
+                path,
+                String::from(r#"{"type": "object", "additionalProperties": {"type": "integer"}}"#),
+            );
+
+            assert_eq!(generator.add_file(path), "Counters");
+
+            let mut types: Vec<GeneratedType> = generator.into();
+            types.remove(0).kind
+        })
+        .collect();
+
+        assert_eq!(
+            value_types,
+            ["Value", "i64"]
+                .iter()
+                .map(|value| TypeKind::Map {
+                    map_type: MapType::BTreeMap,
+                    key: String::from("String"),
+                    value: value.to_string(),
+                })
+                .collect::<Vec<TypeKind>>()
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_list_the_files_refs_were_resolved_in() {
+        let dir = Path::new("src/examples/generator/transitive");
+        let mut generator = Generator::new();
+        generator.add_file(&dir.join("drawing.schema.json"));
+
+        assert_eq!(
+            generator.dependencies(),
+            vec![
+                dir.join("shapes.schema.json"),
+                dir.join("units.schema.json")
+            ]
+        );
+        assert_eq!(generator.report().dependencies, generator.dependencies());
+
+        generator.remove(&dir.join("drawing.schema.json"));
+
+        assert!(generator.dependencies().is_empty());
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_resolve_nested_refs_against_the_file_of_the_definition() {
+        let dir = "src/examples/generator/transitive";
+        let mut generator = Generator::new();
+        generator.set_scalar_definition_style(ScalarDefinitionStyle::Alias);
+        generator.add_file(&Path::new(dir).join("drawing.schema.json"));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(
+            types
+                .iter()
+                .map(|x| (x.name.as_str(), x.src.as_str()))
+                .collect::<Vec<(&str, &str)>>(),
+            vec![
+                ("Drawing", format!("{}/drawing.schema.json", dir).as_str()),
+                (
+                    "Circle",
+                    format!("{}/shapes.schema.json#/definitions/circle", dir).as_str()
+                ),
+                (
+                    "Point",
+                    format!("{}/shapes.schema.json#/definitions/point", dir).as_str()
+                ),
+                (
+                    "Length",
+                    format!("{}/units.schema.json#/definitions/length", dir).as_str()
+                ),
+                (
+                    "Magnitude",
+                    format!("{}/units.schema.json#/definitions/magnitude", dir).as_str()
+                ),
+            ]
+        );
+        assert_eq!(
+            types[1].field_types(),
+            vec!["Option<Point>", "Option<Length>"]
+        );
+        assert_eq!(types[3].field_types()[1], "Option<Magnitude>");
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_generate_tagged_enums_for_discriminated_one_of() {
+        let mut generator = Generator::new();
+        generator.add_file(Path::new(
+            "src/examples/generator/tagged.one.of.schema.json",
+        ));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(
+            types[1].kind,
+            TypeKind::TaggedEnum {
+                tag: String::from("type"),
+                variants: vec![
+                    TaggedVariant {
+                        name: String::from("Circle"),
+                        value: String::from("circle"),
+                        variant_type: String::from("CircleShape"),
+                    },
+                    TaggedVariant {
+                        name: String::from("Square"),
+                        value: String::from("square"),
+                        variant_type: String::from("Square"),
+                    },
+                    TaggedVariant {
+                        name: String::from("NA"),
+                        value: String::from("n/a"),
+                        variant_type: String::from("NaShape"),
+                    },
+                ],
+            }
+        );
+        assert_eq!(types[2].field_types(), vec!["f64"]);
+        assert_eq!(types[3].field_types(), vec!["Option<f64>"]);
+        assert!(types[4].properties.is_empty());
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_keep_untagged_enums_without_a_shared_const_property() {
+        let mut generator = Generator::new();
+        generator.add_file(Path::new(
+            "src/examples/generator/one.of.property.schema.json",
+        ));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert!(matches!(types[1].kind, TypeKind::UntaggedEnum(_)));
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_merge_all_of_branches_of_other_files() {
+        let mut generator = Generator::new();
+        generator.add_file(Path::new(
+            "src/examples/generator/composition/employee.schema.json",
+        ));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(
+            types.iter().map(|x| x.name.as_str()).collect::<Vec<&str>>(),
+            vec!["Employee", "Address"]
+        );
+        assert_eq!(
+            types[0]
+                .properties
+                .iter()
+                .map(|x| (x.name.as_str(), x.property_type.as_str()))
+                .collect::<Vec<(&str, &str)>>(),
+            vec![
+                ("address", "Option<Address>"),
+                ("name", "String"),
+                ("since", "String"),
+                ("badge", "String"),
+            ]
+        );
+        assert_eq!(
+            types[1].src,
+            "src/examples/generator/composition/person.schema.json#/definitions/address"
+        );
+    }
+
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn should_emit_index_maps() {
+        let mut generator = Generator::new();
+        generator.set_map_type(MapType::IndexMap);
+        generator.add_file(Path::new("src/examples/generator/root.map.schema.json"));
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("use indexmap :: IndexMap ;"));
+        assert!(code
+            .contains("pub struct FeatureFlags (pub IndexMap < String , FeatureFlagsValue >) ;"));
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_apply_fluent_configuration() {
+        let mut generator = Generator::new_with_options(
+            GeneratorOptions::default()
+                .with_derives(["Eq", "Default"])
+                .with_map_type(MapType::HashMap),
+        );
+        generator.add_file(Path::new("src/examples/generator/root.map.schema.json"));
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("use std :: collections :: HashMap ;"));
+        assert!(
+            code.contains("pub struct FeatureFlags (pub HashMap < String , FeatureFlagsValue >) ;")
+        );
+        assert!(code.contains(
+            "# [derive (Clone , PartialEq , Debug , Default , Deserialize , Serialize , Eq)] # [serde (transparent)] pub struct FeatureFlags"
+        ));
+        assert!(code.contains(
+            "# [derive (Clone , PartialEq , Debug , Deserialize , Serialize , Eq , Default)] pub struct FeatureFlagsValue"
+        ));
+    }
+
+    #[test]
+    fn should_emit_builders_for_large_structs() {
+        let file = Path::new("builder.schema.json");
+
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r#"{
+  "type": "object",
+  "title": "Order",
+  "required": ["id", "count"],
+  "properties": {
+    "id": { "type": "string" },
+    "count": { "type": "integer", "default": 3 },
+    "note": { "type": "string" },
+    "item": { "type": "object", "properties": { "sku": { "type": "string" } } }
+  }
+}"#,
+            ),
+        );
+        generator.set_builder_threshold(Some(2));
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("# [derive (Default)] pub struct OrderBuilder { count : Option < i64 > , id : Option < String > , item : Option < Item > , note : Option < String > }"));
+        assert!(code.contains(
+            "pub fn note (mut self , value : impl Into < String >) -> Self { self . note = Some (value . into ()) ; self }"
+        ));
+        assert!(code.contains("count : self . count . unwrap_or_else (Order :: default_count) , id : self . id . ok_or (\"id is missing\") ? , item : self . item , note : self . note"));
+        assert!(code.contains("pub fn builder () -> OrderBuilder { < OrderBuilder as :: core :: default :: Default > :: default () }"));
+        assert!(!code.contains("ItemBuilder"));
+    }
+
+    #[test]
+    fn should_rename_setters_of_builder_methods() {
+        let mut generator = Generator::new();
+        generator.set_builder_threshold(Some(1));
+        generator.add_value(
+            "options.json",
+            json!({
+                "title": "Options",
+                "type": "object",
+                "properties": {
+                    "build": {"type": "string"},
+                    "default": {"type": "string"},
+                    "new": {"type": "string"}
+                }
+            }),
+        );
+
+        let code = generator.generate().to_string();
+
+        for name in ["build", "default", "new"] {
+            assert!(code.contains(&format!(
+                "pub fn set_{} (mut self , value : impl Into < String >) -> Self {{ self . {} = Some (value . into ()) ; self }}",
+                name, name
+            )));
+        }
+    }
+
+    #[test]
+    fn should_skip_read_only_and_write_only_properties() {
+        let file = Path::new("access.schema.json");
+
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r#"{
+  "type": "object",
+  "title": "User",
+  "required": ["id", "name", "password"],
+  "properties": {
+    "id": { "type": "string", "readOnly": true },
+    "name": { "type": "string" },
+    "password": { "type": "string", "writeOnly": true }
+  }
+}"#,
+            ),
+        );
+        generator.add_file(file);
+
+        assert!(generator.generate().to_string().contains(
+            "pub struct User { pub id : String , pub name : String , pub password : String }"
+        ));
+
+        generator.set_read_write_only(true);
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("# [serde (skip_serializing)] pub id : String"));
+        assert!(code.contains("pub name : String"));
+        assert!(code.contains(
+            "# [serde (skip_deserializing)] # [serde (skip_serializing_if = \"Option::is_none\")] pub password : Option < String >"
+        ));
+    }
+
+    #[test]
+    fn should_document_the_variants_of_string_enums() {
+        let file = Path::new("documented.enum.schema.json");
+
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r#"{
+  "type": "object",
+  "title": "Config",
+  "properties": {
+    "level": {
+      "title": "LogLevel",
+      "description": "How much to log",
+      "oneOf": [
+        { "const": "debug", "description": "Everything" },
+        { "const": "info", "title": "Only news" }
+      ]
+    }
+  }
+}"#,
+            ),
+        );
+        generator.add_file(file);
+
+        assert!(generator
+            .generate()
+            .to_string()
+            .contains("pub level : Option < String >"));
+
+        generator.set_string_enums(true);
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub level : Option < LogLevel >"));
+        assert!(code.contains("# [doc = \"How much to log\"]"));
+        assert!(code.contains(
+            "pub enum LogLevel { # [doc = \"Everything\"] Debug , # [doc = \"Only news\"] Info , }"
+        ));
+    }
+
+    #[test]
+    fn should_rename_all_fields_which_follow_one_case() {
+        let file = Path::new("rename.all.schema.json");
+
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r#"{
+  "type": "object",
+  "title": "Account",
+  "properties": {
+    "id": { "type": "string" },
+    "userName": { "type": "string" },
+    "createdAt": { "type": "string" },
+    "settings": {
+      "type": "object",
+      "properties": {
+        "darkMode": { "type": "boolean" },
+        "font-size": { "type": "integer" }
+      }
+    }
+  }
+}"#,
+            ),
+        );
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("# [serde (rename_all = \"camelCase\")] pub struct Account {"));
+        assert!(code.contains(
+            "# [doc (alias = \"createdAt\")] # [serde (skip_serializing_if = \"Option::is_none\")] pub created_at"
+        ));
+        assert!(code.contains("# [serde (rename = \"darkMode\")]"));
+        assert!(code.contains("# [serde (rename = \"font-size\")]"));
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_add_and_remove_derives_per_type() {
+        let mut generator = Generator::default();
+        generator.remove_derives(["Debug"]);
+        generator.add_type_derives("FeatureFlagsValue", ["Eq", "Hash"]);
+        generator.remove_type_derives("FeatureFlags", ["PartialEq", "Default"]);
+        generator.add_file(Path::new("src/examples/generator/root.map.schema.json"));
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains(
+            "# [derive (Clone , Deserialize , Serialize)] # [serde (transparent)] pub struct FeatureFlags"
+        ));
+        assert!(code.contains(
+            "# [derive (Clone , PartialEq , Deserialize , Serialize , Eq , Hash)] pub struct FeatureFlagsValue"
+        ));
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_validate_any_of_required_sets() {
+        let mut generator = Generator::new();
+        generator.add_file(Path::new("src/examples/parser/required.any.schema.json"));
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains(
+            "if ! (self . email . is_some () || self . phone . is_some () || (self . name . is_some () && self . postal_address . is_some ()))"
+        ));
+        assert!(code.contains("\"Expected email, or phone, or name and postalAddress\""));
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_check_required_names_without_property_in_an_extra_map() {
+        let file = "src/examples/generator/required.unmatched.schema.json";
+
+        let mut generator = Generator::new();
+        generator.add_file(Path::new(file));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(types[0].properties.len(), 1);
+
+        let mut generator = Generator::new();
+        generator.set_extra_properties(true);
+        generator.add_file(Path::new(file));
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains(
+            "# [doc = \"Requiring `issuer`\"] # [serde (flatten)] pub extra : BTreeMap < String , Value >"
+        ));
+        assert!(code.contains("if ! value . contains_key (\"issuer\")"));
+        assert!(code.contains("\"Missing the required property issuer\""));
+
+        let mut generator = Generator::new();
+        generator.set_extra_properties(true);
+        generator.set_simd_json(true);
+        generator.add_file(Path::new(file));
+
+        let code = generator.generate().to_string();
+
+        assert!(!code.contains("# [serde (flatten)]"));
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_emit_type_arrays_with_null_as_options() {
+        let mut generator = Generator::new();
+        generator.add_file(Path::new("src/examples/generator/type.union.schema.json"));
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub name : String ,"));
+        assert!(code.contains("pub nickname : Option < String > ,"));
+        assert!(code.contains("pub age : Option < i64 > ,"));
+        assert!(code.contains("pub tags : Option < Vec < Option < String >>> }"));
+        assert!(code.contains("pub address : Option < Address > ,"));
+        assert!(code.contains("pub struct Address {"));
+        assert!(code.contains("pub enum Id { String (String) , Integer (i64) }"));
+    }
+
+    #[test]
+    fn should_download_remote_refs_once() {
+        let downloads = Rc::new(RefCell::new(vec![]));
+        let file = Path::new("order.json");
+        let mut generator = Generator::new();
+        generator.set_scalar_definition_style(ScalarDefinitionStyle::Alias);
+        generator.set_overlay(
+            file,
+            String::from(
+                r##"{"title": "Order", "type": "object", "properties": {"billing": {"$ref": "https://example.com/schemas/address.json"}, "shipping": {"$ref": "https://example.com/schemas/address.json#/definitions/Street"}}}"##,
+            ),
+        );
+
+        let log = downloads.clone();
+        generator.set_fetch(move |url| {
+            log.borrow_mut().push(url.to_string());
+
+            match url {
+                "https://example.com/schemas/address.json" => Ok(String::from(
+                    r##"{"title": "Address", "type": "object", "properties": {"country": {"$ref": "../common/country.json"}}, "definitions": {"Street": {"type": "string"}}}"##,
+                )),
+                "https://example.com/common/country.json" => {
+                    Ok(String::from(r#"{"title": "Country", "type": "string"}"#))
+                }
+                _ => Err(String::from("404 Not Found")),
+            }
+        });
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub billing : Option < Address > ,"));
+        assert!(code.contains("pub shipping : Option < Street > }"));
+        assert!(code.contains("pub country : Option < String > }"));
+        assert_eq!(
+            *downloads.borrow(),
+            vec![
+                "https://example.com/schemas/address.json",
+                "https://example.com/common/country.json"
+            ]
+        );
+        assert_eq!(generator.files(), vec![PathBuf::from("order.json")]);
+    }
+
+    #[test]
+    fn should_fail_on_remote_refs_without_fetch() {
+        let file = Path::new("order.json");
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(r#"{"$ref": "https://example.com/order.json"}"#),
+        );
+
+        assert_eq!(
+            generator.try_add_file(file),
+            Err(Error::Resolve {
+                location: String::from("order.json:1:10 at #/$ref"),
+                message: String::from("https://example.com/order.json is remote, downloading it has to be enabled with Generator::set_fetch"),
+            })
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_resolve_refs_to_json_pointers_into_the_schema() {
+        let mut generator = Generator::new();
+        generator.add_file(Path::new("src/examples/generator/pointer.refs.schema.json"));
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub first : Option < Line > ,"));
+        assert!(code.contains("pub lines : Option < Vec < Line >> ,"));
+        assert!(code.contains("pub note : Option < String > }"));
+        assert_eq!(code.matches("pub struct Line").count(), 1);
+    }
+
+    #[test]
+    fn should_parse_every_file_with_the_rules_of_its_draft() {
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            Path::new("order.json"),
+            String::from(
+                r##"{"$schema": "https://json-schema.org/draft/2020-12/schema", "title": "Order", "type": "object", "properties": {"customer": {"$ref": "legacy.json#/definitions/Customer", "properties": {"vip": {"type": "boolean"}}}}}"##,
+            ),
+        );
+        generator.set_overlay(
+            Path::new("legacy.json"),
+            String::from(
+                r##"{"$schema": "http://json-schema.org/draft-04/schema#", "definitions": {"Customer": {"type": "object", "properties": {"address": {"$ref": "#/definitions/Address", "properties": {"zip": {"type": "string"}}}}}, "Address": {"type": "object", "properties": {"street": {"type": "string"}}}}}"##,
+            ),
+        );
+        generator.add_file(Path::new("order.json"));
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub vip : Option < bool >"));
+        assert!(code.contains("pub address : Option < Address > ,"));
+        assert!(!code.contains("zip"));
+    }
+
+    #[test]
+    fn should_only_generate_the_subset_of_the_pointer() {
+        let file = Path::new("specs/api.json");
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r##"{"definitions": {"User": {"type": "object", "properties": {"address": {"$ref": "#/definitions/Address"}}}, "Address": {"type": "object", "properties": {"street": {"type": "string"}}}, "Unrelated": {"type": "object", "properties": {"id": {"type": "integer"}}}}}"##,
+            ),
+        );
+
+        let type_name = generator
+            .try_add_subset(file, "#/definitions/User")
+            .unwrap();
+        let code = generator.generate().to_string();
+
+        assert_eq!(type_name, "Option<User>");
+        assert!(code.contains("pub struct User { # [serde (skip_serializing_if = \"Option::is_none\")] pub address : Option < Address > }"));
+        assert!(code.contains("pub struct Address"));
+        assert!(!code.contains("Unrelated"));
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_read_properties_from_their_aliases() {
+        let file = "src/examples/generator/aliases.schema.json";
+
+        let mut generator = Generator::new();
+        generator.alias_property(&format!("{}#/properties/email", file), "mail");
+        generator.alias_property(&format!("{}#/properties/displayName", file), "name");
+        generator.add_file(Path::new(file));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(
+            types[0]
+                .properties
+                .iter()
+                .map(|x| x.serde_options.aliases.clone())
+                .collect::<Vec<Vec<String>>>(),
+            vec![
+                vec![String::from("display_name"), String::from("name")],
+                vec![String::from("mail")],
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs")]
     #[test]
-    fn should_add_ref_type() {
+    fn should_validate_map_constraints() {
+        let file = "src/examples/generator/map.constraints.schema.json";
+
         let mut generator = Generator::new();
+        generator.add_file(Path::new(file));
 
-        let type_name = add_type(
-            &mut generator,
-            DataType::Ref(Ref {
-                ref_path: String::from("#/$defs/foo"),
-            }),
-            true,
-        );
+        let code = generator.generate().to_string();
 
-        assert_eq!(type_name, "AwesomeFoo");
+        assert!(code.contains("# [doc = \"Between 1 and 16 entries, keys matching `^x-`\"]"));
+        assert!(code.contains("# [doc = \"At most 1 entry\"]"));
+        assert!(code.contains(
+            "pub fn validate (& self) -> Result < () , ValidationError > { { let value = & self . headers ;"
+        ));
+        assert!(code.contains("if let Some (value) = & self . query {"));
+        assert!(code.contains("regex :: Regex :: new (\"^x-\")"));
     }
 
     #[test]
-    fn should_add_optional_ref_type() {
-        let mut generator = Generator::new();
+    fn should_validate_value_constraints() {
+        let file = Path::new("value.constraints.schema.json");
 
-        let type_name = add_type(
-            &mut generator,
-            DataType::Ref(Ref {
-                ref_path: String::from("#/$defs/foo"),
-            }),
-            false,
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r#"{
+  "type": "object",
+  "title": "Order",
+  "required": ["code", "count"],
+  "properties": {
+    "code": { "type": "string", "pattern": "^[A-Z]+$", "maxLength": 8 },
+    "count": { "type": "integer", "minimum": 1, "maximum": 100, "multipleOf": 5 },
+    "small": { "type": "integer", "minimum": 0, "maximum": 255 },
+    "price": { "type": "number", "exclusiveMinimum": 0, "multipleOf": 0.01 },
+    "tags": { "type": "array", "items": { "type": "string" }, "minItems": 1 }
+  }
+}"#,
+            ),
         );
-
-        assert_eq!(type_name, "Option<AwesomeFoo>");
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub fn validate (& self) -> Result < () , ValidationError > { { let value = & self . code ; { static PATTERN : std :: sync :: OnceLock < Result < regex :: Regex , String >> = std :: sync :: OnceLock :: new () ; let pattern = PATTERN . get_or_init (|| regex :: Regex :: new (\"^[A-Z]+$\")"));
+        assert!(code.contains("if value . chars () . count () as u64 > 8 {"));
+        assert!(code.contains("let value = & self . count ; if * value < 1 {"));
+        assert!(code.contains("if * value % 5 != 0 {"));
+        assert!(!code.contains("self . small"));
+        assert!(code.contains("if let Some (value) = & self . price { if * value <= 0.0 {"));
+        assert!(code.contains("let quotient = * value / 0.01 ;"));
+        assert!(code.contains("if (value . len () as u64) < 1 {"));
     }
 
     #[test]
-    fn should_add_one_of_type() {
+    fn should_emit_newtypes_of_constrained_strings_and_numbers() {
+        let file = Path::new("constrained.schema.json");
+
         let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r##"{
+  "type": "object",
+  "title": "User",
+  "required": ["email"],
+  "properties": {
+    "email": { "$ref": "#/definitions/email" },
+    "age": { "type": "integer", "minimum": 13, "maximum": 150 },
+    "name": { "type": "string" },
+    "tags": { "type": "array", "items": { "type": "string" }, "maxItems": 2 }
+  },
+  "definitions": {
+    "email": { "type": "string", "pattern": "^[^@]+@[^@]+$" }
+  }
+}"##,
+            ),
+        );
+        generator.set_constrained_newtypes(true);
+        generator.add_file(file);
+
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub age : Option < Age > , pub email : Email , # [serde (skip_serializing_if = \"Option::is_none\")] pub name : Option < String >"));
+        assert!(code.contains(
+            "# [serde (try_from = \"String\" , into = \"String\")] pub struct Email (String) ;"
+        ));
+        assert!(code.contains("pub fn new (value : String) -> Result < Self , String > { { let value = & value ; { static PATTERN : std :: sync :: OnceLock < Result < regex :: Regex , String >> = std :: sync :: OnceLock :: new () ; let pattern = PATTERN . get_or_init (|| regex :: Regex :: new (\"^[^@]+@[^@]+$\")"));
+        assert!(code.contains("impl std :: convert :: TryFrom < u8 > for Age"));
+        assert!(code.contains("if * value < 13 {"));
+        // Arrays are still checked by the struct
+        assert!(code.contains("pub fn validate (& self) -> Result < () , ValidationError > { if let Some (value) = & self . tags {"));
+        assert!(!code.contains("self . age"));
+    }
 
-        let type_name = add_type(
-            &mut generator,
-            DataType::OneOf(OneOf {
-                types: vec![DataType::Any],
-            }),
-            true,
+    #[test]
+    fn should_add_the_properties_of_conditional_branches() {
+        let file = Path::new("conditional.schema.json");
+
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r#"{
+  "type": "object",
+  "title": "Address",
+  "required": ["country"],
+  "properties": { "country": { "type": "string" } },
+  "if": { "properties": { "country": { "const": "US" } } },
+  "then": { "properties": { "zip": { "type": "string", "maxLength": 10 } }, "required": ["zip"] }
+}"#,
+            ),
         );
+        generator.add_file(file);
 
-        assert_eq!(type_name, "Value");
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("# [doc = \"Required if `country` is `\\\"US\\\"`\"] # [serde (skip_serializing_if = \"Option::is_none\")] pub zip : Option < String >"));
+        assert!(code.contains("if value . chars () . count () as u64 > 10 {"));
     }
 
     #[test]
-    fn should_add_any_of_type() {
-        let mut generator = Generator::new();
+    fn should_validate_nested_types() {
+        let file = Path::new("nested.schema.json");
 
-        let type_name = add_type(
-            &mut generator,
-            DataType::AnyOf(AnyOf {
-                types: vec![DataType::Any],
-            }),
-            true,
+        let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r##"{
+  "type": "object",
+  "title": "Root",
+  "properties": {
+    "child": { "$ref": "#/definitions/child" },
+    "kids": { "type": "array", "items": { "$ref": "#/definitions/child" } },
+    "named": { "type": "object", "patternProperties": { ".*": { "$ref": "#/definitions/child" } } },
+    "either": { "oneOf": [{ "$ref": "#/definitions/child" }, { "type": "integer" }] }
+  },
+  "definitions": {
+    "child": {
+      "type": "object",
+      "properties": { "name": { "type": "string", "minLength": 3 } }
+    }
+  }
+}"##,
+            ),
         );
+        generator.add_file(file);
 
-        assert_eq!(type_name, "Value");
+        let code = generator.generate().to_string();
+
+        assert!(code.contains("pub struct ValidationError"));
+        assert!(code
+            .contains("impl Root { pub fn validate (& self) -> Result < () , ValidationError > {"));
+        assert!(code.contains("if let Some (value) = value { value . validate () . map_err (| error | error . at (\"child\")) ? ; }"));
+        assert!(code.contains("for (index , value) in value . iter () . enumerate () { value . validate () . map_err (| error | error . at (index) . at (\"kids\")) ? ; }"));
+        assert!(code.contains("for (key , value) in value . iter () { value . validate () . map_err (| error | error . at (key) . at (\"named\")) ? ; }"));
+        assert!(code.contains("impl Either { pub fn validate (& self) -> Result < () , ValidationError > { # [allow (unreachable_patterns)] match self { Either :: Child (value) => { value . validate () ? ; } _ => { } } Ok (()) } }"));
     }
 
+    #[cfg(feature = "fs")]
     #[test]
-    fn should_add_all_of_type() {
+    fn should_not_emit_validation_without_constraints() {
         let mut generator = Generator::new();
+        generator.add_file(Path::new("src/examples/generator/map.patterns.schema.json"));
 
-        let type_name = add_type(
-            &mut generator,
-            DataType::AllOf(AllOf {
-                types: vec![DataType::Any],
-            }),
-            true,
-        );
+        let code = generator.generate().to_string();
 
-        assert_eq!(type_name, "Value");
+        assert!(!code.contains("ValidationError"));
     }
 
     #[test]
-    fn should_add_any_type() {
+    fn should_validate_dependent_required_properties() {
+        let file = Path::new("dependent.schema.json");
+
         let mut generator = Generator::new();
+        generator.set_overlay(
+            file,
+            String::from(
+                r#"{
+  "type": "object",
+  "title": "Payment",
+  "properties": {
+    "card": { "type": "string" },
+    "billingAddress": { "type": "string" }
+  },
+  "dependentRequired": { "card": ["billingAddress"] },
+  "dependentSchemas": { "card": { "properties": { "cvc": { "type": "string" } } } }
+}"#,
+            ),
+        );
+        generator.add_file(file);
 
-        let type_name = add_type(&mut generator, DataType::Any, true);
+        let code = generator.generate().to_string();
 
-        assert_eq!(type_name, "Value");
+        assert!(code.contains("# [doc = \"Requires `billingAddress`\"] # [serde (skip_serializing_if = \"Option::is_none\")] pub card : Option < String >"));
+        assert!(code.contains("# [doc = \"Only applies if `card` is present\"] # [serde (skip_serializing_if = \"Option::is_none\")] pub cvc : Option < String >"));
+        assert!(code.contains("if self . card . is_some () { if self . billing_address . is_none () { return Err (ValidationError :: new (\"billingAddress is required if card is present\")) ; } }"));
     }
 
+    #[cfg(feature = "fs")]
     #[test]
-    fn should_detect_loops() {
-        let file = "src/examples/generator/loop1.schema.json";
+    fn should_show_the_first_example_and_expose_all_of_them() {
+        let file = "src/examples/generator/examples.schema.json";
 
         let mut generator = Generator::new();
         generator.add_file(Path::new(file));
 
-        let mut types: Vec<EntryWithPosition<GeneratedType>> = generator
-            .types
-            .into_iter()
-            .map(|(_, value)| value)
-            .collect();
-
-        types.sort();
-
-        let types: Vec<GeneratedType> = types.into_iter().map(|x| x.payload).collect();
+        let code = generator.generate().to_string();
+        let types: Vec<GeneratedType> = generator.into();
 
         assert_eq!(
-            types,
+            types[0].examples,
             vec![
-                GeneratedType {
-                    src: String::from("src/examples/generator/loop1.schema.json"),
-                    name: String::from("Loop"),
-                    properties: vec![GeneratedProperty {
-                        name: String::from("a"),
-                        serde_options: SerdeOptions {
-                            rename: None,
-                            skip_serializing_if: Some(String::from("Option::is_none")),
-                        },
-                        property_type: String::from("Option<B>"),
-                    }],
-                },
-                GeneratedType {
-                    src: String::from("src/examples/generator/loop1.schema.json#/definitions/b"),
-                    name: String::from("B"),
-                    properties: vec![GeneratedProperty {
-                        name: String::from("c"),
-                        serde_options: SerdeOptions {
-                            rename: None,
-                            skip_serializing_if: Some(String::from("Option::is_none")),
-                        },
-                        property_type: String::from("Option<C>"),
-                    }],
-                },
-                GeneratedType {
-                    src: String::from("src/examples/generator/loop2.schema.json#/definitions/c"),
-                    name: String::from("C"),
-                    properties: vec![GeneratedProperty {
-                        name: String::from("b"),
-                        serde_options: SerdeOptions {
-                            rename: None,
-                            skip_serializing_if: Some(String::from("Option::is_none")),
-                        },
-                        property_type: String::from("Option<Box<B>>"),
-                    }],
-                }
+                r#"{"lat":52.52,"lon":13.405}"#,
+                r#"{"lat":-33.87,"lon":151.21}"#
             ]
         );
+        assert!(code.contains(r#"# [doc = "```json"] # [doc = "{\"lat\":52.52,\"lon\":13.405}"]"#));
+        assert!(code.contains("pub fn examples () -> & 'static [& 'static str]"));
     }
 
+    #[cfg(feature = "fs")]
     #[test]
-    fn should_create_referenced_types_once() {
-        let file = "src/examples/generator/reference.twice.schema.json";
+    fn should_map_null_to_unit() {
+        let file = "src/examples/generator/null.property.schema.json";
 
         let mut generator = Generator::new();
+        generator.set_null_style(NullStyle::Unit);
         generator.add_file(Path::new(file));
 
-        let mut types: Vec<EntryWithPosition<GeneratedType>> = generator
-            .types
-            .into_iter()
-            .map(|(_, value)| value)
-            .collect();
-
-        types.sort();
-
-        let types: Vec<GeneratedType> = types.into_iter().map(|x| x.payload).collect();
+        let types: Vec<GeneratedType> = generator.into();
 
         assert_eq!(
-            types,
-            vec![
-                GeneratedType {
-                    src: String::from(file),
-                    name: String::from("Twice"),
-                    properties: vec![
-                        GeneratedProperty {
-                            name: String::from("a"),
-                            serde_options: SerdeOptions {
-                                rename: None,
-                                skip_serializing_if: Some(String::from("Option::is_none")),
-                            },
-                            property_type: String::from("Option<C>"),
-                        },
-                        GeneratedProperty {
-                            name: String::from("b"),
-                            serde_options: SerdeOptions {
-                                rename: None,
-                                skip_serializing_if: Some(String::from("Option::is_none")),
-                            },
-                            property_type: String::from("Option<C>"),
-                        }
-                    ],
-                },
-                GeneratedType {
-                    src: String::from(format!("{}#/definitions/c", file)),
-                    name: String::from("C"),
-                    properties: vec![GeneratedProperty {
-                        name: String::from("foo"),
-                        serde_options: SerdeOptions {
-                            rename: None,
-                            skip_serializing_if: Some(String::from("Option::is_none")),
-                        },
-                        property_type: String::from("Option<Value>"),
-                    }],
-                }
-            ]
+            types[0]
+                .properties
+                .iter()
+                .map(|x| x.property_type.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["()", "Option<()>"]
         );
     }
 
+    #[cfg(feature = "fs")]
     #[test]
-    fn should_prevent_name_collisions() {
-        let file = "src/examples/generator/name.collision.schema.json";
+    fn should_map_null_to_a_single_marker_type() {
+        let file = "src/examples/generator/null.property.schema.json";
 
         let mut generator = Generator::new();
+        generator.set_null_style(NullStyle::Marker);
         generator.add_file(Path::new(file));
 
-        let mut types: Vec<EntryWithPosition<GeneratedType>> = generator
-            .types
-            .into_iter()
-            .map(|(_, value)| value)
-            .collect();
+        let types: Vec<GeneratedType> = generator.into();
 
-        types.sort();
+        assert_eq!(
+            types
+                .iter()
+                .map(|x| (x.name.as_str(), &x.kind))
+                .collect::<Vec<(&str, &TypeKind)>>(),
+            vec![("Reset", &TypeKind::Struct), ("Null", &TypeKind::Null)]
+        );
+        assert_eq!(
+            types[0]
+                .properties
+                .iter()
+                .map(|x| x.property_type.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["Null", "Option<Null>"]
+        );
+    }
 
-        let types: Vec<GeneratedType> = types.into_iter().map(|x| x.payload).collect();
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_create_aliases_for_boolean_and_map_definitions() {
+        let file = "src/examples/generator/definitions.non.object.schema.json";
+
+        let mut generator = Generator::new();
+
+        generator.set_scalar_definition_style(ScalarDefinitionStyle::Alias);
+        generator.add_file(Path::new(file));
+
+        let types: Vec<GeneratedType> = generator.into();
 
         assert_eq!(
-            types,
+            types
+                .iter()
+                .map(|x| (x.name.as_str(), &x.kind))
+                .collect::<Vec<(&str, &TypeKind)>>(),
             vec![
-                GeneratedType {
-                    src: String::from(file),
-                    name: String::from("Collision"),
-                    properties: vec![
-                        GeneratedProperty {
-                            name: String::from("a"),
-                            serde_options: SerdeOptions {
-                                rename: None,
-                                skip_serializing_if: Some(String::from("Option::is_none")),
-                            },
-                            property_type: String::from("Option<A>"),
-                        },
-                        GeneratedProperty {
-                            name: String::from("b"),
-                            serde_options: SerdeOptions {
-                                rename: None,
-                                skip_serializing_if: Some(String::from("Option::is_none")),
-                            },
-                            property_type: String::from("Option<A1>"),
-                        },
-                        GeneratedProperty {
-                            name: String::from("c"),
-                            serde_options: SerdeOptions {
-                                rename: None,
-                                skip_serializing_if: Some(String::from("Option::is_none")),
-                            },
-                            property_type: String::from("Option<A2>"),
-                        }
-                    ],
-                },
-                GeneratedType {
-                    src: String::from(format!("{}/properties/a", file)),
-                    name: String::from("A"),
-                    properties: vec![GeneratedProperty {
-                        name: String::from("foo"),
-                        serde_options: SerdeOptions {
-                            rename: None,
-                            skip_serializing_if: Some(String::from("Option::is_none")),
-                        },
-                        property_type: String::from("Option<Value>"),
-                    }],
-                },
-                GeneratedType {
-                    src: String::from(format!("{}/properties/b", file)),
-                    name: String::from("A1"),
-                    properties: vec![GeneratedProperty {
-                        name: String::from("foo"),
-                        serde_options: SerdeOptions {
-                            rename: None,
-                            skip_serializing_if: Some(String::from("Option::is_none")),
-                        },
-                        property_type: String::from("Option<Value>"),
-                    }],
-                },
-                GeneratedType {
-                    src: String::from(format!("{}/properties/c", file)),
-                    name: String::from("A2"),
-                    properties: vec![GeneratedProperty {
-                        name: String::from("foo"),
-                        serde_options: SerdeOptions {
-                            rename: None,
-                            skip_serializing_if: Some(String::from("Option::is_none")),
-                        },
-                        property_type: String::from("Option<Value>"),
-                    }],
-                }
+                ("Config", &TypeKind::Struct),
+                ("Anything", &TypeKind::Alias(String::from("Value"))),
+                (
+                    "Labels",
+                    &TypeKind::Alias(String::from("BTreeMap<String, String>"))
+                ),
             ]
         );
     }
 
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_inline_scalar_definitions() {
+        let file = "src/examples/generator/alias.schema.json";
+
+        let mut generator = Generator::new();
+        generator.set_scalar_definition_style(ScalarDefinitionStyle::Inline);
+        generator.set_format_type("uuid", "String");
+        generator.add_file(Path::new(file));
+
+        let types: Vec<GeneratedType> = generator.into();
+
+        assert_eq!(types.len(), 1);
+        assert_eq!(
+            types[0]
+                .properties
+                .iter()
+                .map(|x| x.property_type.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["String", "Option<Vec<String>>"]
+        );
+    }
+
     #[test]
     fn should_convert_into_a_sorted_type_list() {
         let mut generator = Generator::new();
@@ -969,7 +6933,16 @@ mod generator_tests {
             DataType::Object(object_with_custom_property(ObjectProperty {
                 name: String::from("first property"),
                 required: false,
+                constant: None,
+                default: None,
                 data_type: Rc::new(DataType::Object(object_with_property())),
+                description: None,
+                map_constraints: None,
+                aliases: vec![],
+                read_only: false,
+                write_only: false,
+                value_constraints: None,
+                requires: vec![],
             })),
             true,
         );
@@ -982,26 +6955,70 @@ mod generator_tests {
                 GeneratedType {
                     src: String::from(""),
                     name: String::from("AwesomeFoo"),
+                    doc_alias: None,
+                    description: None,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::Struct,
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
                     properties: vec![GeneratedProperty {
                         name: String::from("first_property"),
                         serde_options: SerdeOptions {
                             rename: Some(String::from("first property")),
                             skip_serializing_if: Some(String::from("Option::is_none")),
+                            default: None,
+                            with: None,
+                            deserialize_with: None,
+                            aliases: vec![],
+                            flatten: false,
+                            skip_serializing: false,
+                            skip_deserializing: false,
                         },
                         property_type: String::from("Option<AwesomeFoo1>"),
+                        description: None,
+                        map_constraints: None,
+                        value_constraints: None,
+                        requires: vec![],
                     }],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
                 },
                 GeneratedType {
                     src: String::from("wrong src"),
                     name: String::from("AwesomeFoo1"),
+                    doc_alias: None,
+                    description: None,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::Struct,
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
                     properties: vec![GeneratedProperty {
                         name: String::from("awesome_property"),
                         serde_options: SerdeOptions {
                             rename: Some(String::from("awesome property")),
                             skip_serializing_if: Some(String::from("Option::is_none")),
+                            default: None,
+                            with: None,
+                            deserialize_with: None,
+                            aliases: vec![],
+                            flatten: false,
+                            skip_serializing: false,
+                            skip_deserializing: false,
                         },
                         property_type: String::from("Option<Value>"),
+                        description: None,
+                        map_constraints: None,
+                        value_constraints: None,
+                        requires: vec![],
                     }],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
                 }
             ]
         );
@@ -1016,16 +7033,24 @@ mod generator_tests {
         );
 
         generator.add_type(
-            &String::from(""),
             Rc::new(Root {
                 file: Path::new("").to_path_buf(),
+                dialect: None,
+                id: None,
+                title: None,
                 data_type: Rc::new(DataType::Any),
                 definitions,
+                enums: BTreeMap::new(),
+                invalid_defaults: BTreeMap::new(),
+                examples: BTreeMap::new(),
+                constraints: BTreeMap::new(),
+                schema: Schema::default(),
+                locations: Locations::default(),
             }),
             Some(String::from("")),
             &data_type,
             required,
-            Vec::new(),
+            None,
         )
     }
 }
@@ -10,3 +10,21 @@ pub static RUST_KEYWORDS: [&str; 54] = [
     "while", "async", "await", "dyn", "abstract", "become", "box", "do", "final", "macro",
     "override", "priv", "typeof", "unsized", "virtual", "yield", "try", "union", "'static", "dyn",
 ];
+
+/// Crates and modules the generated code refers to by name, which a module must not shadow
+pub static RESERVED_MODULE_NAMES: [&str; 14] = [
+    "alloc",
+    "bytes",
+    "chrono",
+    "core",
+    "indexmap",
+    "regex",
+    "serde",
+    "serde_helpers",
+    "serde_json",
+    "simd_json",
+    "std",
+    "time",
+    "url",
+    "uuid",
+];
@@ -0,0 +1,254 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use std::collections::{HashMap, HashSet};
+
+use crate::generated::{GeneratedType, TypeKind};
+use crate::options::{MapType, SetType, VariantBoxing};
+
+/// The difference in bytes between the largest and the second largest variant
+/// from which `clippy::large_enum_variant` complains
+const LARGE_VARIANT_DIFFERENCE: usize = 200;
+
+/// Boxes the variants of enums the style asks for.
+pub fn box_variants(types: &mut [GeneratedType], style: VariantBoxing) {
+    if style == VariantBoxing::Never {
+        return;
+    }
+
+    let mut boxed: Vec<(usize, usize)> = Vec::new();
+
+    {
+        let by_name: HashMap<&str, &GeneratedType> =
+            types.iter().map(|x| (x.name.as_str(), x)).collect();
+
+        for (index, generated_type) in types.iter().enumerate() {
+            let variant_types = match &generated_type.kind {
+                TypeKind::UntaggedEnum(_) | TypeKind::TaggedEnum { .. } => {
+                    generated_type.field_types()
+                }
+                _ => continue,
+            };
+
+            let candidates: Vec<(usize, usize)> = variant_types
+                .iter()
+                .enumerate()
+                .filter(|(_, x)| !x.starts_with("Box<"))
+                .filter(|(_, x)| !is_unit(x, &by_name))
+                .map(|(i, x)| (i, size(x, &by_name, &mut HashSet::new())))
+                .collect();
+
+            match style {
+                VariantBoxing::All => {
+                    boxed.extend(candidates.iter().map(|(variant, _)| (index, *variant)))
+                }
+                VariantBoxing::Large => {
+                    let mut sizes: Vec<usize> = variant_types
+                        .iter()
+                        .map(|x| size(x, &by_name, &mut HashSet::new()))
+                        .collect();
+                    sizes.sort_unstable();
+
+                    if let [.., second, largest] = sizes[..] {
+                        if largest - second >= LARGE_VARIANT_DIFFERENCE {
+                            boxed.extend(
+                                candidates
+                                    .iter()
+                                    .filter(|(_, size)| *size == largest)
+                                    .map(|(variant, _)| (index, *variant)),
+                            );
+                        }
+                    }
+                }
+                VariantBoxing::Never => {}
+            }
+        }
+    }
+
+    for (index, variant) in boxed {
+        let mut field_types = types[index].field_types_mut();
+        let variant_type = &mut field_types[variant];
+        **variant_type = format!("Box<{}>", variant_type);
+    }
+}
+
+fn is_unit(type_name: &str, types: &HashMap<&str, &GeneratedType>) -> bool {
+    type_name == "()" || matches!(types.get(type_name), Some(x) if x.kind == TypeKind::Null)
+}
+
+/// Estimates the size of a type on 64 bit targets, without padding
+fn size<'a>(
+    type_name: &'a str,
+    types: &HashMap<&str, &'a GeneratedType>,
+    visiting: &mut HashSet<&'a str>,
+) -> usize {
+    if let Some(inner) = type_name
+        .strip_prefix("Option<")
+        .and_then(|x| x.strip_suffix('>'))
+    {
+        let inner_size = size(inner, types, visiting);
+
+        // Pointers and collections have a niche for `None`
+        return match inner.starts_with("Box<") || has_niche(inner) {
+            true => inner_size,
+            false => inner_size + 8,
+        };
+    }
+
+    match type_name {
+        "()" => 0,
+        "bool" | "u8" | "i8" => 1,
+        "u16" | "i16" => 2,
+        "u32" | "i32" => 4,
+        "i64" | "u64" | "f64" => 8,
+        "Value" => 32,
+        "bytes::Bytes" => 32,
+        _ if type_name.starts_with("Box<") => 8,
+        _ if has_niche(type_name) => 24,
+        _ => {
+            let generated_type = match types.get(type_name) {
+                Some(generated_type) => *generated_type,
+                None => return 8,
+            };
+
+            // Cycles are boxed, so they only add a pointer
+            if !visiting.insert(&generated_type.name) {
+                return 8;
+            }
+
+            let size = match &generated_type.kind {
+                TypeKind::Struct | TypeKind::Wrapper => generated_type
+                    .properties
+                    .iter()
+                    .map(|x| size(&x.property_type, types, visiting))
+                    .sum(),
+                TypeKind::Alias(target)
+                | TypeKind::Newtype(target)
+                | TypeKind::Constrained {
+                    value_type: target, ..
+                } => size(target, types, visiting),
+                TypeKind::MapKey(_) | TypeKind::Map { .. } => 24,
+                TypeKind::Null => 0,
+                TypeKind::StringEnum(_) => 1,
+                TypeKind::UntaggedEnum(_) | TypeKind::TaggedEnum { .. } => {
+                    generated_type
+                        .field_types()
+                        .into_iter()
+                        .map(|x| size(x, types, visiting))
+                        .max()
+                        .unwrap_or_default()
+                        + 8
+                }
+            };
+
+            visiting.remove(generated_type.name.as_str());
+
+            size
+        }
+    }
+}
+
+fn has_niche(type_name: &str) -> bool {
+    type_name == "String"
+        || type_name.starts_with("Vec<")
+        || MapType::is_map(type_name)
+        || SetType::is_set(type_name)
+}
+
+#[cfg(test)]
+mod layout_tests {
+    use crate::generated::{
+        GeneratedProperty, GeneratedType, GeneratedVariant, SerdeOptions, TypeKind,
+    };
+    use crate::layout::box_variants;
+    use crate::options::VariantBoxing;
+
+    fn generated_type(name: &str, kind: TypeKind, property_types: Vec<&str>) -> GeneratedType {
+        GeneratedType {
+            src: String::new(),
+            name: String::from(name),
+            doc_alias: None,
+            description: None,
+            derives: vec![],
+            removed_derives: vec![],
+            kind,
+            constants: vec![],
+            defaults: vec![],
+            deny_unknown_fields: false,
+            properties: property_types
+                .into_iter()
+                .enumerate()
+                .map(|(i, property_type)| GeneratedProperty {
+                    name: format!("field{}", i),
+                    property_type: String::from(property_type),
+                    serde_options: SerdeOptions {
+                        rename: None,
+                        skip_serializing_if: None,
+                        default: None,
+                        with: None,
+                        deserialize_with: None,
+                        aliases: vec![],
+                        flatten: false,
+                        skip_serializing: false,
+                        skip_deserializing: false,
+                    },
+                    description: None,
+                    map_constraints: None,
+                    value_constraints: None,
+                    requires: vec![],
+                })
+                .collect(),
+            examples: vec![],
+            module: vec![],
+            required_any: vec![],
+        }
+    }
+
+    fn types() -> Vec<GeneratedType> {
+        vec![
+            generated_type(
+                "Event",
+                TypeKind::UntaggedEnum(
+                    ["Details", "String", "()"]
+                        .iter()
+                        .map(|variant_type| GeneratedVariant {
+                            name: variant_type.replace("()", "Null"),
+                            variant_type: variant_type.to_string(),
+                        })
+                        .collect(),
+                ),
+                vec![],
+            ),
+            generated_type("Details", TypeKind::Struct, vec!["Value"; 8]),
+        ]
+    }
+
+    fn variant_types(types: &[GeneratedType]) -> Vec<&String> {
+        types[0].field_types()
+    }
+
+    #[test]
+    fn should_box_variants_much_larger_than_the_others() {
+        let mut types = types();
+        box_variants(&mut types, VariantBoxing::Large);
+
+        assert_eq!(variant_types(&types), vec!["Box<Details>", "String", "()"]);
+    }
+
+    #[test]
+    fn should_box_all_non_unit_variants_on_request() {
+        let mut types = types();
+        types[1].properties.truncate(1);
+        box_variants(&mut types, VariantBoxing::Large);
+
+        assert_eq!(variant_types(&types), vec!["Details", "String", "()"]);
+
+        box_variants(&mut types, VariantBoxing::All);
+
+        assert_eq!(
+            variant_types(&types),
+            vec!["Box<Details>", "Box<String>", "()"]
+        );
+    }
+}
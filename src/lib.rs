@@ -2,43 +2,137 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
 
-use std::path::Path;
+#[cfg(feature = "fs")]
+use std::path::{Path, PathBuf};
 
-use crate::generator::Generator;
+#[cfg(feature = "fs")]
 use proc_macro2::TokenStream;
 
+pub use crate::binary::BinaryDiagnostic;
+pub use crate::compatibility::{
+    Compatibility, CompatibilityReport, SchemaChange, Snapshot, SnapshotProperty, SnapshotType,
+};
+pub use crate::defaults::DefaultDiagnostic;
+pub use crate::error::Error;
+pub use crate::generated::{
+    GeneratedConstant, GeneratedDefault, GeneratedProperty, GeneratedType, GeneratedVariant,
+    SerdeOptions, StringVariant, TaggedVariant, TypeKind,
+};
+pub use crate::generator::Generator;
+pub use crate::options::{
+    BytesStyle, CratePaths, EmptyObjectStyle, GeneratorOptions, MapType, MethodAttributes,
+    NamingStrategy, NullStyle, ScalarDefinitionStyle, SetType, SourceStyle, VariantBoxing,
+    Visibility,
+};
+pub use crate::parser::{MapConstraints, ValueConstraints};
+pub use crate::plan::{Plan, PlannedField, PlannedType};
+pub use crate::report::Report;
+#[cfg(feature = "fs")]
+pub use crate::scaffold::{scaffold_build_script, scaffold_next_steps, Scaffold};
+pub use crate::statistics::SchemaStatistics;
+
+mod binary;
+#[cfg(feature = "fs")]
+pub mod build_helper;
+mod builders;
+mod compatibility;
+mod conversions;
+mod defaults;
+mod dialect;
+pub mod emitters;
+mod error;
 mod generated;
 mod generator;
 mod keywords;
+mod layout;
+mod locations;
+mod normalizer;
+mod options;
+mod ordering;
+#[cfg(feature = "fs")]
+mod output;
 mod parser;
+mod plan;
 mod ref_parser;
+mod report;
 mod resolver;
+mod round_trip;
 mod sanitizer;
+#[cfg(feature = "fs")]
+mod scaffold;
 mod schema;
+mod serde_helpers;
+mod standalone;
+mod statistics;
+mod subset;
+mod validation;
 
+#[cfg(feature = "fs")]
 pub fn generate(path: &Path) -> String {
     generate_token_stream(path).to_string()
 }
 
+#[cfg(feature = "fs")]
 pub fn generate_token_stream(path: &Path) -> TokenStream {
     let mut generator = Generator::new();
     generator.add_file(path);
     generator.into()
 }
 
-#[cfg(test)]
+/// Writes the types of the schema to `$OUT_DIR/<name>.rs`, named like the schema file
+/// up to its first dot, and asks cargo to rerun the build script when the schema or
+/// a file it refers to changes. Meant to be called from a build script, the file is
+/// included with [`include_generated!`].
+#[cfg(feature = "fs")]
+pub fn generate_into_out_dir(schema: &Path) -> PathBuf {
+    let out_dir = std::env::var_os("OUT_DIR").expect("OUT_DIR is only set for build scripts");
+
+    build_helper::generate_into(Path::new(&out_dir), &[schema.to_path_buf()])
+        .unwrap_or_else(|error| panic!("{}", error))
+        .remove(0)
+}
+
+/// Includes the types [`generate_into_out_dir`] generated from the schema of the name,
+/// e.g. `include_generated!("user")` for `schemas/user.schema.json`.
+#[macro_export]
+macro_rules! include_generated {
+    ($name:literal) => {
+        include!(concat!(env!("OUT_DIR"), "/", $name, ".rs"));
+    };
+}
+
+#[cfg(all(test, feature = "fs"))]
 mod lib_tests {
     use proc_macro2::TokenStream;
 
     use std::{
         fs,
         io::Write,
-        path::Path,
+        path::{Path, PathBuf},
         process::{Command, Stdio},
     };
 
+    use crate::build_helper::generate_into;
     use crate::generator::Generator;
 
+    #[test]
+    fn should_generate_into_a_file_named_after_the_schema() {
+        let dir = std::env::temp_dir().join("jsonschema_code_generator_out_dir_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let paths = generate_into(&dir, &[PathBuf::from("schemas/draft-04.json")]).unwrap();
+
+        assert_eq!(paths, vec![dir.join("draft-04.rs")]);
+        let path = &paths[0];
+        assert_eq!(
+            format(fs::read_to_string(path).unwrap()),
+            fs::read_to_string("schemas/draft-04.rs").unwrap()
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test() {
         let mut generator = Generator::new();
@@ -50,6 +144,31 @@ mod lib_tests {
         assert_eq!(format(actual), expected);
     }
 
+    /// The builders are compiled by `tests/generated.rs`
+    #[test]
+    fn should_generate_builders_of_draft_04() {
+        let mut generator = Generator::new();
+        generator.set_standalone(true);
+        generator.set_builder_threshold(Some(1));
+        generator.add_file(Path::new("schemas/draft-04.json"));
+        let actual = generator.generate().to_string();
+        let expected = fs::read_to_string("schemas/draft-04.builders.rs").unwrap();
+
+        assert_eq!(format(actual), expected);
+    }
+
+    /// The `validate` methods are run by `tests/generated.rs`
+    #[test]
+    fn should_generate_validation_of_nested_values() {
+        let mut generator = Generator::new();
+        generator.set_standalone(true);
+        generator.add_file(Path::new("schemas/validation.json"));
+        let actual = generator.generate().to_string();
+        let expected = fs::read_to_string("schemas/validation.rs").unwrap();
+
+        assert_eq!(format(actual), expected);
+    }
+
     fn format(text: impl std::fmt::Display) -> String {
         let mut rustfmt = Command::new("rustfmt")
             .stdin(Stdio::piped())
@@ -0,0 +1,184 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use std::collections::HashMap;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Line and column, both starting at 1, of every value of a JSON document, keyed by JSON pointer
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Locations(HashMap<String, (usize, usize)>);
+
+impl Locations {
+    /// Indexes a document in a single pass, the document is expected to be valid JSON
+    pub fn index(text: &str) -> Self {
+        let mut scanner = Scanner {
+            chars: text.chars().peekable(),
+            line: 1,
+            column: 1,
+            locations: HashMap::new(),
+        };
+
+        scanner.value(String::new());
+
+        Locations(scanner.locations)
+    }
+
+    pub fn get(&self, pointer: &str) -> Option<(usize, usize)> {
+        self.0.get(pointer).copied()
+    }
+}
+
+struct Scanner<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+    locations: HashMap<String, (usize, usize)>,
+}
+
+impl<'a> Scanner<'a> {
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+
+        match c {
+            '\n' => {
+                self.line += 1;
+                self.column = 1;
+            }
+            _ => self.column += 1,
+        }
+
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.chars.peek() {
+            match c.is_whitespace() {
+                true => self.next(),
+                false => break,
+            };
+        }
+    }
+
+    fn value(&mut self, pointer: String) {
+        self.skip_whitespace();
+        self.locations
+            .insert(pointer.clone(), (self.line, self.column));
+
+        match self.chars.peek() {
+            Some('{') => {
+                self.next();
+
+                loop {
+                    self.skip_whitespace();
+
+                    match self.chars.peek() {
+                        Some('"') => {
+                            let key = self.string();
+                            self.skip_whitespace();
+                            self.next();
+                            let segment = key.replace('~', "~0").replace('/', "~1");
+                            self.value(format!("{}/{}", pointer, segment));
+                        }
+                        Some(',') => {
+                            self.next();
+                        }
+                        _ => {
+                            self.next();
+                            break;
+                        }
+                    }
+                }
+            }
+            Some('[') => {
+                self.next();
+                let mut index = 0;
+
+                loop {
+                    self.skip_whitespace();
+
+                    match self.chars.peek() {
+                        Some(']') | None => {
+                            self.next();
+                            break;
+                        }
+                        Some(',') => {
+                            self.next();
+                        }
+                        _ => {
+                            self.value(format!("{}/{}", pointer, index));
+                            index += 1;
+                        }
+                    }
+                }
+            }
+            Some('"') => {
+                self.string();
+            }
+            _ => {
+                while let Some(c) = self.chars.peek() {
+                    match c.is_whitespace() || matches!(c, ',' | '}' | ']') {
+                        true => break,
+                        false => self.next(),
+                    };
+                }
+            }
+        }
+    }
+
+    fn string(&mut self) -> String {
+        let mut text = String::new();
+        self.next();
+
+        while let Some(c) = self.next() {
+            match c {
+                '"' => break,
+                '\\' => match self.next() {
+                    Some('n') => text.push('\n'),
+                    Some('t') => text.push('\t'),
+                    Some('r') => text.push('\r'),
+                    Some('b') => text.push('\u{8}'),
+                    Some('f') => text.push('\u{c}'),
+                    Some('u') => {
+                        let code: String = (0..4).filter_map(|_| self.next()).collect();
+                        if let Some(c) =
+                            u32::from_str_radix(&code, 16).ok().and_then(char::from_u32)
+                        {
+                            text.push(c);
+                        }
+                    }
+                    Some(c) => text.push(c),
+                    None => break,
+                },
+                c => text.push(c),
+            }
+        }
+
+        text
+    }
+}
+
+#[cfg(test)]
+mod locations_tests {
+    use crate::locations::Locations;
+
+    #[test]
+    fn should_locate_nested_values() {
+        let locations = Locations::index(
+            r##"{
+  "type": "object",
+  "properties": {
+    "a/b": { "$ref": "#/definitions/missing" },
+    "list": [1, {"x": true}]
+  }
+}"##,
+        );
+
+        assert_eq!(locations.get(""), Some((1, 1)));
+        assert_eq!(locations.get("/type"), Some((2, 11)));
+        assert_eq!(locations.get("/properties/a~1b/$ref"), Some((4, 22)));
+        assert_eq!(locations.get("/properties/list/1/x"), Some((5, 23)));
+        assert_eq!(locations.get("/properties/unknown"), None);
+    }
+}
@@ -0,0 +1,472 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use std::mem;
+
+use crate::ref_parser::child_src;
+use crate::schema::{Schema, Types};
+
+/// Rewrites the shorthands of a schema and all of its subschemas to the forms the parser
+/// handles, so it only knows one way to express each of them:
+/// - `nullable: true` becomes an `anyOf` of the schema and `null`
+/// - keywords next to a `$ref` become an `allOf` of the `$ref` and the keywords
+/// - a `type` array becomes an `anyOf` with a branch of each type
+/// - a single `oneOf`, `anyOf` or `allOf` branch replaces a schema without other keywords
+/// - the properties of `then`, `else` and `dependentSchemas` become optional properties
+///   of the schema
+///
+/// Schemas which are taken apart keep the source they were written at in [`Schema::src`].
+pub fn normalize(src: &str, schema: &mut Schema) {
+    let src = schema.src.clone().unwrap_or_else(|| src.to_string());
+    merge_conditionals(&src, schema);
+    merge_dependent_schemas(&src, schema);
+
+    if let Some(branch) = single_branch(&src, schema) {
+        *schema = branch;
+        return normalize(&src, schema);
+    }
+
+    let rewritten = split_nullable(&src, schema)
+        .or_else(|| split_ref_siblings(&src, schema))
+        .or_else(|| split_type_union(&src, schema));
+
+    if let Some(rewritten) = rewritten {
+        *schema = rewritten;
+    }
+
+    normalize_children(&src, schema);
+}
+
+fn normalize_children(src: &str, schema: &mut Schema) {
+    let keyed = [
+        ("properties", &mut schema.properties),
+        ("patternProperties", &mut schema.pattern_properties),
+        ("definitions", &mut schema.definitions),
+        ("$defs", &mut schema.defs),
+    ];
+
+    for (keyword, schemas) in keyed {
+        for (name, child) in schemas.iter_mut() {
+            normalize(&child_src(src, &[keyword, name]), child);
+        }
+    }
+
+    let indexed = [
+        ("oneOf", &mut schema.one_of),
+        ("anyOf", &mut schema.any_of),
+        ("allOf", &mut schema.all_of),
+    ];
+
+    for (keyword, schemas) in indexed {
+        for (i, child) in schemas.iter_mut().enumerate() {
+            normalize(&child_src(src, &[keyword, &i.to_string()]), child);
+        }
+    }
+
+    let keyword = schema.prefix_items_keyword();
+
+    for (i, child) in schema.prefix_items.iter_mut().enumerate() {
+        normalize(&child_src(src, &[keyword, &i.to_string()]), child);
+    }
+
+    let nested = [
+        ("items", schema.items.as_mut()),
+        (
+            "additionalProperties",
+            schema.additional_properties.as_mut(),
+        ),
+        ("propertyNames", schema.property_names.as_mut()),
+    ];
+
+    for (keyword, child) in nested {
+        if let Some(child) = child {
+            normalize(&child_src(src, &[keyword]), child);
+        }
+    }
+}
+
+/// A schema which only combines branches, with the annotations of the schema it replaces.
+/// The definitions and identifiers of the file move to it.
+fn union_of(schema: &mut Schema) -> Schema {
+    Schema {
+        dollar_schema: schema.dollar_schema.take(),
+        dollar_id: schema.dollar_id.take(),
+        id: schema.id.take(),
+        title: schema.title.clone(),
+        description: schema.description.clone(),
+        default: schema.default.clone(),
+        const_: schema.const_.clone(),
+        x_aliases: schema.x_aliases.clone(),
+        x_rust_type: schema.x_rust_type.take(),
+        definitions: mem::take(&mut schema.definitions),
+        defs: mem::take(&mut schema.defs),
+        ..Schema::default()
+    }
+}
+
+fn split_nullable(src: &str, schema: &mut Schema) -> Option<Schema> {
+    if schema.nullable != Some(true) {
+        return None;
+    }
+
+    let mut union = union_of(schema);
+    let null = Schema {
+        type_: Some(Types::Null),
+        src: Some(child_src(src, &["nullable"])),
+        ..Schema::default()
+    };
+    let inner = Schema {
+        nullable: None,
+        src: Some(src.to_string()),
+        ..mem::take(schema)
+    };
+
+    union.any_of = vec![inner, null];
+    Some(union)
+}
+
+/// `properties` and `required` next to a `$ref` refine the referenced schema
+fn split_ref_siblings(src: &str, schema: &mut Schema) -> Option<Schema> {
+    if schema.ref_.is_none() || (schema.properties.is_empty() && schema.required.is_none()) {
+        return None;
+    }
+
+    let mut union = union_of(schema);
+    let reference = Schema {
+        ref_: schema.ref_.take(),
+        description: schema.description.clone(),
+        default: schema.default.clone(),
+        src: Some(src.to_string()),
+        ..Schema::default()
+    };
+    let refinement = Schema {
+        type_: Some(Types::Object),
+        src: Some(src.to_string()),
+        ..mem::take(schema)
+    };
+
+    union.all_of = vec![reference, refinement];
+    Some(union)
+}
+
+/// The branch of a type which is only combined with `null` keeps the source of the schema
+fn split_type_union(src: &str, schema: &mut Schema) -> Option<Schema> {
+    if schema.type_union.is_empty() {
+        return None;
+    }
+
+    let mut union = union_of(schema);
+    let types = mem::take(&mut schema.type_union);
+    let nullable = types.len() == 2 && types.contains(&Types::Null);
+
+    union.any_of = types
+        .into_iter()
+        .enumerate()
+        .map(|(i, type_)| {
+            let branch_src = match nullable && type_ != Types::Null {
+                true => src.to_string(),
+                false => child_src(src, &["type", &i.to_string()]),
+            };
+
+            Schema {
+                type_: Some(type_),
+                src: Some(branch_src),
+                ..schema.clone()
+            }
+        })
+        .collect();
+
+    Some(union)
+}
+
+/// The only branch of a schema which has no keywords beside the annotations,
+/// which it takes unless it has its own
+fn single_branch(src: &str, schema: &mut Schema) -> Option<Schema> {
+    let (keyword, branches) = match (
+        schema.one_of.len(),
+        schema.any_of.len(),
+        schema.all_of.len(),
+    ) {
+        (1, 0, 0) => ("oneOf", &mut schema.one_of),
+        (0, 1, 0) => ("anyOf", &mut schema.any_of),
+        (0, 0, 1) => ("allOf", &mut schema.all_of),
+        _ => return None,
+    };
+
+    let annotations_only = schema.ref_.is_none()
+        && schema.type_.is_none()
+        && schema.type_union.is_empty()
+        && schema.nullable.is_none()
+        && schema.enum_.is_none()
+        && schema.const_.is_none()
+        && schema.required.is_none()
+        && schema.properties.is_empty()
+        && schema.pattern_properties.is_empty()
+        && schema.additional_properties.is_none()
+        && schema.property_names.is_none()
+        && schema.items.is_none();
+
+    if !annotations_only {
+        return None;
+    }
+
+    let mut branch = branches.remove(0);
+    branch.src = Some(child_src(src, &[keyword, "0"]));
+    branch.title = schema.title.take().or(branch.title);
+    branch.description = branch.description.or_else(|| schema.description.take());
+    branch.default = branch.default.or_else(|| schema.default.take());
+    branch.dollar_schema = schema.dollar_schema.take().or(branch.dollar_schema);
+    branch.dollar_id = schema.dollar_id.take().or(branch.dollar_id);
+    branch.id = schema.id.take().or(branch.id);
+    branch.definitions.append(&mut schema.definitions);
+    branch.defs.append(&mut schema.defs);
+
+    Some(branch)
+}
+
+/// Properties of `then` and `else` which the schema doesn't have are added to it,
+/// documented with the condition they depend on. The condition itself isn't checked,
+/// so they stay optional even if they are required in the branch.
+fn merge_conditionals(src: &str, schema: &mut Schema) {
+    let condition = match schema.if_.take() {
+        Some(condition) => describe_condition(&condition),
+        None => return,
+    };
+    let branches = [
+        ("then", schema.then.take(), "if"),
+        ("else", schema.else_.take(), "unless"),
+    ];
+
+    for (keyword, branch, conjunction) in branches {
+        if let Some(branch) = branch {
+            let condition = format!("{} {}", conjunction, condition);
+            merge_properties(&child_src(src, &[keyword]), schema, branch, &condition);
+        }
+    }
+}
+
+/// The properties of the schemas which apply if a key is present are added like the ones
+/// of `then`, the names they require are added to the names the key requires
+fn merge_dependent_schemas(src: &str, schema: &mut Schema) {
+    let keyword = schema.dependent_schemas_keyword();
+
+    for (key, dependent) in mem::take(&mut schema.dependent_schemas) {
+        let required = schema.dependent_required.entry(key.clone()).or_default();
+
+        for name in dependent.required.iter().flatten() {
+            if !required.contains(name) {
+                required.push(name.clone());
+            }
+        }
+
+        let condition = format!("if `{}` is present", key);
+        merge_properties(
+            &child_src(src, &[keyword, &key]),
+            schema,
+            dependent,
+            &condition,
+        );
+    }
+}
+
+/// Adds the properties of the branch which the schema doesn't have,
+/// with a note of the condition they apply under
+fn merge_properties(branch_src: &str, schema: &mut Schema, branch: Schema, condition: &str) {
+    let required = branch.required.unwrap_or_default();
+
+    for (name, mut property) in branch.properties {
+        if schema.properties.contains_key(&name) {
+            continue;
+        }
+
+        let note = match required.contains(&name) {
+            true => format!("Required {}", condition),
+            false => format!("Only applies {}", condition),
+        };
+        property.description = Some(match property.description {
+            Some(description) => format!("{}\n\n{}", description, note),
+            None => note,
+        });
+        property.src = Some(child_src(branch_src, &["properties", &name]));
+        schema.properties.insert(name, property);
+    }
+}
+
+/// The `const` values the condition expects of properties, like `` `country` is `"US"` ``
+fn describe_condition(condition: &Schema) -> String {
+    let parts: Vec<String> = condition
+        .properties
+        .iter()
+        .filter_map(|(name, schema)| {
+            let value = match (&schema.const_, &schema.enum_) {
+                (Some(value), _) => value,
+                (None, Some(values)) if values.len() == 1 => &values[0],
+                _ => return None,
+            };
+            Some(format!("`{}` is `{}`", name, value))
+        })
+        .collect();
+
+    match parts.is_empty() {
+        true => String::from("the `if` schema matches"),
+        false => parts.join(" and "),
+    }
+}
+
+#[cfg(test)]
+mod normalizer_tests {
+    use crate::normalizer::normalize;
+    use crate::schema::{Schema, Types};
+
+    fn normalized(json: &str) -> Schema {
+        let mut schema = serde_json::from_str(json).unwrap();
+        normalize("a.json", &mut schema);
+        schema
+    }
+
+    #[test]
+    fn should_split_nullable_schemas_into_a_union_with_null() {
+        let schema = normalized(r#"{"title": "Name", "type": "string", "nullable": true}"#);
+
+        assert_eq!(schema.type_, None);
+        assert_eq!(schema.title, Some(String::from("Name")));
+        assert_eq!(
+            schema
+                .any_of
+                .iter()
+                .map(|x| (x.type_.clone(), x.src.clone(), x.nullable))
+                .collect::<Vec<_>>(),
+            vec![
+                (Some(Types::String), Some(String::from("a.json")), None),
+                (
+                    Some(Types::Null),
+                    Some(String::from("a.json#/nullable")),
+                    None
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_split_refinements_of_refs_into_an_all_of() {
+        let schema = normalized(
+            r##"{"$ref": "#/definitions/User", "required": ["id"], "definitions": {"User": {}}}"##,
+        );
+
+        assert!(schema.ref_.is_none());
+        assert!(schema.definitions.contains_key("User"));
+        assert_eq!(
+            schema.all_of[0].ref_,
+            Some(String::from("#/definitions/User"))
+        );
+        assert_eq!(schema.all_of[1].type_, Some(Types::Object));
+        assert_eq!(schema.all_of[1].required, Some(vec![String::from("id")]));
+        assert!(schema.all_of[1].properties.is_empty());
+        assert!(schema.all_of[1].definitions.is_empty());
+    }
+
+    #[test]
+    fn should_merge_the_properties_of_then_and_else() {
+        let schema = normalized(
+            r#"{
+                "type": "object",
+                "properties": {"country": {"type": "string"}},
+                "if": {"properties": {"country": {"const": "US"}}},
+                "then": {"properties": {"zip": {"type": "string"}}, "required": ["zip"]},
+                "else": {"properties": {"postcode": {"description": "Postal code"}}}
+            }"#,
+        );
+
+        assert!(schema.if_.is_none() && schema.then.is_none() && schema.else_.is_none());
+        assert_eq!(schema.required, None);
+        assert_eq!(
+            schema
+                .properties
+                .iter()
+                .map(|(name, x)| (name.as_str(), x.description.as_deref(), x.src.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("country", None, None),
+                (
+                    "postcode",
+                    Some("Postal code\n\nOnly applies unless `country` is `\"US\"`"),
+                    Some("a.json#/else/properties/postcode")
+                ),
+                (
+                    "zip",
+                    Some("Required if `country` is `\"US\"`"),
+                    Some("a.json#/then/properties/zip")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_merge_dependent_schemas_of_dependencies() {
+        let schema = normalized(
+            r#"{
+                "type": "object",
+                "properties": {"card": {"type": "string"}},
+                "dependencies": {
+                    "card": {"properties": {"cvc": {"type": "string"}}, "required": ["cvc"]},
+                    "cvc": ["card"]
+                }
+            }"#,
+        );
+
+        assert!(schema.dependent_schemas.is_empty());
+        assert_eq!(
+            schema.dependent_required.into_iter().collect::<Vec<_>>(),
+            vec![
+                (String::from("card"), vec![String::from("cvc")]),
+                (String::from("cvc"), vec![String::from("card")]),
+            ]
+        );
+        assert_eq!(
+            schema.properties["cvc"].description.as_deref(),
+            Some("Required if `card` is present")
+        );
+        assert_eq!(
+            schema.properties["cvc"].src.as_deref(),
+            Some("a.json#/dependencies/card/properties/cvc")
+        );
+    }
+
+    #[test]
+    fn should_split_type_arrays_into_a_branch_of_each_type() {
+        let schema = normalized(r#"{"type": ["integer", "string", "null"], "minimum": 1}"#);
+
+        assert_eq!(
+            schema
+                .any_of
+                .iter()
+                .map(|x| (x.type_.clone().unwrap(), x.src.clone().unwrap(), x.minimum))
+                .collect::<Vec<_>>(),
+            vec![
+                (Types::Integer, String::from("a.json#/type/0"), Some(1.0)),
+                (Types::String, String::from("a.json#/type/1"), Some(1.0)),
+                (Types::Null, String::from("a.json#/type/2"), Some(1.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_inline_single_branches() {
+        let schema = normalized(
+            r#"{"title": "Outer", "description": "Outer docs", "oneOf": [{"title": "Inner", "type": ["string", "null"]}]}"#,
+        );
+
+        assert_eq!(schema.title, Some(String::from("Outer")));
+        assert_eq!(schema.description, Some(String::from("Outer docs")));
+        assert!(schema.one_of.is_empty());
+        assert_eq!(
+            schema
+                .any_of
+                .iter()
+                .map(|x| x.src.clone().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["a.json#/oneOf/0", "a.json#/oneOf/0/type/1"]
+        );
+    }
+}
@@ -0,0 +1,754 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use crate::error::Error;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[non_exhaustive]
+pub enum NamingStrategy {
+    /// Anonymous nested objects are named after their property (`Proxy`)
+    #[default]
+    PropertyName,
+    /// Anonymous nested objects are named after their parent type and property (`ConfigNetworkProxy`)
+    PropertyPath,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[non_exhaustive]
+pub enum ScalarDefinitionStyle {
+    /// References to non-object definitions are replaced by the referenced type
+    #[default]
+    Inline,
+    /// Non-object definitions become type aliases (`pub type UserId = String;`)
+    Alias,
+    /// Primitive and untyped definitions become transparent newtypes (`pub struct UserId(pub String);`),
+    /// array and map definitions become type aliases
+    Newtype,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[non_exhaustive]
+pub enum NullStyle {
+    /// Properties of type `null` become `Value`
+    #[default]
+    Value,
+    /// Properties of type `null` become `()`, which only accepts `null`
+    Unit,
+    /// Properties of type `null` become a generated `Null` unit struct
+    Marker,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[non_exhaustive]
+pub enum EmptyObjectStyle {
+    /// Objects without `properties` become `BTreeMap<String, Value>`
+    #[default]
+    Map,
+    /// Named objects without `properties` become empty structs, which drop unknown properties
+    Struct,
+    /// Objects without `properties` become maps of their `additionalProperties` schema,
+    /// falling back to `Value`
+    AdditionalProperties,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[non_exhaustive]
+pub enum BytesStyle {
+    /// Binary properties become `Vec<u8>` which is (de)serialized as base64 text
+    #[default]
+    Base64,
+    /// Binary properties become `Vec<u8>` with `#[serde(with = "serde_bytes")]`,
+    /// for formats like MessagePack or CBOR which encode bytes natively
+    SerdeBytes,
+    /// Binary properties become `bytes::Bytes`
+    Bytes,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[non_exhaustive]
+pub enum VariantBoxing {
+    /// The largest variant of an untagged enum is boxed when it is at least 200 bytes
+    /// larger than the others, like `clippy::large_enum_variant` suggests
+    #[default]
+    Large,
+    /// Every variant which holds data is boxed
+    All,
+    /// Variants are only boxed to break cycles
+    Never,
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+#[non_exhaustive]
+pub enum SourceStyle {
+    /// Sources are rendered as the path the schema file was loaded from
+    #[default]
+    Path,
+    /// Sources are rendered relative to the given directory
+    RelativeTo(PathBuf),
+    /// Sources are rendered as the `$id` of their schema, falling back to the path
+    Id,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[non_exhaustive]
+pub enum MapType {
+    /// Maps are `BTreeMap`s, ordered by key
+    #[default]
+    BTreeMap,
+    /// Maps are `HashMap`s
+    HashMap,
+    /// Maps are `indexmap::IndexMap`s, which keep the order of the document
+    /// and need the `serde` feature of `indexmap`
+    #[cfg(feature = "indexmap")]
+    IndexMap,
+}
+
+impl MapType {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            MapType::BTreeMap => "BTreeMap",
+            MapType::HashMap => "HashMap",
+            #[cfg(feature = "indexmap")]
+            MapType::IndexMap => "IndexMap",
+        }
+    }
+
+    pub(crate) fn ident(&self) -> proc_macro2::Ident {
+        proc_macro2::Ident::new(self.name(), proc_macro2::Span::call_site())
+    }
+
+    /// Whether the type name is one of the map types
+    pub(crate) fn is_map(type_name: &str) -> bool {
+        ["BTreeMap<", "HashMap<", "IndexMap<"]
+            .iter()
+            .any(|x| type_name.starts_with(x))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[non_exhaustive]
+pub enum SetType {
+    /// Arrays with `uniqueItems` are `Vec`s like all other arrays
+    #[default]
+    Vec,
+    /// Arrays with `uniqueItems` are `BTreeSet`s if their items are ordered, `Vec`s otherwise
+    BTreeSet,
+    /// Arrays with `uniqueItems` are `HashSet`s if their items are hashable, `Vec`s otherwise
+    HashSet,
+}
+
+impl SetType {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            SetType::Vec => "Vec",
+            SetType::BTreeSet => "BTreeSet",
+            SetType::HashSet => "HashSet",
+        }
+    }
+
+    /// Whether the type name is one of the set types
+    pub(crate) fn is_set(type_name: &str) -> bool {
+        ["BTreeSet<", "HashSet<"]
+            .iter()
+            .any(|x| type_name.starts_with(x))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[non_exhaustive]
+pub enum Visibility {
+    /// Types are `pub`
+    #[default]
+    Public,
+    /// Types are `pub(crate)`, which keeps them out of the public API of the crate including them
+    Crate,
+}
+
+impl Visibility {
+    pub(crate) fn tokens(&self) -> TokenStream {
+        match self {
+            Visibility::Public => quote! { pub },
+            Visibility::Crate => quote! { pub(crate) },
+        }
+    }
+}
+
+/// Attributes of the generated constructors and conversions, for crates which lint for them
+/// like `clippy::must_use_candidate` and `clippy::missing_inline_in_public_items`
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct MethodAttributes {
+    /// Inherent methods returning a value are `#[must_use]`, unless the value already is
+    pub must_use: bool,
+    /// Constructors, conversion methods and the methods of conversion traits are `#[inline]`
+    pub inline: bool,
+}
+
+impl MethodAttributes {
+    /// The attributes of an inherent method returning a value which isn't `#[must_use]` itself
+    pub(crate) fn method(&self) -> TokenStream {
+        let must_use = match self.must_use {
+            true => quote! { #[must_use] },
+            false => quote! {},
+        };
+        let inline = self.conversion();
+
+        quote! { #must_use #inline }
+    }
+
+    /// The attributes of a method of a conversion trait like `From` or `Deref`,
+    /// or of a method returning a `Result`
+    pub(crate) fn conversion(&self) -> TokenStream {
+        match self.inline {
+            true => quote! { #[inline] },
+            false => quote! {},
+        }
+    }
+}
+
+/// Paths the generated code uses to refer to its dependencies,
+/// for crates which re-export them (`my_facade::serde`)
+#[derive(Clone, PartialEq, Debug)]
+pub struct CratePaths {
+    pub serde: String,
+    pub serde_json: String,
+    /// Module providing `BTreeMap` and `HashMap`
+    pub collections: String,
+}
+
+impl Default for CratePaths {
+    fn default() -> Self {
+        CratePaths {
+            serde: String::from("serde"),
+            serde_json: String::from("serde_json"),
+            collections: String::from("std::collections"),
+        }
+    }
+}
+
+impl CratePaths {
+    pub(crate) fn serde(&self) -> TokenStream {
+        self.serde.parse().unwrap()
+    }
+
+    pub(crate) fn serde_json(&self) -> TokenStream {
+        self.serde_json.parse().unwrap()
+    }
+
+    pub(crate) fn collections(&self) -> TokenStream {
+        self.collections.parse().unwrap()
+    }
+
+    /// The derive macros refer to `serde` unless told otherwise
+    pub(crate) fn serde_crate_attribute(&self) -> TokenStream {
+        match self.serde.as_str() {
+            "serde" => quote! {},
+            serde => quote! { #[serde(crate = #serde)] },
+        }
+    }
+}
+
+/// Names of the header imports, the support code and the prelude types the fields use
+const RESERVED_TYPE_NAMES: &[&str] = &[
+    "Serialize",
+    "Deserialize",
+    "Value",
+    "BTreeMap",
+    "HashMap",
+    "IndexMap",
+    "BTreeSet",
+    "HashSet",
+    "ValidationError",
+    "Option",
+    "Result",
+    "String",
+    "Vec",
+    "Box",
+];
+
+/// Types of the formats, which the cargo feature of their crate turns on
+const FEATURE_FORMATS: &[(&str, &str)] = &[
+    #[cfg(feature = "chrono")]
+    ("date-time", "chrono::DateTime<chrono::Utc>"),
+    #[cfg(feature = "chrono")]
+    ("date", "chrono::NaiveDate"),
+    #[cfg(feature = "chrono")]
+    ("time", "chrono::NaiveTime"),
+    #[cfg(feature = "uuid")]
+    ("uuid", "uuid::Uuid"),
+    #[cfg(feature = "url")]
+    ("uri", "url::Url"),
+];
+
+#[derive(Clone, PartialEq, Debug, Default)]
+#[non_exhaustive]
+pub struct GeneratorOptions {
+    pub naming_strategy: NamingStrategy,
+    pub namespace_by_file: bool,
+    pub modules_by_id: bool,
+    pub modules_by_file: bool,
+    pub merge_identical_types: bool,
+    /// Names of the types of sources or JSON pointers, see [`crate::Generator::set_type_name`]
+    pub type_names: BTreeMap<String, String>,
+    /// Derives no type gets, not even the ones every type of its kind gets
+    pub removed_derives: Vec<String>,
+    /// Derives of single types by their name, in addition to `derives`
+    pub type_derives: BTreeMap<String, Vec<String>>,
+    /// Derives single types don't get, by their name
+    pub removed_type_derives: BTreeMap<String, Vec<String>>,
+    pub scalar_definition_style: ScalarDefinitionStyle,
+    pub null_style: NullStyle,
+    pub empty_object_style: EmptyObjectStyle,
+    pub bytes_style: BytesStyle,
+    pub variant_boxing: VariantBoxing,
+    pub string_enums: bool,
+    /// Emit every single-property object as a newtype around the property value
+    pub collapse_wrappers: bool,
+    /// Sources of single-property objects which should be emitted as newtypes
+    pub collapsed_wrappers: HashSet<String>,
+    pub source_style: SourceStyle,
+    pub crate_paths: CratePaths,
+    /// Path of the type of untyped values instead of `serde_json::Value`, e.g. `serde_yaml::Value`
+    pub any_type: Option<String>,
+    pub map_type: MapType,
+    pub set_type: SetType,
+    /// Derives every type gets in addition to the ones of its kind, e.g. `Eq` or `schemars::JsonSchema`
+    pub derives: Vec<String>,
+    pub visibility: Visibility,
+    pub method_attributes: MethodAttributes,
+    /// Optional properties which are `None` are serialized as `null` instead of being left out
+    pub serialize_none: bool,
+    /// Structs of objects whose `additionalProperties` is `false` don't get `deny_unknown_fields`
+    pub allow_unknown_fields: bool,
+    /// The `use` declarations in front of the types instead of the ones of `Serialize`,
+    /// `Deserialize`, the untyped value and the map type
+    pub header: Option<String>,
+    pub round_trip_tests: bool,
+    pub simd_json: bool,
+    pub borsh: bool,
+    pub ordering: bool,
+    pub eq_hash: bool,
+    pub read_write_only: bool,
+    /// Structs of at least this many fields get a builder
+    pub builder_threshold: Option<usize>,
+    pub constrained_newtypes: bool,
+    pub extra_properties: bool,
+    pub binary_audit: bool,
+    pub verify_defaults: bool,
+    pub lenient_numbers: bool,
+    pub empty_strings_as_none: bool,
+    /// Module paths of the types of schemas which are not generated, keyed by file name or `$id`
+    pub external_schemas: BTreeMap<String, String>,
+    /// Additional keys of properties, keyed by the source of the property
+    pub property_aliases: BTreeMap<String, Vec<String>>,
+    /// Anonymous property objects with at most this many properties are emitted as maps
+    pub inline_object_threshold: Option<usize>,
+    /// Sources of objects which should be emitted as maps
+    pub inlined_objects: HashSet<String>,
+    /// Types of strings with a `format`, keyed by the format, e.g. `uuid` to `uuid::Uuid`.
+    /// They take precedence over the types of the cargo features, `String` turns those off.
+    pub formats: BTreeMap<String, String>,
+    /// The generated code only depends on `serde` and `serde_json`: patterns are checked by
+    /// an emitted matcher instead of `regex`, and options which need other crates fall back
+    /// to `BTreeMap`, base64 encoded `Vec<u8>`, `serde_json::Value` and `String` formats.
+    /// Types given in `formats` are kept.
+    pub standalone: bool,
+    /// Functions the generated code passes through in order, before it's returned or written
+    pub post_processors: Vec<fn(TokenStream) -> TokenStream>,
+}
+
+impl GeneratorOptions {
+    pub fn with_naming_strategy(mut self, naming_strategy: NamingStrategy) -> Self {
+        self.naming_strategy = naming_strategy;
+        self
+    }
+
+    pub fn with_namespace_by_file(mut self, namespace_by_file: bool) -> Self {
+        self.namespace_by_file = namespace_by_file;
+        self
+    }
+
+    pub fn with_modules_by_id(mut self, modules_by_id: bool) -> Self {
+        self.modules_by_id = modules_by_id;
+        self
+    }
+
+    pub fn with_modules_by_file(mut self, modules_by_file: bool) -> Self {
+        self.modules_by_file = modules_by_file;
+        self
+    }
+
+    pub fn with_merge_identical_types(mut self, merge_identical_types: bool) -> Self {
+        self.merge_identical_types = merge_identical_types;
+        self
+    }
+
+    pub fn with_type_name(mut self, src: &str, name: &str) -> Self {
+        self.type_names
+            .insert(String::from(src), String::from(name));
+        self
+    }
+
+    pub fn with_removed_derives<I, S>(mut self, derives: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.removed_derives
+            .extend(derives.into_iter().map(|x| x.into()));
+        self
+    }
+
+    pub fn with_type_derives<I, S>(mut self, type_name: &str, derives: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.type_derives
+            .entry(String::from(type_name))
+            .or_default()
+            .extend(derives.into_iter().map(|x| x.into()));
+        self
+    }
+
+    pub fn with_removed_type_derives<I, S>(mut self, type_name: &str, derives: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.removed_type_derives
+            .entry(String::from(type_name))
+            .or_default()
+            .extend(derives.into_iter().map(|x| x.into()));
+        self
+    }
+
+    pub fn with_scalar_definition_style(
+        mut self,
+        scalar_definition_style: ScalarDefinitionStyle,
+    ) -> Self {
+        self.scalar_definition_style = scalar_definition_style;
+        self
+    }
+
+    pub fn with_null_style(mut self, null_style: NullStyle) -> Self {
+        self.null_style = null_style;
+        self
+    }
+
+    pub fn with_empty_object_style(mut self, empty_object_style: EmptyObjectStyle) -> Self {
+        self.empty_object_style = empty_object_style;
+        self
+    }
+
+    pub fn with_bytes_style(mut self, bytes_style: BytesStyle) -> Self {
+        self.bytes_style = bytes_style;
+        self
+    }
+
+    pub fn with_variant_boxing(mut self, variant_boxing: VariantBoxing) -> Self {
+        self.variant_boxing = variant_boxing;
+        self
+    }
+
+    pub fn with_string_enums(mut self, string_enums: bool) -> Self {
+        self.string_enums = string_enums;
+        self
+    }
+
+    pub fn with_collapse_wrappers(mut self, collapse_wrappers: bool) -> Self {
+        self.collapse_wrappers = collapse_wrappers;
+        self
+    }
+
+    pub fn with_collapsed_wrapper(mut self, pointer: &str) -> Self {
+        self.collapsed_wrappers.insert(String::from(pointer));
+        self
+    }
+
+    pub fn with_source_style(mut self, source_style: SourceStyle) -> Self {
+        self.source_style = source_style;
+        self
+    }
+
+    pub fn with_crate_paths(mut self, crate_paths: CratePaths) -> Self {
+        self.crate_paths = crate_paths;
+        self
+    }
+
+    pub fn with_any_type(mut self, path: &str) -> Self {
+        self.any_type = Some(String::from(path));
+        self
+    }
+
+    pub fn with_map_type(mut self, map_type: MapType) -> Self {
+        self.map_type = map_type;
+        self
+    }
+
+    pub fn with_set_type(mut self, set_type: SetType) -> Self {
+        self.set_type = set_type;
+        self
+    }
+
+    pub fn with_derives<I, S>(mut self, derives: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.derives.extend(derives.into_iter().map(|x| x.into()));
+        self
+    }
+
+    pub fn with_visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    pub fn with_method_attributes(mut self, method_attributes: MethodAttributes) -> Self {
+        self.method_attributes = method_attributes;
+        self
+    }
+
+    pub fn with_serialize_none(mut self, serialize_none: bool) -> Self {
+        self.serialize_none = serialize_none;
+        self
+    }
+
+    pub fn with_allow_unknown_fields(mut self, allow_unknown_fields: bool) -> Self {
+        self.allow_unknown_fields = allow_unknown_fields;
+        self
+    }
+
+    pub fn with_header(mut self, header: &str) -> Self {
+        self.header = Some(String::from(header));
+        self
+    }
+
+    pub fn with_round_trip_tests(mut self, round_trip_tests: bool) -> Self {
+        self.round_trip_tests = round_trip_tests;
+        self
+    }
+
+    pub fn with_simd_json(mut self, simd_json: bool) -> Self {
+        self.simd_json = simd_json;
+        self
+    }
+
+    pub fn with_borsh(mut self, borsh: bool) -> Self {
+        self.borsh = borsh;
+        self
+    }
+
+    pub fn with_ordering(mut self, ordering: bool) -> Self {
+        self.ordering = ordering;
+        self
+    }
+
+    pub fn with_eq_hash(mut self, eq_hash: bool) -> Self {
+        self.eq_hash = eq_hash;
+        self
+    }
+
+    pub fn with_read_write_only(mut self, read_write_only: bool) -> Self {
+        self.read_write_only = read_write_only;
+        self
+    }
+
+    pub fn with_builder_threshold(mut self, builder_threshold: Option<usize>) -> Self {
+        self.builder_threshold = builder_threshold;
+        self
+    }
+
+    pub fn with_constrained_newtypes(mut self, constrained_newtypes: bool) -> Self {
+        self.constrained_newtypes = constrained_newtypes;
+        self
+    }
+
+    pub fn with_extra_properties(mut self, extra_properties: bool) -> Self {
+        self.extra_properties = extra_properties;
+        self
+    }
+
+    pub fn with_binary_audit(mut self, binary_audit: bool) -> Self {
+        self.binary_audit = binary_audit;
+        self
+    }
+
+    pub fn with_verify_defaults(mut self, verify_defaults: bool) -> Self {
+        self.verify_defaults = verify_defaults;
+        self
+    }
+
+    pub fn with_lenient_numbers(mut self, lenient_numbers: bool) -> Self {
+        self.lenient_numbers = lenient_numbers;
+        self
+    }
+
+    pub fn with_empty_strings_as_none(mut self, empty_strings_as_none: bool) -> Self {
+        self.empty_strings_as_none = empty_strings_as_none;
+        self
+    }
+
+    pub fn with_external_schema(mut self, schema: &str, module: &str) -> Self {
+        self.external_schemas.insert(
+            String::from(schema.trim_end_matches('#')),
+            String::from(module.trim_end_matches("::")),
+        );
+        self
+    }
+
+    pub fn with_property_alias(mut self, pointer: &str, alias: &str) -> Self {
+        self.property_aliases
+            .entry(String::from(pointer))
+            .or_default()
+            .push(String::from(alias));
+        self
+    }
+
+    pub fn with_inline_object_threshold(mut self, inline_object_threshold: Option<usize>) -> Self {
+        self.inline_object_threshold = inline_object_threshold;
+        self
+    }
+
+    pub fn with_inlined_object(mut self, pointer: &str) -> Self {
+        self.inlined_objects.insert(String::from(pointer));
+        self
+    }
+
+    pub fn with_format(mut self, format: &str, type_path: &str) -> Self {
+        self.formats
+            .insert(String::from(format), String::from(type_path));
+        self
+    }
+
+    pub fn with_standalone(mut self, standalone: bool) -> Self {
+        self.standalone = standalone;
+        self
+    }
+
+    pub fn with_post_processor(mut self, post_processor: fn(TokenStream) -> TokenStream) -> Self {
+        self.post_processors.push(post_processor);
+        self
+    }
+
+    /// Checks the options which are rendered as code, so they can't panic the generation
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        let paths = &self.crate_paths;
+        let code = vec![
+            ("crate_paths.serde", &paths.serde),
+            ("crate_paths.serde_json", &paths.serde_json),
+            ("crate_paths.collections", &paths.collections),
+        ]
+        .into_iter()
+        .chain(self.header.iter().map(|x| ("header", x)))
+        .chain(self.any_type.iter().map(|x| ("any_type", x)))
+        .chain(self.derives.iter().map(|x| ("derives", x)))
+        .chain(
+            self.type_derives
+                .values()
+                .flatten()
+                .map(|x| ("type_derives", x)),
+        )
+        .chain(self.formats.values().map(|x| ("formats", x)))
+        .chain(
+            self.external_schemas
+                .values()
+                .map(|x| ("external_schemas", x)),
+        );
+
+        for (option, value) in code {
+            if let Err(error) = value.parse::<TokenStream>() {
+                return Err(Error::Config {
+                    option: String::from(option),
+                    message: format!("`{}` is no Rust code: {}", value, error),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn post_process(&self, tokens: TokenStream) -> TokenStream {
+        self.post_processors
+            .iter()
+            .fold(tokens, |tokens, post_processor| post_processor(tokens))
+    }
+
+    /// The name given to the type of the source, by its source or its JSON pointer
+    pub(crate) fn type_name(&self, src: &str) -> Option<&String> {
+        let pointer = match src.split_once('#') {
+            Some((_, pointer)) => format!("#{}", pointer),
+            None => String::from("#"),
+        };
+
+        self.type_names
+            .get(src)
+            .or_else(|| self.type_names.get(&pointer))
+    }
+
+    /// The type of strings with the format, which is `String` for unknown formats
+    pub(crate) fn format_type(&self, format: &str) -> String {
+        let feature_type = FEATURE_FORMATS
+            .iter()
+            .filter(|_| !self.standalone)
+            .find(|(name, _)| *name == format)
+            .map(|(_, type_path)| type_path.to_string());
+
+        self.formats
+            .get(format)
+            .cloned()
+            .or(feature_type)
+            .unwrap_or_else(|| String::from("String"))
+    }
+
+    pub(crate) fn effective_map_type(&self) -> MapType {
+        match (self.standalone, self.map_type) {
+            #[cfg(feature = "indexmap")]
+            (true, MapType::IndexMap) => MapType::BTreeMap,
+            (_, map_type) => map_type,
+        }
+    }
+
+    pub(crate) fn effective_bytes_style(&self) -> BytesStyle {
+        match self.standalone {
+            true => BytesStyle::Base64,
+            false => self.bytes_style,
+        }
+    }
+
+    fn custom_any_type(&self) -> Option<&str> {
+        self.any_type.as_deref().filter(|_| !self.standalone)
+    }
+
+    /// The name the generated code refers to untyped values by
+    pub(crate) fn any_type_name(&self) -> &str {
+        let path = self.any_type_label();
+        path.rsplit("::").next().unwrap_or(path).trim()
+    }
+
+    /// Whether a generated type of the name would clash with the imports of the header,
+    /// the support code or the prelude types the fields use
+    pub(crate) fn is_reserved_type_name(&self, name: &str) -> bool {
+        RESERVED_TYPE_NAMES.contains(&name) || name == self.any_type_name()
+    }
+
+    /// The path of the type of untyped values, without custom crate paths
+    pub(crate) fn any_type_label(&self) -> &str {
+        self.custom_any_type().unwrap_or("serde_json::Value")
+    }
+
+    pub(crate) fn any_type_path(&self) -> TokenStream {
+        match self.custom_any_type() {
+            Some(path) => path.parse().unwrap(),
+            None => {
+                let serde_json = self.crate_paths.serde_json();
+                quote! { #serde_json::Value }
+            }
+        }
+    }
+}
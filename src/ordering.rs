@@ -0,0 +1,198 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use crate::generated::{GeneratedType, TypeKind};
+use crate::options::MapType;
+use std::collections::HashSet;
+
+const ORD_DERIVES: [&str; 3] = ["Eq", "PartialOrd", "Ord"];
+
+const EQ_HASH_DERIVES: [&str; 2] = ["Eq", "Hash"];
+
+/// Names in type names which are, or are ordered if their parameters are
+const ORDERED_NAMES: [&str; 17] = [
+    "bool", "i64", "u64", "String", "Vec", "Option", "Box", "BTreeMap", "u8", "bytes", "Bytes",
+    "u16", "u32", "i8", "i16", "i32", "BTreeSet",
+];
+
+/// Derives `Eq`, `PartialOrd` and `Ord` for every type which only consists of ordered
+/// types, directly or through other generated types. Variants of enums are ordered
+/// by declaration.
+pub fn add_ord_derives(types: &mut [GeneratedType]) {
+    add_derives_to_ordered(types, &ORD_DERIVES);
+}
+
+/// Derives `Eq` and `Hash` for the same types as [`add_ord_derives`], since the types
+/// which are ordered are also hashable, so they can be keys of maps and items of sets
+pub fn add_eq_hash_derives(types: &mut [GeneratedType]) {
+    add_derives_to_ordered(types, &EQ_HASH_DERIVES);
+}
+
+fn add_derives_to_ordered(types: &mut [GeneratedType], derives: &[&str]) {
+    let unordered = unordered_types(types);
+    let derives: Vec<String> = derives.iter().map(|x| x.to_string()).collect();
+
+    for generated_type in types.iter_mut() {
+        if !unordered.contains(&generated_type.name) {
+            generated_type.add_derives(&derives);
+        }
+    }
+}
+
+fn unordered_types(types: &[GeneratedType]) -> HashSet<String> {
+    let names: HashSet<&str> = types.iter().map(|x| x.name.as_str()).collect();
+    let mut unordered: HashSet<String> = types
+        .iter()
+        .filter(
+            |x| matches!(x.kind, TypeKind::Map { map_type, .. } if map_type != MapType::BTreeMap),
+        )
+        .map(|x| x.name.clone())
+        .collect();
+
+    loop {
+        let mut changed = false;
+
+        for generated_type in types {
+            if unordered.contains(&generated_type.name) {
+                continue;
+            }
+
+            let ordered = generated_type.referenced_names().into_iter().all(|part| {
+                match names.contains(part) {
+                    true => !unordered.contains(part),
+                    false => ORDERED_NAMES.contains(&part),
+                }
+            });
+
+            if !ordered {
+                unordered.insert(generated_type.name.clone());
+                changed = true;
+            }
+        }
+
+        if !changed {
+            return unordered;
+        }
+    }
+}
+
+#[cfg(test)]
+mod ordering_tests {
+    use crate::generated::{
+        GeneratedProperty, GeneratedType, GeneratedVariant, SerdeOptions, TypeKind,
+    };
+    use crate::ordering::{add_eq_hash_derives, add_ord_derives};
+
+    fn generated_type(name: &str, kind: TypeKind, property_types: Vec<&str>) -> GeneratedType {
+        GeneratedType {
+            src: String::new(),
+            name: String::from(name),
+            doc_alias: None,
+            description: None,
+            derives: vec![],
+            removed_derives: vec![],
+            kind,
+            constants: vec![],
+            defaults: vec![],
+            deny_unknown_fields: false,
+            properties: property_types
+                .into_iter()
+                .enumerate()
+                .map(|(i, property_type)| GeneratedProperty {
+                    name: format!("field{}", i),
+                    property_type: String::from(property_type),
+                    serde_options: SerdeOptions {
+                        rename: None,
+                        skip_serializing_if: None,
+                        default: None,
+                        with: None,
+                        deserialize_with: None,
+                        aliases: vec![],
+                        flatten: false,
+                        skip_serializing: false,
+                        skip_deserializing: false,
+                    },
+                    description: None,
+                    map_constraints: None,
+                    value_constraints: None,
+                    requires: vec![],
+                })
+                .collect(),
+            examples: vec![],
+            module: vec![],
+            required_any: vec![],
+        }
+    }
+
+    #[test]
+    fn should_only_derive_ord_for_types_of_ordered_fields() {
+        let mut types = vec![
+            generated_type(
+                "Version",
+                TypeKind::Struct,
+                vec!["i64", "Option<String>", "Vec<Label>"],
+            ),
+            generated_type("Label", TypeKind::StringEnum(vec![]), vec![]),
+            generated_type("Point", TypeKind::Struct, vec!["Option<Meters>"]),
+            generated_type("Meters", TypeKind::Alias(String::from("f64")), vec![]),
+            generated_type("Node", TypeKind::Struct, vec!["Option<Box<Node>>"]),
+            generated_type(
+                "Id",
+                TypeKind::UntaggedEnum(vec![
+                    GeneratedVariant {
+                        name: String::from("Integer"),
+                        variant_type: String::from("i64"),
+                    },
+                    GeneratedVariant {
+                        name: String::from("Any"),
+                        variant_type: String::from("Value"),
+                    },
+                ]),
+                vec![],
+            ),
+        ];
+
+        add_ord_derives(&mut types);
+
+        assert_eq!(
+            types
+                .iter()
+                .map(|x| (x.name.as_str(), x.derives.join(", ")))
+                .collect::<Vec<(&str, String)>>(),
+            vec![
+                ("Version", String::from("Eq, PartialOrd, Ord")),
+                ("Label", String::new()),
+                ("Point", String::new()),
+                ("Meters", String::new()),
+                ("Node", String::from("Eq, PartialOrd, Ord")),
+                ("Id", String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_derive_hash_for_types_without_floats() {
+        let mut types = vec![
+            generated_type("Tag", TypeKind::Struct, vec!["String", "Option<u32>"]),
+            generated_type("Point", TypeKind::Struct, vec!["f64", "Vec<Tag>"]),
+            generated_type("Labels", TypeKind::Struct, vec!["BTreeSet<Tag>"]),
+            generated_type("Extra", TypeKind::Struct, vec!["BTreeMap<String, Value>"]),
+        ];
+
+        add_eq_hash_derives(&mut types);
+
+        assert_eq!(
+            types
+                .iter()
+                .map(|x| (x.name.as_str(), x.derives.join(", ")))
+                .collect::<Vec<(&str, String)>>(),
+            vec![
+                ("Tag", String::from("Eq, Hash")),
+                ("Point", String::new()),
+                ("Labels", String::from("Eq, Hash")),
+                ("Extra", String::new()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,84 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Lists the files of the previous run, so only generated files are ever deleted
+pub const MANIFEST: &str = ".jsonschema_code_generator";
+
+/// Writes the files into the directory and removes the files of the previous run
+/// which are not part of this one.
+///
+/// Returns the removed files.
+pub fn write_files(dir: &Path, files: &[(String, String)]) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+
+    let previous: BTreeSet<String> = match fs::read_to_string(dir.join(MANIFEST)) {
+        Ok(manifest) => manifest.lines().map(String::from).collect(),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => BTreeSet::new(),
+        Err(error) => return Err(error),
+    };
+
+    let current: BTreeSet<String> = files.iter().map(|(name, _)| name.clone()).collect();
+
+    for (name, content) in files {
+        let path = dir.join(name);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Unchanged files keep their timestamp, so cargo doesn't rebuild them
+        if fs::read_to_string(&path).ok().as_ref() != Some(content) {
+            fs::write(&path, content)?;
+        }
+    }
+
+    let mut removed = Vec::new();
+
+    for name in previous.difference(&current) {
+        let path = dir.join(name);
+
+        match fs::remove_file(&path) {
+            Ok(()) => removed.push(path),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => return Err(error),
+        }
+    }
+
+    let manifest: Vec<&str> = current.iter().map(|x| x.as_str()).collect();
+    fs::write(dir.join(MANIFEST), manifest.join("\n") + "\n")?;
+
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod output_tests {
+    use std::fs;
+
+    use crate::output::write_files;
+
+    #[test]
+    fn should_remove_stale_files_of_the_previous_run() {
+        let dir = std::env::temp_dir().join("jsonschema_code_generator_output_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("handwritten.rs"), "").unwrap();
+
+        let file = |name: &str| (String::from(name), String::from("// generated"));
+
+        write_files(&dir, &[file("mod.rs"), file("old_name.rs")]).unwrap();
+        let removed = write_files(&dir, &[file("mod.rs"), file("new_name.rs")]).unwrap();
+
+        assert_eq!(removed, vec![dir.join("old_name.rs")]);
+        assert!(dir.join("new_name.rs").exists());
+        assert!(dir.join("handwritten.rs").exists());
+        assert!(!dir.join("old_name.rs").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -2,9 +2,17 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
 
+use crate::defaults::default_violation;
+use crate::dialect::{apply_dialect, schema_id, Dialect};
+use crate::error::Error;
+use crate::locations::Locations;
+use crate::normalizer::normalize;
+use crate::ref_parser::child_src;
+use crate::sanitizer::singularize;
 use crate::schema::{Schema, Types};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+#[cfg(feature = "fs")]
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
@@ -12,109 +20,507 @@ use std::rc::Rc;
 #[derive(PartialEq, Debug)]
 pub struct Root {
     pub file: PathBuf,
+    /// The draft of the file's `$schema`, whose rules its schemas are parsed with
+    pub dialect: Option<Dialect>,
+    pub id: Option<String>,
+    pub title: Option<String>,
     pub data_type: Rc<DataType>,
     pub definitions: HashMap<String, Rc<DataType>>,
+    /// Number of allowed values of every `enum`, keyed by source
+    pub enums: BTreeMap<String, usize>,
+    /// Why a `default` violates its own subschema, keyed by source
+    pub invalid_defaults: BTreeMap<String, String>,
+    /// The `examples` of every schema which has some, keyed by source
+    pub examples: BTreeMap<String, Vec<Value>>,
+    /// The `pattern`, length and bounds of every schema which has some, keyed by source
+    pub constraints: BTreeMap<String, ValueConstraints>,
+    /// The schema of the file as written, for `$ref`s to JSON pointers which are no definitions
+    pub schema: Schema,
+    pub locations: Locations,
 }
 
-#[derive(PartialEq, Debug)]
+impl Root {
+    /// Describes where a source of this file is, as `file.json:12:7 at #/properties/foo`
+    pub fn describe(&self, src: &str) -> String {
+        let pointer = src.split_once('#').map(|(_, x)| x).unwrap_or_default();
+
+        match self.locations.get(pointer) {
+            Some((line, column)) => format!(
+                "{}:{}:{} at #{}",
+                self.file.display(),
+                line,
+                column,
+                pointer
+            ),
+            None => format!("{} at #{}", self.file.display(), pointer),
+        }
+    }
+
+    /// Parses the schema a JSON pointer of unescaped segments points to, like
+    /// `["properties", "foo", "items"]`
+    pub fn parse_pointer(&self, segments: &[String]) -> Result<DataType, String> {
+        let pointer: String = segments
+            .iter()
+            .map(|x| format!("/{}", x.replace('~', "~0").replace('/', "~1")))
+            .collect();
+        let missing = || format!("No schema at #{} of {}", pointer, self.file.display());
+        let mut schema = &self.schema;
+        let mut name = None;
+        let mut segments_iter = segments.iter();
+
+        while let Some(keyword) = segments_iter.next() {
+            let child = match keyword.as_str() {
+                "properties" | "patternProperties" | "definitions" | "$defs" => {
+                    let key = segments_iter.next().ok_or_else(missing)?;
+                    let schemas = match keyword.as_str() {
+                        "properties" => &schema.properties,
+                        "patternProperties" => &schema.pattern_properties,
+                        "definitions" => &schema.definitions,
+                        _ => &schema.defs,
+                    };
+
+                    name = match keyword.as_str() {
+                        "patternProperties" => None,
+                        _ => Some(key.clone()),
+                    };
+                    schemas.get(key)
+                }
+                "items" if schema.items_array => {
+                    let index = segments_iter.next().and_then(|x| x.parse::<usize>().ok());
+
+                    name = None;
+                    index.and_then(|x| schema.prefix_items.get(x))
+                }
+                "oneOf" | "anyOf" | "allOf" | "prefixItems" => {
+                    let schemas = match keyword.as_str() {
+                        "oneOf" => &schema.one_of,
+                        "anyOf" => &schema.any_of,
+                        "allOf" => &schema.all_of,
+                        _ => &schema.prefix_items,
+                    };
+                    let index = segments_iter.next().and_then(|x| x.parse::<usize>().ok());
+
+                    name = None;
+                    index.and_then(|x| schemas.get(x))
+                }
+                "items" | "additionalProperties" | "propertyNames" => {
+                    name = None;
+
+                    match keyword.as_str() {
+                        "items" => schema.items.as_ref().as_ref(),
+                        "additionalProperties" => schema.additional_properties.as_ref().as_ref(),
+                        _ => schema.property_names.as_ref().as_ref(),
+                    }
+                }
+                _ => None,
+            };
+
+            schema = child.ok_or_else(missing)?;
+        }
+
+        let segments: Vec<&str> = segments.iter().map(|x| x.as_str()).collect();
+        let src = child_src(&self.file.display().to_string(), &segments);
+        let mut schema = schema.clone();
+        normalize(&src, &mut schema);
+
+        Ok(parse_type(src, schema, None, name))
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
 pub enum DataType {
     PrimitiveType(PrimitiveType),
     Array(Rc<DataType>),
+    /// An array with `uniqueItems`, which the generator may emit as a set
+    Set(Rc<DataType>),
     Object(Object),
     Map(Rc<DataType>),
+    KeyedMap(KeyedMap),
+    EmptyObject(EmptyObject),
+    StringEnum(StringEnum),
     Ref(Ref),
     OneOf(OneOf),
     AnyOf(AnyOf),
     AllOf(AllOf),
+    /// An array of a fixed number of items with their own types, from `prefixItems`
+    Tuple(Vec<Rc<DataType>>),
+    /// The path of a Rust type from the `x-rust-type` extension, which is used as it is
+    Custom(String),
     Any,
 }
 
-#[derive(PartialEq, Debug)]
+/// Whether the type is `i64` or one the bounds of integers select
+pub fn is_integer_type(type_name: &str) -> bool {
+    matches!(
+        type_name,
+        "u8" | "u16" | "u32" | "u64" | "i8" | "i16" | "i32" | "i64"
+    )
+}
+
+/// The smallest and largest value of an integer type
+pub fn integer_range(type_name: &str) -> Option<(f64, f64)> {
+    match type_name {
+        "u8" => Some((0.0, u8::MAX as f64)),
+        "u16" => Some((0.0, u16::MAX as f64)),
+        "u32" => Some((0.0, u32::MAX as f64)),
+        "u64" => Some((0.0, u64::MAX as f64)),
+        "i8" => Some((i8::MIN as f64, i8::MAX as f64)),
+        "i16" => Some((i16::MIN as f64, i16::MAX as f64)),
+        "i32" => Some((i32::MIN as f64, i32::MAX as f64)),
+        "i64" => Some((i64::MIN as f64, i64::MAX as f64)),
+        _ => None,
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
 pub enum PrimitiveType {
     Null,
     Boolean,
     Integer,
+    /// An integer whose bounds select a type other than `i64`, like `u64` for `minimum: 0`
+    SizedInteger(&'static str),
     Number,
     String,
+    /// Binary content, from `contentEncoding: base64` or the OpenAPI formats `byte` and `binary`
+    Bytes,
+    /// A string with a `format`, which the generator may map to a richer type
+    Format(String),
 }
 
-#[derive(PartialEq, Debug)]
-pub struct PrimitiveTypeInfos {
-    pub enum_values: Vec<Value>,
-    pub constant: Option<Value>,
-}
-
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct Object {
     pub src: String,
     pub name: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
     pub properties: Vec<ObjectProperty>,
+    /// Sets of properties of which at least one has to be present, from an `anyOf`
+    pub required_any: Vec<Vec<String>>,
+    /// Names in `required` without a property, which only have to be present
+    pub unmatched_required: Vec<String>,
+    /// `additionalProperties` is `false`, so keys without a property are rejected
+    pub deny_unknown_fields: bool,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct ObjectProperty {
     pub name: String,
     pub required: bool,
+    pub constant: Option<Value>,
+    /// The `default` value, which absent properties take
+    pub default: Option<Value>,
     pub data_type: Rc<DataType>,
+    pub description: Option<String>,
+    pub map_constraints: Option<MapConstraints>,
+    pub value_constraints: Option<ValueConstraints>,
+    /// Properties which have to be present if this one is, from `dependentRequired`
+    pub requires: Vec<String>,
+    /// Other keys the property is read from, from the `x-aliases` extension
+    pub aliases: Vec<String>,
+    pub read_only: bool,
+    pub write_only: bool,
 }
 
-#[derive(PartialEq, Debug)]
+/// Entry-count bounds, the key pattern and the required keys of a map, which its type can't express
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct MapConstraints {
+    pub min_properties: Option<u64>,
+    pub max_properties: Option<u64>,
+    /// The `patternProperties` pattern, unless `propertyNames` already gives the keys a type
+    pub key_pattern: Option<String>,
+    /// Keys which have to be present, from `required`
+    pub required_keys: Vec<String>,
+}
+
+/// The `pattern`, length, bounds, item counts and `multipleOf` of a value,
+/// which its type can't express
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct ValueConstraints {
+    pub pattern: Option<String>,
+    pub min_length: Option<u64>,
+    pub max_length: Option<u64>,
+    /// The lower bound and whether it is exclusive
+    pub minimum: Option<(f64, bool)>,
+    /// The upper bound and whether it is exclusive
+    pub maximum: Option<(f64, bool)>,
+    pub multiple_of: Option<f64>,
+    pub min_items: Option<u64>,
+    pub max_items: Option<u64>,
+    /// The constraints of the items of an array
+    pub items: Option<Box<ValueConstraints>>,
+}
+
+// The numbers come from JSON, which has no NaN
+impl Eq for ValueConstraints {}
+
+impl ValueConstraints {
+    pub fn is_empty(&self) -> bool {
+        self == &ValueConstraints::default()
+    }
+
+    /// Whether the value or its items are checked against a pattern
+    pub(crate) fn has_pattern(&self) -> bool {
+        self.pattern.is_some() || self.items.as_ref().is_some_and(|x| x.has_pattern())
+    }
+}
+
+/// A map whose keys are restricted by a `propertyNames` pattern
+#[derive(Clone, PartialEq, Debug)]
+pub struct KeyedMap {
+    pub key: MapKey,
+    pub values: Rc<DataType>,
+}
+
+/// An object without `properties` or `patternProperties`, which the generator
+/// emits as a map or an empty struct
+#[derive(Clone, PartialEq, Debug)]
+pub struct EmptyObject {
+    pub src: String,
+    /// Title or property name
+    pub name: Option<String>,
+    pub title: Option<String>,
+    pub key: Option<MapKey>,
+    pub additional_properties: Option<Rc<DataType>>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct MapKey {
+    pub src: String,
+    pub name: String,
+    pub pattern: String,
+}
+
+/// A string restricted to an `enum` of strings
+#[derive(Clone, PartialEq, Debug)]
+pub struct StringEnum {
+    pub src: String,
+    /// Title or property name, an enum without one stays a `String`
+    pub name: String,
+    pub description: Option<String>,
+    pub values: Vec<String>,
+    /// Docs of the values, from their titles and descriptions or `x-enumDescriptions`
+    pub descriptions: Vec<Option<String>>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
 pub struct Ref {
+    /// Source of the schema with the `$ref`
+    pub src: String,
     pub ref_path: String,
+    /// Keywords next to the `$ref`, which take precedence over the ones of the target
+    pub description: Option<String>,
+    pub default: Option<Value>,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct OneOf {
+    pub src: String,
+    /// Title or property name
+    pub name: Option<String>,
     pub types: Vec<DataType>,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct AnyOf {
+    pub src: String,
+    /// Title or property name
+    pub name: Option<String>,
     pub types: Vec<DataType>,
+    /// The `patternProperties` pattern of each type, if the types are the values of a map
+    pub patterns: Vec<String>,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct AllOf {
+    pub src: String,
+    /// Title or property name
+    pub name: Option<String>,
     pub types: Vec<DataType>,
 }
 
-pub fn parse_from_file(file: &Path) -> Root {
+pub type Overlay = HashMap<String, String>;
+
+#[cfg(all(test, feature = "fs"))]
+pub fn parse_from_file(file: &Path, overlay: &Overlay) -> Root {
+    try_parse_from_file(file, overlay).unwrap_or_else(|error| panic!("{}", error))
+}
+
+pub fn try_parse_from_file(file: &Path, overlay: &Overlay) -> Result<Root, Error> {
+    let (file, json_schema) = read_source(file, overlay)?;
+    try_parse_from_string(&file, &json_schema)
+}
+
+/// The path and the content of the file, from the overlay if it has one.
+/// Files which don't exist fall back to the same path with a `.json` extension.
+pub fn read_source(file: &Path, overlay: &Overlay) -> Result<(PathBuf, String), Error> {
+    let fallback = file.to_path_buf().with_extension("json");
+
+    for file in [file, fallback.as_path()] {
+        if let Some(json_schema) = overlay.get(&file.display().to_string()) {
+            return Ok((file.to_path_buf(), json_schema.clone()));
+        }
+    }
+
+    read_file(file, fallback)
+}
+
+#[cfg(feature = "fs")]
+fn read_file(file: &Path, fallback: PathBuf) -> Result<(PathBuf, String), Error> {
     let file = match file.exists() {
         true => file.to_path_buf(),
-        false => file.to_path_buf().with_extension("json"),
+        false => fallback,
     };
 
     match fs::read_to_string(&file) {
-        Ok(json_schema) => parse_from_string(&file, &json_schema),
-        Err(err) => panic!("Could not open {}: {}", &file.display(), err),
+        Ok(json_schema) => Ok((file, json_schema)),
+        Err(err) => Err(Error::Read {
+            file,
+            message: err.to_string(),
+        }),
     }
 }
 
-pub fn parse_from_string(file: &Path, json_schema: &str) -> Root {
+#[cfg(not(feature = "fs"))]
+fn read_file(file: &Path, _fallback: PathBuf) -> Result<(PathBuf, String), Error> {
+    Err(Error::Read {
+        file: file.to_path_buf(),
+        message: String::from("the file system is disabled, provide the schema as an overlay"),
+    })
+}
+
+pub fn try_parse_from_string(file: &Path, json_schema: &str) -> Result<Root, Error> {
     let src = file.display().to_string();
-    match serde_json::from_str(json_schema) {
-        Ok(schema) => {
-            let definitions = parse_definitions(src.clone(), &schema);
-            let data_type = Rc::new(parse_type(src, schema, None, None));
+    match serde_json::from_str::<Schema>(json_schema) {
+        Ok(mut schema) => {
+            let dialect = schema.dollar_schema.as_deref().and_then(Dialect::from_uri);
+            apply_dialect(dialect, &mut schema);
+            let id = schema_id(dialect, &schema);
+            let title = schema.title.clone();
+            let mut enums = BTreeMap::new();
+            let mut invalid_defaults = BTreeMap::new();
+            let mut examples = BTreeMap::new();
+            let mut constraints = BTreeMap::new();
+            visit_schemas(src.clone(), &schema, &mut |src, schema| {
+                if let Some(values) = &schema.enum_ {
+                    enums.insert(src.to_string(), values.len());
+                }
+
+                if let Some(violation) = default_violation(schema) {
+                    invalid_defaults.insert(src.to_string(), violation);
+                }
+
+                if let Some(Value::Array(values)) = &schema.examples {
+                    if !values.is_empty() {
+                        examples.insert(src.to_string(), values.clone());
+                    }
+                }
+
+                if let Some(value_constraints) = parse_value_constraints(schema) {
+                    constraints.insert(src.to_string(), value_constraints);
+                }
+            });
+            let mut normalized = schema.clone();
+            normalize(&src, &mut normalized);
+            let definitions = parse_definitions(src.clone(), &normalized);
+            let data_type = Rc::new(parse_type(src, normalized, None, None));
             let mut file_buf = PathBuf::new();
             file_buf.push(file);
-            Root {
+            Ok(Root {
                 file: file_buf,
+                dialect,
+                id,
+                title,
                 data_type,
                 definitions,
-            }
+                enums,
+                invalid_defaults,
+                examples,
+                constraints,
+                schema,
+                locations: Locations::index(json_schema),
+            })
         }
-        Err(err) => {
-            panic!("Could not parse {}: {}", file.display(), err)
+        Err(err) => Err(parse_error(file, err)),
+    }
+}
+
+pub fn parse_error(file: &Path, err: serde_json::Error) -> Error {
+    let message = err.to_string();
+    let location = format!(" at line {} column {}", err.line(), err.column());
+
+    Error::Parse {
+        file: file.to_path_buf(),
+        line: err.line(),
+        column: err.column(),
+        message: message
+            .strip_suffix(&location)
+            .unwrap_or(&message)
+            .to_string(),
+    }
+}
+
+fn visit_schemas(src: String, schema: &Schema, visit: &mut impl FnMut(&str, &Schema)) {
+    visit(&src, schema);
+
+    let children = [
+        ("properties", &schema.properties),
+        ("patternProperties", &schema.pattern_properties),
+        ("definitions", &schema.definitions),
+        ("$defs", &schema.defs),
+    ];
+
+    for (keyword, schemas) in children {
+        for (name, child) in schemas {
+            visit_schemas(child_src(&src, &[keyword, name]), child, visit);
+        }
+    }
+
+    let alternatives = [
+        ("oneOf", &schema.one_of),
+        ("anyOf", &schema.any_of),
+        ("allOf", &schema.all_of),
+    ];
+
+    for (keyword, schemas) in alternatives {
+        for (i, child) in schemas.iter().enumerate() {
+            visit_schemas(child_src(&src, &[keyword, &i.to_string()]), child, visit);
+        }
+    }
+
+    if let Some(items) = &*schema.items {
+        visit_schemas(child_src(&src, &["items"]), items, visit);
+    }
+
+    for (i, child) in schema.prefix_items.iter().enumerate() {
+        let keyword = schema.prefix_items_keyword();
+        visit_schemas(child_src(&src, &[keyword, &i.to_string()]), child, visit);
+    }
+
+    if let Some(additional_properties) = &*schema.additional_properties {
+        visit_schemas(
+            child_src(&src, &["additionalProperties"]),
+            additional_properties,
+            visit,
+        );
+    }
+
+    for (keyword, branch) in [("then", &*schema.then), ("else", &*schema.else_)] {
+        if let Some(branch) = branch {
+            visit_schemas(child_src(&src, &[keyword]), branch, visit);
         }
     }
+
+    for (key, dependent) in &schema.dependent_schemas {
+        let keyword = schema.dependent_schemas_keyword();
+        visit_schemas(child_src(&src, &[keyword, key]), dependent, visit);
+    }
 }
 
 fn parse_definitions(src: String, schema: &Schema) -> HashMap<String, Rc<DataType>> {
     let mut definitions = HashMap::new();
 
     for (name, definition) in schema.defs.clone() {
-        let src = format!("{}/$defs/{}", src, name);
+        let src = child_src(&src, &["$defs", &name]);
         definitions.insert(
             name.clone(),
             Rc::new(parse_type(src, definition, None, Some(name))),
@@ -122,7 +528,7 @@ fn parse_definitions(src: String, schema: &Schema) -> HashMap<String, Rc<DataTyp
     }
 
     for (name, definition) in schema.definitions.clone() {
-        let src = format!("{}/definitions/{}", src, name);
+        let src = child_src(&src, &["definitions", &name]);
         definitions.insert(
             name.clone(),
             Rc::new(parse_type(src, definition, None, Some(name))),
@@ -138,52 +544,123 @@ fn parse_type(
     parent_schema: Option<&Schema>,
     property_name: Option<String>,
 ) -> DataType {
-    match schema.ref_ {
-        Some(ref_path) => DataType::Ref(Ref { ref_path }),
+    let src = schema.src.clone().unwrap_or(src);
+
+    if let Some(type_path) = &schema.x_rust_type {
+        return DataType::Custom(type_path.clone());
+    }
+
+    match schema.ref_.clone() {
+        Some(ref_path) => DataType::Ref(Ref {
+            src,
+            ref_path,
+            description: schema.description.clone(),
+            default: schema.default.clone(),
+        }),
         None => {
-            if schema.one_of.len() > 0 {
+            if let Some(string_enum) = parse_const_enum(&src, &schema, property_name.clone()) {
+                return DataType::StringEnum(string_enum);
+            }
+
+            if !schema.one_of.is_empty() {
                 let mut data_types = vec![];
 
                 for (i, alternative) in (0..).zip(schema.clone().one_of) {
+                    let name = split_off_name(&src, "oneOf", &alternative, &property_name);
                     data_types.push(parse_type(
-                        format!("{}/oneOf/{}", src, i),
-                        alternative,
+                        child_src(&src, &["oneOf", &i.to_string()]),
+                        inherit_parent_keywords(alternative, &schema),
                         Some(&schema),
-                        None,
+                        name,
                     ));
                 }
 
-                return DataType::OneOf(OneOf { types: data_types });
+                return DataType::OneOf(OneOf {
+                    src,
+                    name: property_name.or_else(|| schema.title.clone()),
+                    types: data_types,
+                });
+            }
+
+            if let Some(required_any) = parse_required_any(&schema) {
+                let object_schema = Schema {
+                    any_of: vec![],
+                    ..schema
+                };
+
+                return match parse_object_type(src, object_schema, parent_schema, property_name) {
+                    DataType::Object(object) => DataType::Object(Object {
+                        required_any,
+                        unmatched_required: vec![],
+                        ..object
+                    }),
+                    data_type => data_type,
+                };
             }
 
-            if schema.any_of.len() > 0 {
+            if !schema.any_of.is_empty() {
                 let mut data_types = vec![];
 
                 for (i, alternative) in (0..).zip(schema.clone().any_of) {
+                    let name = split_off_name(&src, "anyOf", &alternative, &property_name);
                     data_types.push(parse_type(
-                        format!("{}/anyOf/{}", src, i),
-                        alternative,
+                        child_src(&src, &["anyOf", &i.to_string()]),
+                        inherit_parent_keywords(alternative, &schema),
                         Some(&schema),
-                        None,
+                        name,
                     ));
                 }
 
-                return DataType::AnyOf(AnyOf { types: data_types });
+                return DataType::AnyOf(AnyOf {
+                    src,
+                    name: property_name.or_else(|| schema.title.clone()),
+                    types: data_types,
+                    patterns: vec![],
+                });
             }
 
-            if schema.all_of.len() > 0 {
+            if !schema.all_of.is_empty() {
                 let mut data_types = vec![];
 
                 for (i, alternative) in (0..).zip(schema.clone().all_of) {
-                    data_types.push(parse_type(
-                        format!("{}/allOf/{}", src, i),
-                        alternative,
-                        Some(&schema),
-                        None,
-                    ));
+                    let mut alternative = inherit_parent_keywords(alternative, &schema);
+
+                    // Properties which are only required are marked as required by the merge,
+                    // if another branch defines them
+                    let object = alternative.ref_.is_none()
+                        && matches!(alternative.type_, None | Some(Types::Object))
+                        && alternative.one_of.is_empty()
+                        && alternative.any_of.is_empty()
+                        && alternative.all_of.is_empty();
+
+                    if object && alternative.required.is_some() {
+                        alternative.type_ = Some(Types::Object);
+                    }
+
+                    let name = split_off_name(&src, "allOf", &alternative, &property_name);
+                    let alternative_src = alternative
+                        .src
+                        .clone()
+                        .unwrap_or_else(|| child_src(&src, &["allOf", &i.to_string()]));
+                    let only_required = object
+                        && alternative.required.is_some()
+                        && alternative.properties.is_empty()
+                        && alternative.pattern_properties.is_empty()
+                        && alternative.additional_properties.is_none();
+
+                    data_types.push(match only_required {
+                        true => {
+                            parse_object_type(alternative_src, alternative, Some(&schema), name)
+                        }
+                        false => parse_type(alternative_src, alternative, Some(&schema), name),
+                    });
                 }
 
-                return DataType::AllOf(AllOf { types: data_types });
+                return DataType::AllOf(AllOf {
+                    src,
+                    name: schema.title.clone().or(property_name),
+                    types: data_types,
+                });
             }
 
             let mut enum_values = match &schema.enum_ {
@@ -191,41 +668,60 @@ fn parse_type(
                 None => vec![],
             };
 
-            match parent_schema {
-                Some(parent) => match &parent.enum_ {
-                    Some(values) => {
-                        for value in values {
-                            enum_values.push(value.clone());
-                        }
+            if let Some(parent) = parent_schema {
+                if let Some(values) = &parent.enum_ {
+                    for value in values {
+                        enum_values.push(value.clone());
                     }
-                    None => {}
-                },
-                None => {}
+                }
             }
 
             match &schema.type_ {
                 Some(type_) => match type_ {
                     Types::Null => DataType::PrimitiveType(PrimitiveType::Null),
                     Types::Boolean => DataType::PrimitiveType(PrimitiveType::Boolean),
-                    Types::Integer => DataType::PrimitiveType(PrimitiveType::Integer),
+                    Types::Integer => DataType::PrimitiveType(parse_integer_type(&schema)),
                     Types::Number => DataType::PrimitiveType(PrimitiveType::Number),
-                    Types::String => DataType::PrimitiveType(PrimitiveType::String),
-                    Types::Array => parse_array_type(src, schema),
-                    Types::Object => match schema.pattern_properties.values().nth(0) {
-                        Some(schema) => DataType::Map(Rc::new(parse_type(
-                            format!("{}/patternProperties", src),
-                            schema.clone(),
-                            None,
-                            None,
-                        ))),
-                        None => {
-                            if schema.properties.len() > 0 {
-                                parse_object_type(src, schema, parent_schema, property_name)
-                            } else {
-                                DataType::Map(Rc::new(DataType::Any))
+                    Types::String => {
+                        match (schema.content_encoding.as_deref(), schema.format.as_deref()) {
+                            (Some("base64"), _) | (_, Some("binary")) | (_, Some("byte")) => {
+                                DataType::PrimitiveType(PrimitiveType::Bytes)
+                            }
+                            (_, Some(format)) if enum_values.is_empty() => {
+                                DataType::PrimitiveType(PrimitiveType::Format(format.to_string()))
+                            }
+                            _ => {
+                                match parse_string_enum(&src, &schema, &enum_values, property_name)
+                                {
+                                    Some(string_enum) => DataType::StringEnum(string_enum),
+                                    None => DataType::PrimitiveType(PrimitiveType::String),
+                                }
                             }
                         }
-                    },
+                    }
+                    Types::Array => parse_array_type(src, schema, property_name),
+                    Types::Object => {
+                        let values = match schema.pattern_properties.iter().next() {
+                            Some(_) if has_distinct_pattern_values(&schema) => {
+                                Rc::new(parse_pattern_values(&src, &schema, &property_name))
+                            }
+                            Some((pattern, schema)) => Rc::new(parse_type(
+                                child_src(&src, &["patternProperties", pattern]),
+                                schema.clone(),
+                                None,
+                                None,
+                            )),
+                            None if !schema.properties.is_empty() => {
+                                return parse_object_type(src, schema, parent_schema, property_name)
+                            }
+                            None => return parse_empty_object(src, &schema, property_name),
+                        };
+
+                        match parse_map_key(&src, &schema, property_name) {
+                            Some(key) => DataType::KeyedMap(KeyedMap { key, values }),
+                            None => DataType::Map(values),
+                        }
+                    }
                 },
                 None => DataType::Any,
             }
@@ -233,12 +729,330 @@ fn parse_type(
     }
 }
 
-fn parse_array_type(src: String, schema: Schema) -> DataType {
+fn has_distinct_pattern_values(schema: &Schema) -> bool {
+    let mut values = schema.pattern_properties.values();
+    let first = values.next();
+
+    values.any(|x| Some(x) != first)
+}
+
+/// The values of a map whose `patternProperties` have different schemas, as an `anyOf`
+/// of the schemas sorted by their patterns
+fn parse_pattern_values(src: &str, schema: &Schema, property_name: &Option<String>) -> DataType {
+    let mut patterns = vec![];
+    let mut types = vec![];
+
+    for (pattern, value_schema) in &schema.pattern_properties {
+        patterns.push(pattern.clone());
+        types.push(parse_type(
+            child_src(src, &["patternProperties", pattern]),
+            value_schema.clone(),
+            None,
+            None,
+        ));
+    }
+
+    DataType::AnyOf(AnyOf {
+        src: child_src(src, &["patternProperties"]),
+        name: property_name
+            .clone()
+            .or_else(|| schema.title.clone())
+            .map(|name| format!("{} value", name)),
+        types,
+        patterns,
+    })
+}
+
+/// The smallest integer type which holds every value between the bounds, `u64` for
+/// integers without negative values and an upper bound, and `i64` for all others
+fn parse_integer_type(schema: &Schema) -> PrimitiveType {
+    let lower = lower_bound(schema);
+    let upper = upper_bound(schema);
+
+    let candidates: &[(&'static str, f64, f64)] = match (lower, upper) {
+        (Some(lower), None) if lower >= 0.0 => return PrimitiveType::SizedInteger("u64"),
+        (Some(lower), Some(_)) if lower >= 0.0 => &[
+            ("u8", 0.0, u8::MAX as f64),
+            ("u16", 0.0, u16::MAX as f64),
+            ("u32", 0.0, u32::MAX as f64),
+            ("u64", 0.0, u64::MAX as f64),
+        ],
+        (Some(_), Some(_)) => &[
+            ("i8", i8::MIN as f64, i8::MAX as f64),
+            ("i16", i16::MIN as f64, i16::MAX as f64),
+            ("i32", i32::MIN as f64, i32::MAX as f64),
+        ],
+        _ => return PrimitiveType::Integer,
+    };
+
+    let (lower, upper) = (lower.unwrap_or_default(), upper.unwrap_or_default());
+
+    candidates
+        .iter()
+        .find(|(_, min, max)| lower >= *min && upper <= *max)
+        .map_or(PrimitiveType::Integer, |(name, _, _)| {
+            PrimitiveType::SizedInteger(name)
+        })
+}
+
+/// The smallest integer the schema allows. Draft 4 makes `minimum` exclusive
+/// with `exclusiveMinimum: true`, later drafts give the exclusive bound itself.
+fn lower_bound(schema: &Schema) -> Option<f64> {
+    match (schema.minimum, &schema.exclusive_minimum) {
+        (Some(minimum), Some(Value::Bool(true))) => Some(minimum.floor() + 1.0),
+        (_, Some(Value::Number(minimum))) => minimum.as_f64().map(|x| x.floor() + 1.0),
+        (minimum, _) => minimum.map(f64::ceil),
+    }
+}
+
+/// The largest integer the schema allows, see `lower_bound`
+fn upper_bound(schema: &Schema) -> Option<f64> {
+    match (schema.maximum, &schema.exclusive_maximum) {
+        (Some(maximum), Some(Value::Bool(true))) => Some(maximum.ceil() - 1.0),
+        (_, Some(Value::Number(maximum))) => maximum.as_f64().map(|x| x.ceil() - 1.0),
+        (maximum, _) => maximum.map(f64::floor),
+    }
+}
+
+/// Branches the normalizer split off a schema, rather than ones of its own `keyword`,
+/// are named like the schema
+fn split_off_name(
+    src: &str,
+    keyword: &str,
+    branch: &Schema,
+    property_name: &Option<String>,
+) -> Option<String> {
+    match &branch.src {
+        Some(branch_src) if !branch_src.starts_with(&child_src(src, &[keyword])) => {
+            property_name.clone()
+        }
+        _ => None,
+    }
+}
+
+fn parse_string_enum(
+    src: &str,
+    schema: &Schema,
+    enum_values: &[Value],
+    property_name: Option<String>,
+) -> Option<StringEnum> {
+    let name = schema.title.clone().or(property_name)?;
+    let mut values: Vec<String> = vec![];
+    let mut descriptions = vec![];
+
+    for (i, value) in enum_values.iter().enumerate() {
+        let value = value.as_str()?.to_string();
+        let description = match &schema.x_enum_descriptions {
+            Some(Value::Array(descriptions)) => descriptions.get(i),
+            Some(Value::Object(descriptions)) => descriptions.get(&value),
+            _ => None,
+        };
+
+        if !values.contains(&value) {
+            descriptions.push(description.and_then(Value::as_str).map(String::from));
+            values.push(value);
+        }
+    }
+
+    match values.is_empty() {
+        true => None,
+        false => Some(StringEnum {
+            src: src.to_string(),
+            name,
+            description: schema.description.clone(),
+            values,
+            descriptions,
+        }),
+    }
+}
+
+/// A `oneOf` or `anyOf` of `const` strings, whose titles and descriptions document the values
+fn parse_const_enum(
+    src: &str,
+    schema: &Schema,
+    property_name: Option<String>,
+) -> Option<StringEnum> {
+    let branches = match schema.one_of.is_empty() {
+        true => &schema.any_of,
+        false => &schema.one_of,
+    };
+    let mut values = vec![];
+    let mut descriptions = vec![];
+
+    for branch in branches {
+        let value = match (&branch.const_, &branch.ref_, &branch.type_) {
+            (Some(Value::String(value)), None, None | Some(Types::String)) => value.clone(),
+            _ => return None,
+        };
+        let description: Vec<&str> = [branch.title.as_deref(), branch.description.as_deref()]
+            .iter()
+            .flatten()
+            .copied()
+            .collect();
+
+        if !values.contains(&value) {
+            values.push(value);
+            descriptions.push(Some(description.join("\n\n")).filter(|x| !x.is_empty()));
+        }
+    }
+
+    match values.is_empty() {
+        true => None,
+        false => Some(StringEnum {
+            src: src.to_string(),
+            name: schema.title.clone().or(property_name)?,
+            description: schema.description.clone(),
+            values,
+            descriptions,
+        }),
+    }
+}
+
+fn parse_empty_object(src: String, schema: &Schema, property_name: Option<String>) -> DataType {
+    let additional_properties = schema.additional_properties.as_ref().as_ref().map(|x| {
+        Rc::new(parse_type(
+            child_src(&src, &["additionalProperties"]),
+            x.clone(),
+            None,
+            None,
+        ))
+    });
+
+    DataType::EmptyObject(EmptyObject {
+        key: parse_map_key(&src, schema, property_name.clone()),
+        name: schema.title.clone().or(property_name),
+        title: schema.title.clone(),
+        src,
+        additional_properties,
+    })
+}
+
+fn parse_map_key(src: &str, schema: &Schema, property_name: Option<String>) -> Option<MapKey> {
+    let property_names = schema.property_names.as_ref().as_ref()?;
+    let pattern = property_names.pattern.clone()?;
+    let name = match (
+        &property_names.title,
+        property_name.or_else(|| schema.title.clone()),
+    ) {
+        (Some(title), _) => title.clone(),
+        (None, Some(name)) => format!("{} key", name),
+        (None, None) => String::from("Key"),
+    };
+
+    Some(MapKey {
+        src: child_src(src, &["propertyNames"]),
+        name,
+        pattern,
+    })
+}
+
+/// Keywords next to `oneOf`, `anyOf` and `allOf` apply to every branch.
+/// The `required` sets of an `anyOf` whose branches only require properties of the
+/// schema, which expresses that at least one of the sets has to be present
+fn parse_required_any(schema: &Schema) -> Option<Vec<Vec<String>>> {
+    if schema.any_of.is_empty() || schema.properties.is_empty() {
+        return None;
+    }
+
+    schema
+        .any_of
+        .iter()
+        .map(|branch| {
+            let only_required = branch.ref_.is_none()
+                && matches!(branch.type_, None | Some(Types::Object))
+                && branch.one_of.is_empty()
+                && branch.any_of.is_empty()
+                && branch.all_of.is_empty()
+                && branch
+                    .properties
+                    .keys()
+                    .all(|x| schema.properties.contains_key(x));
+            let required = branch.required.clone().filter(|required| {
+                !required.is_empty() && required.iter().all(|x| schema.properties.contains_key(x))
+            })?;
+
+            match only_required {
+                true => Some(required),
+                false => None,
+            }
+        })
+        .collect()
+}
+
+fn inherit_parent_keywords(branch: Schema, parent: &Schema) -> Schema {
+    if branch.ref_.is_some() {
+        return branch;
+    }
+
+    let mut branch = branch;
+
+    if branch.type_.is_none() {
+        branch.type_ = parent.type_.clone();
+    }
+
+    if branch.description.is_none() {
+        branch.description = parent.description.clone();
+    }
+
+    for (name, property) in &parent.properties {
+        branch
+            .properties
+            .entry(name.clone())
+            .or_insert_with(|| property.clone());
+    }
+
+    branch
+}
+
+/// The items are named after the singular of the array, like `Event` for `events`
+fn parse_array_type(src: String, schema: Schema, property_name: Option<String>) -> DataType {
+    // Only arrays of exactly the prefix items fit a tuple, which needs `items: false` and
+    // a `minItems` covering them. The prefix items don't fit a `Vec` of the other items either.
+    if !schema.prefix_items.is_empty() {
+        let closed = matches!(
+            &*schema.items,
+            Some(Schema {
+                boolean: Some(false),
+                ..
+            })
+        );
+        let complete = schema.min_items.unwrap_or(0) >= schema.prefix_items.len() as u64;
+
+        if !closed || !complete {
+            return DataType::Array(Rc::new(DataType::Any));
+        }
+
+        let keyword = schema.prefix_items_keyword();
+        let items = (0..)
+            .zip(schema.prefix_items)
+            .map(|(i, item)| {
+                Rc::new(parse_type(
+                    child_src(&src, &[keyword, &i.to_string()]),
+                    item,
+                    None,
+                    property_name
+                        .as_ref()
+                        .map(|x| format!("{} {}", singularize(x), i + 1)),
+                ))
+            })
+            .collect();
+
+        return DataType::Tuple(items);
+    }
+
     match *schema.items {
         Some(items) => {
-            let data_type = parse_type(format!("{}/items", src), items, None, None);
-
-            DataType::Array(Rc::new(data_type))
+            let data_type = parse_type(
+                child_src(&src, &["items"]),
+                items,
+                None,
+                property_name.map(|x| singularize(&x)),
+            );
+
+            match schema.unique_items {
+                Some(true) => DataType::Set(Rc::new(data_type)),
+                _ => DataType::Array(Rc::new(data_type)),
+            }
         }
         None => DataType::Array(Rc::new(DataType::Any)),
     }
@@ -250,58 +1064,65 @@ fn parse_object_type(
     x_of_parent: Option<&Schema>,
     property_name: Option<String>,
 ) -> DataType {
-    let name = match schema.title {
-        Some(title) => title,
-        None => match x_of_parent {
-            Some(parent) => match &parent.title {
-                Some(title) => title.to_string(),
-                None => match &property_name {
-                    Some(title) => title.to_string(),
-                    None => String::from("Unknown"),
-                },
-            },
-            None => match &property_name {
-                Some(title) => title.to_string(),
-                None => String::from("Unknown"),
-            },
-        },
+    let title = match schema.title {
+        Some(title) => Some(title),
+        None => x_of_parent.and_then(|parent| parent.title.clone()),
     };
 
-    let mut required_properties = match schema.required {
-        Some(required) => required,
-        None => vec![],
+    let name = match (&title, &property_name) {
+        (Some(title), _) => title.to_string(),
+        (None, Some(property_name)) => property_name.to_string(),
+        (None, None) => String::from("Unknown"),
     };
 
-    match x_of_parent {
-        Some(parent) => match &parent.required {
-            Some(required) => {
-                for r in required {
-                    required_properties.push(r.to_string());
-                }
+    let mut required_properties = schema.required.unwrap_or_default();
+
+    if let Some(parent) = x_of_parent {
+        if let Some(required) = &parent.required {
+            for r in required {
+                required_properties.push(r.to_string());
             }
-            None => {}
-        },
-        None => {}
+        }
+    }
+
+    let mut unmatched_required: Vec<String> = vec![];
+    let deny_unknown_fields =
+        matches!(&*schema.additional_properties, Some(x) if x.boolean == Some(false));
+
+    for name in &required_properties {
+        if !schema.properties.contains_key(name) && !unmatched_required.contains(name) {
+            unmatched_required.push(name.clone());
+        }
     }
 
     let mut properties: Vec<ObjectProperty> = vec![];
 
     for (name, property) in schema.properties {
         let required = required_properties.contains(&name);
-        let property = parse_property(
-            format!("{}/properties/{}", src, name),
+        let mut property = parse_property(
+            child_src(&src, &["properties", &name]),
             &name,
             property,
             required,
         );
+        property.requires = schema
+            .dependent_required
+            .get(&name)
+            .cloned()
+            .unwrap_or_default();
         properties.push(property);
     }
 
-    return DataType::Object(Object {
+    DataType::Object(Object {
         src,
         name,
+        title,
+        description: schema.description,
         properties,
-    });
+        required_any: vec![],
+        unmatched_required,
+        deny_unknown_fields,
+    })
 }
 
 fn parse_property(src: String, name: &str, schema: Schema, required: bool) -> ObjectProperty {
@@ -310,26 +1131,131 @@ fn parse_property(src: String, name: &str, schema: Schema, required: bool) -> Ob
         None => name.to_string(),
     };
 
+    let constant = match (&schema.const_, &schema.enum_) {
+        (Some(constant), _) => Some(constant.clone()),
+        (None, Some(values)) if values.len() == 1 => Some(values[0].clone()),
+        _ => None,
+    };
+
+    let map_constraints = parse_map_constraints(&schema);
+    let value_constraints = parse_value_constraints(&schema);
+    let aliases = schema.x_aliases.clone().unwrap_or_default();
+    let description = schema.description.clone();
+    let default = schema.default.clone();
+    let schema_read_only = schema.read_only == Some(true);
+    let schema_write_only = schema.write_only == Some(true);
+
     ObjectProperty {
         name: name.to_string(),
         required,
+        constant,
+        default,
         data_type: Rc::new(parse_type(src, schema, None, Some(fallback_name))),
+        description,
+        map_constraints,
+        value_constraints,
+        requires: vec![],
+        aliases,
+        read_only: schema_read_only,
+        write_only: schema_write_only,
+    }
+}
+
+fn parse_map_constraints(schema: &Schema) -> Option<MapConstraints> {
+    if schema.type_ != Some(Types::Object)
+        || !schema.properties.is_empty()
+        || !schema.one_of.is_empty()
+        || !schema.any_of.is_empty()
+        || !schema.all_of.is_empty()
+    {
+        return None;
+    }
+
+    let key_pattern = match (
+        schema
+            .property_names
+            .as_ref()
+            .as_ref()
+            .and_then(|x| x.pattern.as_ref()),
+        schema.pattern_properties.len(),
+    ) {
+        (None, 1) => schema.pattern_properties.keys().next().cloned(),
+        _ => None,
+    };
+
+    let constraints = MapConstraints {
+        min_properties: schema.min_properties,
+        max_properties: schema.max_properties,
+        key_pattern,
+        required_keys: schema.required.clone().unwrap_or_default(),
+    };
+
+    match constraints.min_properties.is_some()
+        || constraints.max_properties.is_some()
+        || constraints.key_pattern.is_some()
+        || !constraints.required_keys.is_empty()
+    {
+        true => Some(constraints),
+        false => None,
+    }
+}
+
+/// Draft 4 makes `minimum` and `maximum` exclusive with a boolean, later drafts give the bound
+fn parse_value_constraints(schema: &Schema) -> Option<ValueConstraints> {
+    let bound = |inclusive: Option<f64>, exclusive: &Option<Value>| match exclusive {
+        Some(Value::Number(bound)) => bound.as_f64().map(|x| (x, true)),
+        Some(Value::Bool(exclusive)) => inclusive.map(|x| (x, *exclusive)),
+        _ => inclusive.map(|x| (x, false)),
+    };
+
+    let constraints = ValueConstraints {
+        pattern: schema.pattern.clone(),
+        min_length: schema.min_length,
+        max_length: schema.max_length,
+        minimum: bound(schema.minimum, &schema.exclusive_minimum),
+        maximum: bound(schema.maximum, &schema.exclusive_maximum),
+        multiple_of: schema.multiple_of.filter(|x| *x > 0.0),
+        min_items: schema.min_items,
+        max_items: schema.max_items,
+        items: schema
+            .items
+            .as_ref()
+            .as_ref()
+            .and_then(parse_value_constraints)
+            .map(Box::new),
+    };
+
+    match constraints.is_empty() {
+        true => None,
+        false => Some(constraints),
     }
 }
 
 #[cfg(test)]
 mod parser_tests {
+    #[cfg(feature = "fs")]
+    use crate::parser::{
+        parse_from_file, AnyOf, KeyedMap, MapConstraints, MapKey, ObjectProperty, OneOf, Overlay,
+        Root,
+    };
     use crate::parser::{
-        parse_from_file, parse_from_string, AllOf, AnyOf, DataType, Object, ObjectProperty, OneOf,
-        PrimitiveType, Root,
+        parse_value_constraints, try_parse_from_string, AllOf, DataType, EmptyObject, Object,
+        PrimitiveType, Ref, ValueConstraints,
     };
+    use crate::schema::Schema;
+    use serde_json::{json, Value};
+    #[cfg(feature = "fs")]
     use std::collections::HashMap;
     use std::path::Path;
     use std::rc::Rc;
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_parse_null() {
-        let schema = parse_from_file(Path::new("src/examples/parser/null.schema.json"));
+        let schema = parse_from_file(
+            Path::new("src/examples/parser/null.schema.json"),
+            &Overlay::new(),
+        );
 
         assert_eq!(
             &schema.data_type as &DataType,
@@ -337,9 +1263,13 @@ mod parser_tests {
         );
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_parse_boolean() {
-        let schema = parse_from_file(Path::new("src/examples/parser/boolean.schema.json"));
+        let schema = parse_from_file(
+            Path::new("src/examples/parser/boolean.schema.json"),
+            &Overlay::new(),
+        );
 
         assert_eq!(
             &schema.data_type as &DataType,
@@ -347,9 +1277,13 @@ mod parser_tests {
         );
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_parse_integer() {
-        let schema = parse_from_file(Path::new("src/examples/parser/integer.schema.json"));
+        let schema = parse_from_file(
+            Path::new("src/examples/parser/integer.schema.json"),
+            &Overlay::new(),
+        );
 
         assert_eq!(
             &schema.data_type as &DataType,
@@ -357,9 +1291,149 @@ mod parser_tests {
         );
     }
 
+    #[test]
+    fn should_select_integer_types_from_bounds() {
+        let schemas = [
+            (r#"{"type": "integer", "minimum": 0}"#, "u64"),
+            (
+                r#"{"type": "integer", "minimum": 1, "maximum": 65535}"#,
+                "u16",
+            ),
+            (
+                r#"{"type": "integer", "exclusiveMinimum": -1, "maximum": 255}"#,
+                "u8",
+            ),
+            (
+                r#"{"type": "integer", "minimum": -1, "maximum": 256}"#,
+                "i16",
+            ),
+            (
+                r#"{"type": "integer", "minimum": -1e10, "maximum": 0}"#,
+                "i64",
+            ),
+            (r#"{"type": "integer", "maximum": 10}"#, "i64"),
+        ];
+
+        for (schema, type_name) in schemas {
+            let schema = try_parse_from_string(Path::new("integer.json"), schema).unwrap();
+            let expected = match type_name {
+                "i64" => PrimitiveType::Integer,
+                type_name => PrimitiveType::SizedInteger(type_name),
+            };
+
+            assert_eq!(&schema.data_type as &DataType, &primitive_type(expected));
+        }
+    }
+
+    #[test]
+    fn should_read_exclusive_bounds_of_draft_4_as_booleans() {
+        let schema = try_parse_from_string(
+            Path::new("integer.json"),
+            r#"{"type": "integer", "minimum": -1, "exclusiveMinimum": true, "maximum": 256, "exclusiveMaximum": true}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            &schema.data_type as &DataType,
+            &primitive_type(PrimitiveType::SizedInteger("u8"))
+        );
+    }
+
+    #[test]
+    fn should_parse_items_arrays_as_tuples() {
+        let schema = try_parse_from_string(
+            Path::new("pair.json"),
+            r#"{"type": "array", "items": [{"type": "string"}, {"type": "boolean"}],
+                "additionalItems": false, "minItems": 2}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            &schema.data_type as &DataType,
+            &DataType::Tuple(vec![
+                Rc::new(primitive_type(PrimitiveType::String)),
+                Rc::new(primitive_type(PrimitiveType::Boolean)),
+            ])
+        );
+        assert_eq!(
+            schema.parse_pointer(&[String::from("items"), String::from("1")]),
+            Ok(primitive_type(PrimitiveType::Boolean))
+        );
+    }
+
+    #[test]
+    fn should_parse_open_or_shorter_items_arrays_as_vecs() {
+        for schema in [
+            r#"{"type": "array", "items": [{"type": "string"}], "minItems": 1}"#,
+            r#"{"type": "array", "items": [{"type": "string"}], "additionalItems": {"type": "integer"}, "minItems": 1}"#,
+            r#"{"type": "array", "prefixItems": [{"type": "string"}, {"type": "boolean"}], "items": false, "minItems": 1}"#,
+        ] {
+            let root = try_parse_from_string(Path::new("pair.json"), schema).unwrap();
+
+            assert_eq!(
+                &root.data_type as &DataType,
+                &DataType::Array(Rc::new(DataType::Any))
+            );
+        }
+    }
+
+    #[test]
+    fn should_read_the_descriptions_of_enum_values() {
+        let described = |schema: &str| {
+            let root = try_parse_from_string(Path::new("level.json"), schema).unwrap();
+
+            match &*root.data_type {
+                DataType::StringEnum(string_enum) => string_enum.clone(),
+                data_type => panic!("{:?} is no string enum", data_type),
+            }
+        };
+
+        let string_enum = described(
+            r#"{"title": "Level", "type": "string", "enum": ["debug", "info", "debug"],
+                "x-enumDescriptions": ["Everything", "Only news", "Everything again"]}"#,
+        );
+
+        assert_eq!(string_enum.values, vec!["debug", "info"]);
+        assert_eq!(
+            string_enum.descriptions,
+            vec![
+                Some(String::from("Everything")),
+                Some(String::from("Only news"))
+            ]
+        );
+
+        let string_enum = described(
+            r#"{"title": "Level", "type": "string", "enum": ["debug", "info"],
+                "x-enumDescriptions": {"info": "Only news"}}"#,
+        );
+
+        assert_eq!(
+            string_enum.descriptions,
+            vec![None, Some(String::from("Only news"))]
+        );
+
+        let string_enum = described(
+            r#"{"title": "Level", "description": "How much to log", "oneOf": [
+                {"const": "debug", "title": "Debug", "description": "Everything"},
+                {"const": "info"}
+            ]}"#,
+        );
+
+        assert_eq!(string_enum.description.as_deref(), Some("How much to log"));
+        assert_eq!(string_enum.values, vec!["debug", "info"]);
+        assert_eq!(
+            string_enum.descriptions,
+            vec![Some(String::from("Debug\n\nEverything")), None]
+        );
+    }
+
+    #[cfg(feature = "fs")]
     #[test]
     fn should_parse_number() {
-        let schema = parse_from_file(Path::new("src/examples/parser/number.schema.json"));
+        let schema = parse_from_file(
+            Path::new("src/examples/parser/number.schema.json"),
+            &Overlay::new(),
+        );
 
         assert_eq!(
             &schema.data_type as &DataType,
@@ -367,9 +1441,13 @@ mod parser_tests {
         );
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_parse_string() {
-        let schema = parse_from_file(Path::new("src/examples/parser/string.schema.json"));
+        let schema = parse_from_file(
+            Path::new("src/examples/parser/string.schema.json"),
+            &Overlay::new(),
+        );
 
         assert_eq!(
             &schema.data_type as &DataType,
@@ -377,9 +1455,13 @@ mod parser_tests {
         );
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_parse_array() {
-        let schema = parse_from_file(Path::new("src/examples/parser/array.schema.json"));
+        let schema = parse_from_file(
+            Path::new("src/examples/parser/array.schema.json"),
+            &Overlay::new(),
+        );
 
         assert_eq!(
             &schema.data_type as &DataType,
@@ -387,9 +1469,13 @@ mod parser_tests {
         );
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_parse_nested_array() {
-        let schema = parse_from_file(Path::new("src/examples/parser/array.nested.schema.json"));
+        let schema = parse_from_file(
+            Path::new("src/examples/parser/array.nested.schema.json"),
+            &Overlay::new(),
+        );
 
         assert_eq!(
             &schema.data_type as &DataType,
@@ -397,14 +1483,18 @@ mod parser_tests {
         );
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_parse_object_in_array() {
-        let schema = parse_from_file(Path::new("src/examples/parser/array.object.schema.json"));
+        let schema = parse_from_file(
+            Path::new("src/examples/parser/array.object.schema.json"),
+            &Overlay::new(),
+        );
 
         assert_eq!(
             &schema.data_type as &DataType,
             &array_type(object_type(
-                String::from("src/examples/parser/array.object.schema.json/items"),
+                String::from("src/examples/parser/array.object.schema.json#/items"),
                 vec![property(
                     String::from("subProperty"),
                     primitive_type(PrimitiveType::String),
@@ -413,9 +1503,13 @@ mod parser_tests {
         );
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_parse_object() {
-        let schema = parse_from_file(Path::new("src/examples/parser/object.schema.json"));
+        let schema = parse_from_file(
+            Path::new("src/examples/parser/object.schema.json"),
+            &Overlay::new(),
+        );
 
         assert_eq!(
             &schema.data_type as &DataType,
@@ -429,11 +1523,13 @@ mod parser_tests {
         );
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_parse_pattern_properties_to_map() {
-        let schema = parse_from_file(Path::new(
-            "src/examples/parser/object.pattern.properties.schema.json",
-        ));
+        let schema = parse_from_file(
+            Path::new("src/examples/parser/object.pattern.properties.schema.json"),
+            &Overlay::new(),
+        );
 
         assert_eq!(
             &schema.data_type as &DataType,
@@ -441,28 +1537,173 @@ mod parser_tests {
         );
     }
 
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_parse_property_names_pattern_to_map_key() {
+        let file = "src/examples/parser/map.key.schema.json";
+        let schema = parse_from_file(Path::new(file), &Overlay::new());
+
+        assert_eq!(
+            &schema.data_type as &DataType,
+            &DataType::KeyedMap(KeyedMap {
+                key: MapKey {
+                    src: format!("{}#/propertyNames", file),
+                    name: String::from("Ports key"),
+                    pattern: String::from("^[a-z]+$"),
+                },
+                values: Rc::new(primitive_type(PrimitiveType::Integer)),
+            })
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_parse_map_constraints_of_properties() {
+        let file = "src/examples/parser/map.constraints.schema.json";
+        let schema = parse_from_file(Path::new(file), &Overlay::new());
+
+        let properties = match &*schema.data_type {
+            DataType::Object(object) => &object.properties,
+            data_type => panic!("Expected an object, found {:?}", data_type),
+        };
+
+        assert_eq!(
+            properties[0].map_constraints,
+            Some(MapConstraints {
+                min_properties: Some(1),
+                max_properties: Some(16),
+                key_pattern: Some(String::from("^x-")),
+                required_keys: vec![String::from("x-request-id")],
+            })
+        );
+        assert_eq!(
+            properties[1].map_constraints,
+            Some(MapConstraints {
+                min_properties: None,
+                max_properties: Some(4),
+                key_pattern: None,
+                required_keys: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn should_parse_exclusive_bounds_of_both_drafts() {
+        let draft_4: Schema = serde_json::from_str(
+            r#"{"type": "number", "minimum": 1, "exclusiveMinimum": true, "maximum": 9}"#,
+        )
+        .unwrap();
+        let draft_6: Schema =
+            serde_json::from_str(r#"{"type": "number", "exclusiveMaximum": 9}"#).unwrap();
+
+        assert_eq!(
+            parse_value_constraints(&draft_4),
+            Some(ValueConstraints {
+                minimum: Some((1.0, true)),
+                maximum: Some((9.0, false)),
+                ..ValueConstraints::default()
+            })
+        );
+        assert_eq!(
+            parse_value_constraints(&draft_6),
+            Some(ValueConstraints {
+                maximum: Some((9.0, true)),
+                ..ValueConstraints::default()
+            })
+        );
+    }
+
+    #[test]
+    fn should_parse_the_constraints_of_items() {
+        let schema: Schema = serde_json::from_str(
+            r#"{"type": "array", "maxItems": 3, "items": {"type": "string", "pattern": "^a"}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            parse_value_constraints(&schema),
+            Some(ValueConstraints {
+                max_items: Some(3),
+                items: Some(Box::new(ValueConstraints {
+                    pattern: Some(String::from("^a")),
+                    ..ValueConstraints::default()
+                })),
+                ..ValueConstraints::default()
+            })
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_keep_required_names_without_property() {
+        let file = "src/examples/generator/required.unmatched.schema.json";
+        let schema = parse_from_file(Path::new(file), &Overlay::new());
+
+        match &*schema.data_type {
+            DataType::Object(object) => {
+                assert!(object.properties[0].required);
+                assert_eq!(object.unmatched_required, vec![String::from("issuer")]);
+            }
+            data_type => panic!("Expected an object, found {:?}", data_type),
+        }
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_parse_any_of_required_sets_as_one_object() {
+        let file = "src/examples/parser/required.any.schema.json";
+        let schema = parse_from_file(Path::new(file), &Overlay::new());
+
+        let object = match &*schema.data_type {
+            DataType::Object(object) => object,
+            data_type => panic!("Expected an object, found {:?}", data_type),
+        };
+
+        assert_eq!(object.name, "Contact");
+        assert!(object.properties.iter().all(|x| !x.required));
+        assert_eq!(
+            object.required_any,
+            vec![
+                vec![String::from("email")],
+                vec![String::from("phone")],
+                vec![String::from("name"), String::from("postalAddress")],
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs")]
     #[test]
     fn should_use_title() {
-        let schema = parse_from_file(Path::new("src/examples/parser/object.title.schema.json"));
+        let schema = parse_from_file(
+            Path::new("src/examples/parser/object.title.schema.json"),
+            &Overlay::new(),
+        );
 
         assert_eq!(
             &schema.data_type as &DataType,
             &DataType::Object(Object {
                 src: String::from("src/examples/parser/object.title.schema.json"),
                 name: String::from("Some object"),
+                title: Some(String::from("Some object")),
+                description: None,
                 properties: vec![property(
                     String::from("property"),
                     primitive_type(PrimitiveType::String),
                 )],
+                required_any: vec![],
+                unmatched_required: vec![],
+                deny_unknown_fields: false,
             })
         );
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_use_property_name_as_fallback() {
-        let schema = parse_from_file(Path::new(
-            "src/examples/parser/object.nested.property.name.fallback.schema.json",
-        ));
+        let schema = parse_from_file(
+            Path::new("src/examples/parser/object.nested.property.name.fallback.schema.json"),
+            &Overlay::new(),
+        );
 
         assert_eq!(
             &schema.data_type as &DataType,
@@ -471,21 +1712,30 @@ mod parser_tests {
                 vec![property(
                     String::from("someProperty"),
                     DataType::Object(Object {
-                        src: String::from("src/examples/parser/object.nested.property.name.fallback.schema.json/properties/someProperty"),
+                        src: String::from("src/examples/parser/object.nested.property.name.fallback.schema.json#/properties/someProperty"),
                         name: String::from("someProperty"),
+                        title: None,
+                        description: None,
                         properties: vec![property(
                             String::from("property"),
                             primitive_type(PrimitiveType::String),
                         )],
+                        required_any: vec![],
+                        unmatched_required: vec![],
+                        deny_unknown_fields: false,
                     }),
                 )],
             )
         );
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_make_property_required() {
-        let schema = parse_from_file(Path::new("src/examples/parser/object.required.schema.json"));
+        let schema = parse_from_file(
+            Path::new("src/examples/parser/object.required.schema.json"),
+            &Overlay::new(),
+        );
 
         assert_eq!(
             &schema.data_type as &DataType,
@@ -494,30 +1744,48 @@ mod parser_tests {
                 vec![ObjectProperty {
                     name: String::from("property"),
                     required: true,
+                    constant: None,
+                    default: None,
                     data_type: Rc::new(primitive_type(PrimitiveType::String)),
+                    description: None,
+                    map_constraints: None,
+                    aliases: vec![],
+                    read_only: false,
+                    write_only: false,
+                    value_constraints: None,
+                    requires: vec![],
                 }],
             )
         );
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_read_defs() {
-        let root = parse_from_file(Path::new("src/examples/parser/defs.schema.json"));
+        let root = parse_from_file(
+            Path::new("src/examples/parser/defs.schema.json"),
+            &Overlay::new(),
+        );
         check_defs(
-            "src/examples/parser/defs.schema.json/$defs/referenced",
+            "src/examples/parser/defs.schema.json#/$defs/referenced",
             root,
         );
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_read_definitions() {
-        let root = parse_from_file(Path::new("src/examples/parser/definitions.schema.json"));
+        let root = parse_from_file(
+            Path::new("src/examples/parser/definitions.schema.json"),
+            &Overlay::new(),
+        );
         check_defs(
-            "src/examples/parser/definitions.schema.json/definitions/referenced",
+            "src/examples/parser/definitions.schema.json#/definitions/referenced",
             root,
         );
     }
 
+    #[cfg(feature = "fs")]
     fn check_defs(src: &str, root: Root) {
         let mut definitions = HashMap::new();
 
@@ -526,52 +1794,104 @@ mod parser_tests {
             Rc::new(DataType::Object(Object {
                 src: String::from(src),
                 name: String::from("referenced"),
+                title: None,
+                description: None,
                 properties: vec![property(
                     String::from("property"),
                     primitive_type(PrimitiveType::String),
                 )],
+                required_any: vec![],
+                unmatched_required: vec![],
+                deny_unknown_fields: false,
             })),
         );
 
         assert_eq!(root.definitions, definitions);
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_parse_one_of() {
-        let schema = parse_from_file(Path::new("src/examples/parser/oneof.schema.json"));
+        let schema = parse_from_file(
+            Path::new("src/examples/parser/oneof.schema.json"),
+            &Overlay::new(),
+        );
+
+        assert_eq!(
+            &schema.data_type as &DataType,
+            &one_of_type(
+                "src/examples/parser/oneof.schema.json",
+                None,
+                generate_types(String::from("src/examples/parser/oneof.schema.json#/oneOf"))
+            )
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_parse_type_arrays_as_any_of() {
+        let file = "src/examples/parser/type.union.schema.json";
+        let schema = parse_from_file(Path::new(file), &Overlay::new());
+
+        assert_eq!(
+            &schema.data_type as &DataType,
+            &any_of_type(
+                file,
+                Some("Id"),
+                vec![
+                    primitive_type(PrimitiveType::String),
+                    primitive_type(PrimitiveType::Integer),
+                    primitive_type(PrimitiveType::Null),
+                ]
+            )
+        );
+
+        let schema =
+            try_parse_from_string(Path::new("single.json"), r#"{"type": ["boolean"]}"#).unwrap();
 
         assert_eq!(
             &schema.data_type as &DataType,
-            &one_of_type(generate_types(String::from(
-                "src/examples/parser/oneof.schema.json/oneOf"
-            )))
+            &primitive_type(PrimitiveType::Boolean)
         );
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_parse_any_of() {
-        let schema = parse_from_file(Path::new("src/examples/parser/anyof.schema.json"));
+        let schema = parse_from_file(
+            Path::new("src/examples/parser/anyof.schema.json"),
+            &Overlay::new(),
+        );
 
         assert_eq!(
             &schema.data_type as &DataType,
-            &any_of_type(generate_types(String::from(
-                "src/examples/parser/anyof.schema.json/anyOf"
-            )))
+            &any_of_type(
+                "src/examples/parser/anyof.schema.json",
+                None,
+                generate_types(String::from("src/examples/parser/anyof.schema.json#/anyOf"))
+            )
         );
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_parse_all_of() {
-        let schema = parse_from_file(Path::new("src/examples/parser/allof.schema.json"));
+        let schema = parse_from_file(
+            Path::new("src/examples/parser/allof.schema.json"),
+            &Overlay::new(),
+        );
 
         assert_eq!(
             &schema.data_type as &DataType,
-            &all_of_type(generate_types(String::from(
-                "src/examples/parser/allof.schema.json/allOf"
-            )))
+            &all_of_type(
+                "src/examples/parser/allof.schema.json",
+                None,
+                generate_types(String::from("src/examples/parser/allof.schema.json#/allOf"))
+            )
         );
     }
 
+    #[cfg(feature = "fs")]
     fn generate_types(src: String) -> Vec<DataType> {
         vec![
             object_type(
@@ -591,28 +1911,134 @@ mod parser_tests {
         ]
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_inherit_root_properties() {
-        let schema = parse_from_file(Path::new(
-            "src/examples/parser/oneof.inherit.properties.schema.json",
-        ));
+        let schema = parse_from_file(
+            Path::new("src/examples/parser/oneof.inherit.properties.schema.json"),
+            &Overlay::new(),
+        );
 
         assert_eq!(
             &schema.data_type as &DataType,
-            &one_of_type(vec![
-                DataType::Object(Object {
-                    src: String::from(
-                        "src/examples/parser/oneof.inherit.properties.schema.json/oneOf/0"
-                    ),
-                    name: String::from("Root title"),
-                    properties: vec![ObjectProperty {
-                        name: String::from("property"),
-                        required: true,
-                        data_type: Rc::new(primitive_type(PrimitiveType::String)),
-                    }],
-                }),
-                DataType::PrimitiveType(PrimitiveType::String)
-            ])
+            &one_of_type(
+                "src/examples/parser/oneof.inherit.properties.schema.json",
+                Some("Root title"),
+                vec![
+                    DataType::Object(Object {
+                        src: String::from(
+                            "src/examples/parser/oneof.inherit.properties.schema.json#/oneOf/0"
+                        ),
+                        name: String::from("Root title"),
+                        title: Some(String::from("Root title")),
+                        description: None,
+                        properties: vec![ObjectProperty {
+                            name: String::from("property"),
+                            required: true,
+                            constant: None,
+                            default: None,
+                            data_type: Rc::new(primitive_type(PrimitiveType::String)),
+                            description: None,
+                            map_constraints: None,
+                            aliases: vec![],
+                            read_only: false,
+                            write_only: false,
+                            value_constraints: None,
+                            requires: vec![],
+                        }],
+                        required_any: vec![],
+                        unmatched_required: vec![],
+                        deny_unknown_fields: false,
+                    }),
+                    DataType::PrimitiveType(PrimitiveType::String)
+                ]
+            )
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_inherit_parent_type_and_properties() {
+        let schema = parse_from_file(
+            Path::new("src/examples/parser/oneof.inherit.type.schema.json"),
+            &Overlay::new(),
+        );
+
+        let src = "src/examples/parser/oneof.inherit.type.schema.json#/oneOf";
+
+        assert_eq!(
+            &schema.data_type as &DataType,
+            &one_of_type(
+                "src/examples/parser/oneof.inherit.type.schema.json",
+                None,
+                vec![
+                    DataType::Object(Object {
+                        src: format!("{}/0", src),
+                        name: String::from("Unknown"),
+                        title: None,
+                        description: Some(String::from("Either a or b")),
+                        properties: vec![
+                            property(String::from("a"), primitive_type(PrimitiveType::String)),
+                            ObjectProperty {
+                                name: String::from("id"),
+                                required: true,
+                                constant: None,
+                                default: None,
+                                data_type: Rc::new(primitive_type(PrimitiveType::Integer)),
+                                description: None,
+                                map_constraints: None,
+                                aliases: vec![],
+                                read_only: false,
+                                write_only: false,
+                                value_constraints: None,
+                                requires: vec![],
+                            },
+                        ],
+                        required_any: vec![],
+                        unmatched_required: vec![],
+                        deny_unknown_fields: false,
+                    }),
+                    DataType::Object(Object {
+                        src: format!("{}/1", src),
+                        name: String::from("Unknown"),
+                        title: None,
+                        description: Some(String::from("Either a or b")),
+                        properties: vec![
+                            ObjectProperty {
+                                name: String::from("b"),
+                                required: true,
+                                constant: None,
+                                default: None,
+                                data_type: Rc::new(primitive_type(PrimitiveType::String)),
+                                description: None,
+                                map_constraints: None,
+                                aliases: vec![],
+                                read_only: false,
+                                write_only: false,
+                                value_constraints: None,
+                                requires: vec![],
+                            },
+                            ObjectProperty {
+                                name: String::from("id"),
+                                required: true,
+                                constant: None,
+                                default: None,
+                                data_type: Rc::new(primitive_type(PrimitiveType::Integer)),
+                                description: None,
+                                map_constraints: None,
+                                aliases: vec![],
+                                read_only: false,
+                                write_only: false,
+                                value_constraints: None,
+                                requires: vec![],
+                            },
+                        ],
+                        required_any: vec![],
+                        unmatched_required: vec![],
+                        deny_unknown_fields: false,
+                    }),
+                ]
+            )
         );
     }
 
@@ -620,58 +2046,262 @@ mod parser_tests {
         DataType::PrimitiveType(primitive_type)
     }
 
+    #[cfg(feature = "fs")]
     fn object_type(src: String, properties: Vec<ObjectProperty>) -> DataType {
         DataType::Object(Object {
             src,
             name: String::from("Unknown"),
+            title: None,
+            description: None,
             properties,
+            required_any: vec![],
+            unmatched_required: vec![],
+            deny_unknown_fields: false,
         })
     }
 
+    #[cfg(feature = "fs")]
     fn property(name: String, data_type: DataType) -> ObjectProperty {
         ObjectProperty {
             name,
             required: false,
+            constant: None,
+            default: None,
             data_type: Rc::new(data_type),
+            description: None,
+            map_constraints: None,
+            aliases: vec![],
+            read_only: false,
+            write_only: false,
+            value_constraints: None,
+            requires: vec![],
         }
     }
 
+    #[cfg(feature = "fs")]
     fn array_type(nested_type: DataType) -> DataType {
         DataType::Array(Rc::new(nested_type))
     }
 
-    fn one_of_type(types: Vec<DataType>) -> DataType {
-        DataType::OneOf(OneOf { types })
+    #[cfg(feature = "fs")]
+    fn one_of_type(src: &str, name: Option<&str>, types: Vec<DataType>) -> DataType {
+        DataType::OneOf(OneOf {
+            src: String::from(src),
+            name: name.map(String::from),
+            types,
+        })
     }
 
-    fn any_of_type(types: Vec<DataType>) -> DataType {
-        DataType::AnyOf(AnyOf { types })
+    #[cfg(feature = "fs")]
+    fn any_of_type(src: &str, name: Option<&str>, types: Vec<DataType>) -> DataType {
+        DataType::AnyOf(AnyOf {
+            src: String::from(src),
+            name: name.map(String::from),
+            types,
+            patterns: vec![],
+        })
     }
 
-    fn all_of_type(types: Vec<DataType>) -> DataType {
-        DataType::AllOf(AllOf { types })
+    #[cfg(feature = "fs")]
+    fn all_of_type(src: &str, name: Option<&str>, types: Vec<DataType>) -> DataType {
+        DataType::AllOf(AllOf {
+            src: String::from(src),
+            name: name.map(String::from),
+            types,
+        })
     }
 
     #[test]
-    fn should_fallback_to_map_for_empty_objects() {
-        let schema = parse_from_string(Path::new(""), "{\"type\": \"object\"}");
+    fn should_parse_objects_without_properties_as_empty_objects() {
+        let schema = try_parse_from_string(Path::new(""), "{\"type\": \"object\"}").unwrap();
 
         assert_eq!(
             &schema.data_type as &DataType,
-            &DataType::Map(Rc::new(DataType::Any))
+            &DataType::EmptyObject(EmptyObject {
+                src: String::new(),
+                name: None,
+                title: None,
+                key: None,
+                additional_properties: None,
+            })
+        );
+
+        let schema = try_parse_from_string(
+            Path::new(""),
+            r#"{"title": "Counts", "type": "object", "additionalProperties": {"type": "integer"}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            &schema.data_type as &DataType,
+            &DataType::EmptyObject(EmptyObject {
+                src: String::new(),
+                name: Some(String::from("Counts")),
+                title: Some(String::from("Counts")),
+                key: None,
+                additional_properties: Some(Rc::new(primitive_type(PrimitiveType::Integer))),
+            })
+        );
+    }
+
+    #[test]
+    fn should_cite_the_location_of_syntax_errors() {
+        let error = try_parse_from_string(Path::new("broken.json"), "{\n  \"type\": object\n}")
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Could not parse broken.json:2:11: expected value"
+        );
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_keep_keywords_next_to_refs() {
+        let root = parse_from_file(
+            Path::new("src/examples/parser/ref.siblings.schema.json"),
+            &Overlay::new(),
+        );
+
+        let items = match &*root.data_type {
+            DataType::Object(Object { properties, .. }) => properties[0].data_type.clone(),
+            data_type => panic!("Expected an object but got {:?}", data_type),
+        };
+
+        assert_eq!(
+            &*items,
+            &DataType::Array(Rc::new(DataType::AnyOf(AnyOf {
+                src: String::from(
+                    "src/examples/parser/ref.siblings.schema.json#/properties/items/items"
+                ),
+                name: Some(String::from("item")),
+                types: vec![
+                    DataType::Ref(Ref {
+                        src: String::from(
+                            "src/examples/parser/ref.siblings.schema.json#/properties/items/items"
+                        ),
+                        ref_path: String::from("#/definitions/item"),
+                        description: Some(String::from("An item of the list")),
+                        default: Some(json!({})),
+                    }),
+                    DataType::PrimitiveType(PrimitiveType::Null),
+                ],
+                patterns: vec![],
+            })))
+        );
+    }
+
+    #[test]
+    fn should_keep_names_only_required_by_all_of_branches() {
+        let root = try_parse_from_string(
+            Path::new("required.json"),
+            r#"{"allOf": [{"type": "object", "properties": {"since": {"type": "string"}}}, {"required": ["since"]}]}"#,
+        )
+        .unwrap();
+
+        let types = match &*root.data_type {
+            DataType::AllOf(AllOf { types, .. }) => types,
+            data_type => panic!("Expected allOf, got {:?}", data_type),
+        };
+
+        assert_eq!(
+            types[1],
+            DataType::Object(Object {
+                src: String::from("required.json#/allOf/1"),
+                name: String::from("Unknown"),
+                title: None,
+                description: None,
+                properties: vec![],
+                required_any: vec![],
+                unmatched_required: vec![String::from("since")],
+                deny_unknown_fields: false,
+            })
+        );
+    }
+
+    #[test]
+    fn should_parse_required_next_to_refs_as_all_of() {
+        let root = try_parse_from_string(
+            Path::new("refinement.json"),
+            r##"{"$ref": "#/definitions/base", "required": ["id"]}"##,
+        )
+        .unwrap();
+
+        assert_eq!(
+            &*root.data_type,
+            &DataType::AllOf(AllOf {
+                src: String::from("refinement.json"),
+                name: None,
+                types: vec![
+                    DataType::Ref(Ref {
+                        src: String::from("refinement.json"),
+                        ref_path: String::from("#/definitions/base"),
+                        description: None,
+                        default: None,
+                    }),
+                    DataType::Object(Object {
+                        src: String::from("refinement.json"),
+                        name: String::from("Unknown"),
+                        title: None,
+                        description: None,
+                        properties: vec![],
+                        required_any: vec![],
+                        unmatched_required: vec![String::from("id")],
+                        deny_unknown_fields: false,
+                    }),
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn should_read_constants() {
+        let schema = try_parse_from_string(
+            Path::new(""),
+            "{\"type\": \"object\", \"properties\": {\"a\": {\"const\": 2}, \"b\": {\"enum\": [\"x\"]}, \"c\": {\"enum\": [\"x\", \"y\"]}}}",
+        ).unwrap();
+
+        match &*schema.data_type {
+            DataType::Object(object) => assert_eq!(
+                object
+                    .properties
+                    .iter()
+                    .map(|x| x.constant.clone())
+                    .collect::<Vec<Option<Value>>>(),
+                vec![Some(json!(2)), Some(json!("x")), None]
+            ),
+            data_type => panic!("Expected object but got {:?}", data_type),
+        }
+    }
+
+    #[test]
+    fn should_parse_boolean_schemas_as_any() {
+        let schema = try_parse_from_string(
+            Path::new(""),
+            "{\"type\": \"array\", \"items\": true, \"definitions\": {\"nothing\": false}}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            &schema.data_type as &DataType,
+            &DataType::Array(Rc::new(DataType::Any))
+        );
+        assert_eq!(
+            schema.definitions.get("nothing"),
+            Some(&Rc::new(DataType::Any))
         );
     }
 
     #[test]
     fn should_fallback_to_any() {
-        let schema = parse_from_string(Path::new(""), "{}");
+        let schema = try_parse_from_string(Path::new(""), "{}").unwrap();
 
         assert_eq!(&schema.data_type as &DataType, &DataType::Any);
     }
 
     #[test]
     fn should_fallback_to_any_if_items_is_missing() {
-        let schema = parse_from_string(Path::new(""), "{\"type\": \"array\"}");
+        let schema = try_parse_from_string(Path::new(""), "{\"type\": \"array\"}").unwrap();
 
         assert_eq!(
             &schema.data_type as &DataType,
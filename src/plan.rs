@@ -0,0 +1,173 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use crate::generated::{GeneratedType, TypeKind};
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// The types a [`crate::Generator`] would emit, without the code.
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct Plan {
+    pub types: Vec<PlannedType>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct PlannedType {
+    pub name: String,
+    pub src: String,
+    /// What the type is emitted as, like `struct`, `alias` or `untagged_enum`
+    pub kind: String,
+    /// The type an alias or newtype stands for
+    pub target: Option<String>,
+    /// Names of the generated types this type refers to
+    pub references: Vec<String>,
+    pub fields: Vec<PlannedField>,
+}
+
+/// A field of a struct, with the key it has in JSON documents
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct PlannedField {
+    pub name: String,
+    pub json_name: String,
+    pub rust_type: String,
+    /// Whether documents without the field are rejected
+    pub required: bool,
+}
+
+impl Plan {
+    /// The plan as pretty-printed JSON, whose keys follow the declaration order of the fields
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+impl From<&[GeneratedType]> for Plan {
+    fn from(types: &[GeneratedType]) -> Self {
+        let names: HashSet<&str> = types.iter().map(|x| x.name.as_str()).collect();
+
+        let types = types
+            .iter()
+            .map(|generated_type| {
+                let mut references: Vec<String> = Vec::new();
+
+                for part in generated_type.referenced_names() {
+                    if names.contains(part) && !references.iter().any(|x| x == part) {
+                        references.push(part.to_string());
+                    }
+                }
+
+                PlannedType {
+                    name: generated_type.name.clone(),
+                    src: generated_type.src.clone(),
+                    kind: String::from(kind_name(&generated_type.kind)),
+                    target: match &generated_type.kind {
+                        TypeKind::Alias(target)
+                        | TypeKind::Newtype(target)
+                        | TypeKind::Constrained {
+                            value_type: target, ..
+                        } => Some(target.clone()),
+                        _ => None,
+                    },
+                    references,
+                    fields: generated_type
+                        .properties
+                        .iter()
+                        .map(|property| PlannedField {
+                            name: property.name.clone(),
+                            json_name: property
+                                .serde_options
+                                .rename
+                                .clone()
+                                .unwrap_or_else(|| property.name.clone()),
+                            rust_type: property.property_type.clone(),
+                            required: property.serde_options.skip_serializing_if.is_none()
+                                && property.serde_options.default.is_none(),
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        Plan { types }
+    }
+}
+
+fn kind_name(kind: &TypeKind) -> &'static str {
+    match kind {
+        TypeKind::Struct => "struct",
+        TypeKind::Alias(_) => "alias",
+        TypeKind::Newtype(_) => "newtype",
+        TypeKind::Wrapper => "wrapper",
+        TypeKind::UntaggedEnum(_) => "untagged_enum",
+        TypeKind::TaggedEnum { .. } => "tagged_enum",
+        TypeKind::MapKey(_) => "map_key",
+        TypeKind::Null => "null",
+        TypeKind::Map { .. } => "map",
+        TypeKind::StringEnum(_) => "string_enum",
+        TypeKind::Constrained { .. } => "constrained",
+    }
+}
+
+#[cfg(test)]
+mod plan_tests {
+    use crate::generated::{GeneratedProperty, GeneratedType, SerdeOptions, TypeKind};
+    use crate::plan::Plan;
+
+    #[test]
+    fn should_list_each_reference_once() {
+        let types = vec![
+            create_type(
+                "Node",
+                vec!["Option<Box<Node>>", "Vec<Node>", "BTreeMap<String, Leaf>"],
+            ),
+            create_type("Leaf", vec!["String"]),
+        ];
+
+        let plan = Plan::from(types.as_slice());
+
+        assert_eq!(plan.types[0].references, vec!["Node", "Leaf"]);
+        assert!(plan.types[1].references.is_empty());
+    }
+
+    fn create_type(name: &str, property_types: Vec<&str>) -> GeneratedType {
+        GeneratedType {
+            src: String::from("nirvana"),
+            name: String::from(name),
+            doc_alias: None,
+            description: None,
+            derives: vec![],
+            removed_derives: vec![],
+            kind: TypeKind::Struct,
+            constants: vec![],
+            defaults: vec![],
+            deny_unknown_fields: false,
+            properties: property_types
+                .into_iter()
+                .enumerate()
+                .map(|(index, property_type)| GeneratedProperty {
+                    name: format!("p{}", index),
+                    property_type: String::from(property_type),
+                    serde_options: SerdeOptions {
+                        rename: None,
+                        skip_serializing_if: None,
+                        default: None,
+                        with: None,
+                        deserialize_with: None,
+                        aliases: vec![],
+                        flatten: false,
+                        skip_serializing: false,
+                        skip_deserializing: false,
+                    },
+                    description: None,
+                    map_constraints: None,
+                    value_constraints: None,
+                    requires: vec![],
+                })
+                .collect(),
+            examples: vec![],
+            module: vec![],
+            required_any: vec![],
+        }
+    }
+}
@@ -8,29 +8,86 @@ pub struct RefPath {
     pub path: Option<String>,
 }
 
+/// Splits at the first `#`, further ones stay in the path which then resolves to nothing
 pub fn parse_ref(full_path: String) -> RefPath {
-    let parts: Vec<&str> = full_path.split("#").collect();
+    let (file, path) = match full_path.split_once('#') {
+        Some((file, path)) => (file, path),
+        None => (full_path.as_str(), ""),
+    };
+
+    RefPath {
+        file: Some(file.to_string()).filter(|x| !x.is_empty()),
+        path: Some(path.to_string()).filter(|x| !x.is_empty()),
+    }
+}
 
-    let file = match parts[0] {
-        "" => None,
-        _ => Some(parts[0].to_string()),
+/// Builds the canonical source of a nested schema, `file#/json/pointer`.
+pub fn child_src(src: &str, segments: &[&str]) -> String {
+    let mut src = match src.contains('#') {
+        true => src.to_string(),
+        false => format!("{}#", src),
     };
 
-    let path = match parts.len() {
-        1 => None,
-        2 => match parts[1] {
-            "" => None,
-            _ => Some(parts[1].to_string()),
+    for segment in segments {
+        src.push('/');
+        src.push_str(&segment.replace('~', "~0").replace('/', "~1"));
+    }
+
+    src
+}
+
+pub fn unescape_segment(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+/// The `http(s)://` URL of a file, whose schema has to be downloaded
+pub fn remote_url(file: &str) -> Option<&str> {
+    match file.starts_with("http://") || file.starts_with("https://") {
+        true => Some(file),
+        false => None,
+    }
+}
+
+/// Resolves a reference to a file relative to the URL of the file it is in,
+/// `.` and `..` segments are removed
+pub fn join_url(base: &str, reference: &str) -> String {
+    if remote_url(reference).is_some() {
+        return reference.to_string();
+    }
+
+    let (origin, base_path) = match base.find("://").map(|i| i + 3) {
+        Some(start) => match base[start..].find('/') {
+            Some(end) => base.split_at(start + end),
+            None => (base, ""),
         },
-        _ => panic!("Malformed ref path: {}", full_path),
+        None => ("", base),
+    };
+    let mut segments: Vec<&str> = match reference.starts_with('/') {
+        true => vec![],
+        false => base_path.split('/').filter(|x| !x.is_empty()).collect(),
     };
 
-    RefPath { file, path }
+    // The last segment of the base is its file name
+    if !reference.starts_with('/') {
+        segments.pop();
+    }
+
+    for segment in reference.split('/').filter(|x| !x.is_empty()) {
+        match segment {
+            "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+
+    format!("{}/{}", origin, segments.join("/"))
 }
 
 #[cfg(test)]
 mod ref_parser_tests {
-    use crate::ref_parser::{parse_ref, RefPath};
+    use crate::ref_parser::{child_src, join_url, parse_ref, unescape_segment, RefPath};
 
     #[test]
     fn should_parse_empty_path() {
@@ -75,4 +132,54 @@ mod ref_parser_tests {
             parse_ref(String::from("definitions.json#/abc"))
         );
     }
+
+    #[test]
+    fn should_keep_further_hashes_in_the_path() {
+        assert_eq!(
+            RefPath {
+                file: Some(String::from("definitions.json")),
+                path: Some(String::from("/abc#def")),
+            },
+            parse_ref(String::from("definitions.json#/abc#def"))
+        );
+    }
+
+    #[test]
+    fn should_build_child_sources() {
+        assert_eq!(
+            child_src("schema.json", &["definitions", "foo"]),
+            "schema.json#/definitions/foo"
+        );
+        assert_eq!(
+            child_src("schema.json#/definitions/foo", &["properties", "a/b~c"]),
+            "schema.json#/definitions/foo/properties/a~1b~0c"
+        );
+    }
+
+    #[test]
+    fn should_unescape_segments() {
+        assert_eq!(unescape_segment("a~1b~0c"), "a/b~c");
+    }
+
+    #[test]
+    fn should_join_urls() {
+        let base = "https://example.com/schemas/billing/invoice.json";
+
+        assert_eq!(
+            join_url(base, "address.json"),
+            "https://example.com/schemas/billing/address.json"
+        );
+        assert_eq!(
+            join_url(base, "../common/./money.json"),
+            "https://example.com/schemas/common/money.json"
+        );
+        assert_eq!(
+            join_url(base, "/user.json"),
+            "https://example.com/user.json"
+        );
+        assert_eq!(
+            join_url(base, "http://other.org/a.json"),
+            "http://other.org/a.json"
+        );
+    }
 }
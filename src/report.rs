@@ -0,0 +1,148 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use std::fmt;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::binary::BinaryDiagnostic;
+use crate::defaults::DefaultDiagnostic;
+use crate::plan::PlannedType;
+
+/// What a generation run emits, with the findings of the enabled audits.
+#[derive(Clone, PartialEq, Debug, Serialize)]
+#[non_exhaustive]
+pub struct Report {
+    pub types: Vec<PlannedType>,
+    /// See [`crate::Generator::dependencies`]
+    pub dependencies: Vec<PathBuf>,
+    /// Only filled with [`crate::Generator::set_binary_audit`]
+    pub binary_diagnostics: Vec<BinaryDiagnostic>,
+    /// Only filled with [`crate::Generator::set_verify_defaults`]
+    pub default_diagnostics: Vec<DefaultDiagnostic>,
+}
+
+impl Report {
+    /// The report as pretty-printed JSON, for tools which annotate schemas with the types
+    /// they produce. The keys follow the declaration order of the fields.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Generated {} types", self.types.len())?;
+
+        for dependency in &self.dependencies {
+            writeln!(f, "depends on {}", dependency.display())?;
+        }
+
+        for diagnostic in &self.binary_diagnostics {
+            writeln!(
+                f,
+                "warning: {} ({}) {}",
+                diagnostic.name, diagnostic.src, diagnostic.message
+            )?;
+        }
+
+        for diagnostic in &self.default_diagnostics {
+            writeln!(
+                f,
+                "warning: {} has a {}",
+                diagnostic.src, diagnostic.message
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod report_tests {
+    use crate::binary::BinaryDiagnostic;
+    use crate::defaults::DefaultDiagnostic;
+    use crate::plan::{PlannedField, PlannedType};
+    use crate::report::Report;
+    use std::path::PathBuf;
+
+    #[test]
+    fn should_list_warnings_after_the_summary() {
+        let report = Report {
+            types: vec![PlannedType {
+                name: String::from("Reading"),
+                src: String::from("sensor.json"),
+                kind: String::from("struct"),
+                target: None,
+                references: vec![],
+                fields: vec![],
+            }],
+            dependencies: vec![PathBuf::from("units.json")],
+            binary_diagnostics: vec![BinaryDiagnostic {
+                name: String::from("Reading"),
+                src: String::from("sensor.json"),
+                message: String::from("is an untagged enum"),
+            }],
+            default_diagnostics: vec![DefaultDiagnostic {
+                src: String::from("sensor.json#/properties/unit"),
+                message: String::from("default \"K\" is not one of the enum values"),
+            }],
+        };
+
+        assert_eq!(
+            report.to_string(),
+            "Generated 1 types\n\
+             depends on units.json\n\
+             warning: Reading (sensor.json) is an untagged enum\n\
+             warning: sensor.json#/properties/unit has a default \"K\" is not one of the enum values\n"
+        );
+    }
+
+    #[test]
+    fn should_serialize_to_json() {
+        let report = Report {
+            types: vec![PlannedType {
+                name: String::from("Reading"),
+                src: String::from("sensor.json"),
+                kind: String::from("struct"),
+                target: None,
+                references: vec![],
+                fields: vec![PlannedField {
+                    name: String::from("value"),
+                    json_name: String::from("value"),
+                    rust_type: String::from("f64"),
+                    required: true,
+                }],
+            }],
+            dependencies: vec![PathBuf::from("units.json")],
+            binary_diagnostics: vec![],
+            default_diagnostics: vec![],
+        };
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "types": [{
+                    "name": "Reading",
+                    "src": "sensor.json",
+                    "kind": "struct",
+                    "target": null,
+                    "references": [],
+                    "fields": [{
+                        "name": "value",
+                        "json_name": "value",
+                        "rust_type": "f64",
+                        "required": true
+                    }]
+                }],
+                "dependencies": ["units.json"],
+                "binary_diagnostics": [],
+                "default_diagnostics": []
+            })
+        );
+    }
+}
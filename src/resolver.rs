@@ -3,11 +3,14 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
 
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
-use crate::parser::{parse_from_file, DataType, Root};
-use crate::ref_parser::{parse_ref, RefPath};
+use crate::parser::{try_parse_from_file, try_parse_from_string, DataType, Overlay, Root};
+use crate::ref_parser::{join_url, parse_ref, remote_url, unescape_segment, RefPath};
+
+/// Downloads the content of an `http(s)://` URL
+pub type Fetch = Box<dyn Fn(&str) -> Result<String, String>>;
 
 #[derive(PartialEq, Debug)]
 pub struct ResolveResult {
@@ -18,22 +21,51 @@ pub struct ResolveResult {
 
 pub struct Resolver {
     cache: HashMap<String, Rc<Root>>,
+    overlay: Overlay,
+    fetch: Option<Fetch>,
 }
 
 impl Resolver {
     pub fn new() -> Self {
         Resolver {
             cache: HashMap::new(),
+            overlay: Overlay::new(),
+            fetch: None,
         }
     }
 
-    pub fn resolve(&mut self, root: Rc<Root>, ref_path: String) -> ResolveResult {
+    pub fn set_fetch(&mut self, fetch: Fetch) {
+        self.fetch = Some(fetch);
+    }
+
+    pub fn overlay(&self) -> &Overlay {
+        &self.overlay
+    }
+
+    pub fn set_overlay(&mut self, file: &Path, content: String) {
+        self.invalidate(file);
+        self.overlay.insert(file.display().to_string(), content);
+    }
+
+    pub fn remove_overlay(&mut self, file: &Path) {
+        self.invalidate(file);
+        self.overlay.remove(&file.display().to_string());
+    }
+
+    /// The root of the result is the file the data type is defined in,
+    /// which the nested refs of the data type have to be resolved against
+    pub fn resolve(&mut self, root: Rc<Root>, ref_path: String) -> Result<ResolveResult, String> {
         let RefPath { file, path } = parse_ref(ref_path.clone());
 
+        let base = root.file.display().to_string();
+
         let file = match file {
+            Some(file) if remote_url(&base).is_some() || remote_url(&file).is_some() => {
+                Some(PathBuf::from(join_url(&base, &file)))
+            }
             Some(file) => match root.file.parent() {
                 Some(base_path) => Some(Path::join(Path::new(base_path), Path::new(&file))),
-                None => panic!("'{}' has no parent", root.file.display()),
+                None => return Err(format!("'{}' has no parent", root.file.display())),
             },
             None => None,
         };
@@ -41,64 +73,95 @@ impl Resolver {
         let root = match &file {
             Some(file) => match self.cache.get(&file.display().to_string()) {
                 Some(root) => root.clone(),
-                None => self.load(file),
+                None => self.load(file)?,
             },
             None => root,
         };
 
         let data_type = match &path {
-            Some(path) => Resolver::deref(path.clone(), &root.definitions),
+            Some(path) => Resolver::deref(path, &root)?,
             None => root.data_type.clone(),
         };
 
-        ResolveResult {
+        Ok(ResolveResult {
             root,
             path,
             data_type,
-        }
+        })
+    }
+
+    /// The files loaded for references
+    pub fn loaded(&self) -> impl Iterator<Item = &Rc<Root>> {
+        self.cache.values()
+    }
+
+    pub fn invalidate(&mut self, file: &Path) {
+        self.cache.remove(&file.display().to_string());
     }
 
-    fn load(&mut self, file: &Path) -> Rc<Root> {
-        let root = parse_from_file(file);
+    fn load(&mut self, file: &Path) -> Result<Rc<Root>, String> {
+        let root = match remote_url(&file.display().to_string()) {
+            Some(url) => self.download(url)?,
+            None => try_parse_from_file(file, &self.overlay).map_err(|error| error.to_string())?,
+        };
         let rc = Rc::new(root);
         self.cache.insert(file.display().to_string(), rc.clone());
-        rc
+        Ok(rc)
     }
 
-    fn deref(path: String, root_definitions: &HashMap<String, Rc<DataType>>) -> Rc<DataType> {
-        let parts: Vec<&str> = path
-            .split("/")
-            .into_iter()
-            .filter(|x| x.len() > 0)
-            .collect();
+    /// Overlays take precedence, so remote schemas can also be pinned to a local copy
+    fn download(&self, url: &str) -> Result<Root, String> {
+        let json_schema = match (self.overlay.get(url), &self.fetch) {
+            (Some(json_schema), _) => json_schema.clone(),
+            (None, Some(fetch)) => {
+                fetch(url).map_err(|message| format!("Could not download {}: {}", url, message))?
+            }
+            (None, None) => {
+                return Err(format!(
+                    "{} is remote, downloading it has to be enabled with Generator::set_fetch",
+                    url
+                ))
+            }
+        };
 
-        match parts.len() {
-            0 => panic!("Cannot resolve empty ref {}", path),
-            2 => {
-                if parts[0] != "definitions" && parts[0] != "$defs" {
-                    panic!("Ref path should begin with #/definitions or #/$defs")
-                }
+        try_parse_from_string(Path::new(url), &json_schema).map_err(|error| error.to_string())
+    }
 
-                match root_definitions.get(parts[1]) {
-                    Some(data_type) => data_type.clone(),
-                    None => {
-                        panic!("No local definition for {} found", path);
-                    }
+    /// Definitions are looked up by name, every other pointer is parsed from the schema
+    fn deref(path: &str, root: &Root) -> Result<Rc<DataType>, String> {
+        let parts: Vec<String> = path
+            .split('/')
+            .filter(|x| !x.is_empty())
+            .map(unescape_segment)
+            .collect();
+
+        match parts.as_slice() {
+            [] => Err(format!("Cannot resolve empty ref {}", path)),
+            [keyword, name] if keyword == "definitions" || keyword == "$defs" => {
+                match root.definitions.get(name) {
+                    Some(data_type) => Ok(data_type.clone()),
+                    None => Err(format!("No local definition for {} found", path)),
                 }
             }
-            _ => panic!("Invalid ref {}", path),
+            _ => root.parse_pointer(&parts).map(Rc::new),
         }
     }
 }
 
 #[cfg(test)]
 mod resolver_tests {
-    use std::collections::HashMap;
+    use std::collections::{BTreeMap, HashMap};
+    #[cfg(feature = "fs")]
+    use std::fs;
     use std::path::Path;
     use std::rc::Rc;
 
-    use crate::parser::{DataType, Object, ObjectProperty, PrimitiveType, Root};
+    use crate::locations::Locations;
+    use crate::parser::{try_parse_from_string, DataType, PrimitiveType, Root};
+    #[cfg(feature = "fs")]
+    use crate::parser::{Object, ObjectProperty};
     use crate::resolver::{ResolveResult, Resolver};
+    use crate::schema::Schema;
 
     #[test]
     fn should_resolve_local_definition() {
@@ -109,12 +172,23 @@ mod resolver_tests {
 
         let root = Rc::new(Root {
             file: Path::new("does not exist").to_path_buf(),
+            dialect: None,
+            id: None,
+            title: None,
             data_type: Rc::new(DataType::Any),
             definitions,
+            enums: BTreeMap::new(),
+            invalid_defaults: BTreeMap::new(),
+            examples: BTreeMap::new(),
+            constraints: BTreeMap::new(),
+            schema: Schema::default(),
+            locations: Locations::default(),
         });
 
         assert_eq!(
-            resolver.resolve(root.clone(), String::from("#/definitions/foo")),
+            resolver
+                .resolve(root.clone(), String::from("#/definitions/foo"))
+                .unwrap(),
             ResolveResult {
                 root,
                 data_type: referenced_value,
@@ -123,6 +197,7 @@ mod resolver_tests {
         );
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_resolve_file_definition() {
         let mut resolver = Resolver::new();
@@ -130,8 +205,17 @@ mod resolver_tests {
 
         let root = Rc::new(Root {
             file: Path::new("src/examples/resolver/only-here-for-the-base-dir").to_path_buf(),
+            dialect: None,
+            id: None,
+            title: None,
             data_type: Rc::new(DataType::Any),
             definitions: HashMap::new(),
+            enums: BTreeMap::new(),
+            invalid_defaults: BTreeMap::new(),
+            examples: BTreeMap::new(),
+            constraints: BTreeMap::new(),
+            schema: Schema::default(),
+            locations: Locations::default(),
         });
 
         let mut definitions = HashMap::new();
@@ -139,15 +223,31 @@ mod resolver_tests {
 
         let new_root = Rc::new(Root {
             file: Path::new("src/examples/resolver/definitions.json").to_path_buf(),
+            dialect: None,
+            id: None,
+            title: Some(String::from("r00t")),
             data_type: Rc::new(create_root_object()),
             definitions,
+            enums: BTreeMap::new(),
+            invalid_defaults: BTreeMap::new(),
+            examples: BTreeMap::new(),
+            constraints: BTreeMap::new(),
+            schema: serde_json::from_str::<Schema>(
+                &fs::read_to_string("src/examples/resolver/definitions.json").unwrap(),
+            )
+            .unwrap(),
+            locations: Locations::index(
+                &fs::read_to_string("src/examples/resolver/definitions.json").unwrap(),
+            ),
         });
 
         assert_eq!(
-            resolver.resolve(
-                root.clone(),
-                String::from("definitions.json#/definitions/foo"),
-            ),
+            resolver
+                .resolve(
+                    root.clone(),
+                    String::from("definitions.json#/definitions/foo"),
+                )
+                .unwrap(),
             ResolveResult {
                 root: new_root,
                 data_type: referenced_value,
@@ -156,14 +256,24 @@ mod resolver_tests {
         );
     }
 
+    #[cfg(feature = "fs")]
     #[test]
     fn should_resolve_file() {
         let mut resolver = Resolver::new();
 
         let root = Rc::new(Root {
             file: Path::new("src/examples/resolver/only-here-for-the-base-dir").to_path_buf(),
+            dialect: None,
+            id: None,
+            title: None,
             data_type: Rc::new(DataType::Any),
             definitions: HashMap::new(),
+            enums: BTreeMap::new(),
+            invalid_defaults: BTreeMap::new(),
+            examples: BTreeMap::new(),
+            constraints: BTreeMap::new(),
+            schema: Schema::default(),
+            locations: Locations::default(),
         });
 
         let root_object = Rc::new(create_root_object());
@@ -176,12 +286,28 @@ mod resolver_tests {
 
         let new_root = Rc::new(Root {
             file: Path::new("src/examples/resolver/definitions.json").to_path_buf(),
+            dialect: None,
+            id: None,
+            title: Some(String::from("r00t")),
             data_type: root_object.clone(),
             definitions,
+            enums: BTreeMap::new(),
+            invalid_defaults: BTreeMap::new(),
+            examples: BTreeMap::new(),
+            constraints: BTreeMap::new(),
+            schema: serde_json::from_str::<Schema>(
+                &fs::read_to_string("src/examples/resolver/definitions.json").unwrap(),
+            )
+            .unwrap(),
+            locations: Locations::index(
+                &fs::read_to_string("src/examples/resolver/definitions.json").unwrap(),
+            ),
         });
 
         assert_eq!(
-            resolver.resolve(root.clone(), String::from("definitions.json")),
+            resolver
+                .resolve(root.clone(), String::from("definitions.json"))
+                .unwrap(),
             ResolveResult {
                 root: new_root,
                 data_type: root_object,
@@ -190,15 +316,30 @@ mod resolver_tests {
         );
     }
 
+    #[cfg(feature = "fs")]
     fn create_root_object() -> DataType {
         DataType::Object(Object {
             src: String::from("src/examples/resolver/definitions.json"),
             name: String::from("r00t"),
+            title: Some(String::from("r00t")),
+            description: None,
             properties: vec![ObjectProperty {
                 name: String::from("foo"),
                 required: false,
+                constant: None,
+                default: None,
                 data_type: Rc::new(DataType::PrimitiveType(PrimitiveType::String)),
+                description: None,
+                map_constraints: None,
+                aliases: vec![],
+                read_only: false,
+                write_only: false,
+                value_constraints: None,
+                requires: vec![],
             }],
+            required_any: vec![],
+            unmatched_required: vec![],
+            deny_unknown_fields: false,
         })
     }
 
@@ -209,12 +350,21 @@ mod resolver_tests {
 
         let root = Rc::new(Root {
             file: Path::new("does not exist").to_path_buf(),
+            dialect: None,
+            id: None,
+            title: None,
             data_type: root_type.clone(),
             definitions: HashMap::new(),
+            enums: BTreeMap::new(),
+            invalid_defaults: BTreeMap::new(),
+            examples: BTreeMap::new(),
+            constraints: BTreeMap::new(),
+            schema: Schema::default(),
+            locations: Locations::default(),
         });
 
         assert_eq!(
-            resolver.resolve(root.clone(), String::from("")),
+            resolver.resolve(root.clone(), String::from("")).unwrap(),
             ResolveResult {
                 root,
                 data_type: root_type,
@@ -222,4 +372,67 @@ mod resolver_tests {
             }
         );
     }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn should_drop_invalidated_files_from_the_cache() {
+        let mut resolver = Resolver::new();
+
+        let root = Rc::new(Root {
+            file: Path::new("src/examples/resolver/only-here-for-the-base-dir").to_path_buf(),
+            dialect: None,
+            id: None,
+            title: None,
+            data_type: Rc::new(DataType::Any),
+            definitions: HashMap::new(),
+            enums: BTreeMap::new(),
+            invalid_defaults: BTreeMap::new(),
+            examples: BTreeMap::new(),
+            constraints: BTreeMap::new(),
+            schema: Schema::default(),
+            locations: Locations::default(),
+        });
+
+        resolver
+            .resolve(root, String::from("definitions.json"))
+            .unwrap();
+        assert_eq!(resolver.cache.len(), 1);
+
+        resolver.invalidate(Path::new("src/examples/resolver/definitions.json"));
+        assert!(resolver.cache.is_empty());
+    }
+
+    #[test]
+    fn should_resolve_json_pointers_into_the_schema() {
+        let mut resolver = Resolver::new();
+        let file = Path::new("pointers.json");
+        let root = Rc::new(
+            try_parse_from_string(
+                file,
+                r#"{"properties": {"tags": {"type": "array", "items": {"type": "string"}}}, "$defs": {"a": {"$defs": {"b~c/d": {"type": "integer"}}}}}"#,
+            )
+            .unwrap(),
+        );
+
+        let resolve = |resolver: &mut Resolver, path: &str| {
+            resolver
+                .resolve(root.clone(), String::from(path))
+                .map(|x| x.data_type)
+        };
+
+        assert_eq!(
+            resolve(&mut resolver, "#/properties/tags/items"),
+            Ok(Rc::new(DataType::PrimitiveType(PrimitiveType::String)))
+        );
+        assert_eq!(
+            resolve(&mut resolver, "#/$defs/a/$defs/b~0c~1d"),
+            Ok(Rc::new(DataType::PrimitiveType(PrimitiveType::Integer)))
+        );
+        assert_eq!(
+            resolve(&mut resolver, "#/properties/tags/properties/missing"),
+            Err(String::from(
+                "No schema at #/properties/tags/properties/missing of pointers.json"
+            ))
+        );
+    }
 }
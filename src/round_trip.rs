@@ -0,0 +1,219 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use crate::generated::{GeneratedProperty, GeneratedType, TypeKind};
+use crate::options::{CratePaths, MapType, SetType};
+use crate::parser::is_integer_type;
+use crate::sanitizer::sanitize_property_name;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+/// Tests which deserialize a minimal document for every struct and check that
+/// serializing it again only writes the required and defaulted properties.
+///
+/// Structs with required properties whose value cannot be made up are skipped.
+pub fn round_trip_tests(types: &[GeneratedType], paths: &CratePaths) -> TokenStream {
+    let aliases: HashMap<&str, &str> = types
+        .iter()
+        .filter_map(|x| match &x.kind {
+            TypeKind::Alias(target) => Some((x.name.as_str(), target.as_str())),
+            _ => None,
+        })
+        .collect();
+
+    let tests: Vec<TokenStream> = types
+        .iter()
+        .filter(|x| x.kind == TypeKind::Struct)
+        .filter_map(|x| round_trip_test(x, &aliases, paths))
+        .collect();
+
+    if tests.is_empty() {
+        return quote! {};
+    }
+
+    quote! {
+        #[cfg(test)]
+        mod round_trip_tests {
+            use super::*;
+            #(#tests)*
+        }
+    }
+}
+
+fn round_trip_test(
+    generated_type: &GeneratedType,
+    aliases: &HashMap<&str, &str>,
+    paths: &CratePaths,
+) -> Option<TokenStream> {
+    let serde_json = paths.serde_json();
+    let mut document = Map::new();
+    let mut expected_keys = Vec::new();
+
+    for property in &generated_type.properties {
+        let key = property_key(property);
+        let optional = property.serde_options.skip_serializing_if.is_some();
+        let defaulted = property.serde_options.default.is_some();
+
+        if !optional && !defaulted {
+            let value = match property.serde_options.with.as_deref() {
+                Some("serde_helpers::base64") => json!(""),
+                _ => minimal_value(&property.property_type, aliases)?,
+            };
+            document.insert(key.clone(), value);
+        }
+
+        if !optional || defaulted {
+            expected_keys.push(key);
+        }
+    }
+
+    expected_keys.sort();
+
+    let name = proc_macro2::Ident::new(&generated_type.name, Span::call_site());
+    let test_name = proc_macro2::Ident::new(
+        &format!(
+            "{}_round_trips",
+            sanitize_property_name(generated_type.name.clone()).trim_end_matches('_')
+        ),
+        Span::call_site(),
+    );
+    let document = Value::Object(document).to_string();
+
+    Some(quote! {
+        #[test]
+        fn #test_name() {
+            let value: #name = #serde_json::from_str(#document).unwrap();
+            let json = #serde_json::to_value(&value).unwrap();
+            let keys: Vec<&str> = json.as_object().unwrap().keys().map(|x| x.as_str()).collect();
+            assert_eq!(keys, vec![#(#expected_keys),*] as Vec<&str>);
+            assert_eq!(#serde_json::from_value::<#name>(json).unwrap(), value);
+        }
+    })
+}
+
+fn property_key(property: &GeneratedProperty) -> String {
+    match &property.serde_options.rename {
+        Some(rename) => rename.clone(),
+        None => property.name.clone(),
+    }
+}
+
+fn minimal_value(type_name: &str, aliases: &HashMap<&str, &str>) -> Option<Value> {
+    match type_name {
+        "String" => Some(json!("")),
+        "bool" => Some(json!(false)),
+        _ if is_integer_type(type_name) => Some(json!(0)),
+        "f64" => Some(json!(0.0)),
+        "Value" | "()" => Some(Value::Null),
+        _ if type_name.starts_with("Vec<") || SetType::is_set(type_name) => Some(json!([])),
+        _ if MapType::is_map(type_name) => Some(json!({})),
+        _ => aliases
+            .get(type_name)
+            .and_then(|target| minimal_value(target, aliases)),
+    }
+}
+
+#[cfg(test)]
+mod round_trip_tests {
+    use crate::generated::{GeneratedProperty, GeneratedType, SerdeOptions, TypeKind};
+    use crate::options::CratePaths;
+    use crate::round_trip::round_trip_tests;
+
+    fn property(name: &str, property_type: &str, required: bool) -> GeneratedProperty {
+        GeneratedProperty {
+            name: String::from(name),
+            property_type: String::from(property_type),
+            serde_options: SerdeOptions {
+                rename: None,
+                skip_serializing_if: match required {
+                    true => None,
+                    false => Some(String::from("Option::is_none")),
+                },
+                default: None,
+                with: None,
+                deserialize_with: None,
+                aliases: vec![],
+                flatten: false,
+                skip_serializing: false,
+                skip_deserializing: false,
+            },
+            description: None,
+            map_constraints: None,
+            value_constraints: None,
+            requires: vec![],
+        }
+    }
+
+    fn struct_type(name: &str, properties: Vec<GeneratedProperty>) -> GeneratedType {
+        GeneratedType {
+            src: String::new(),
+            name: String::from(name),
+            doc_alias: None,
+            description: None,
+            derives: vec![],
+            removed_derives: vec![],
+            kind: TypeKind::Struct,
+            constants: vec![],
+            defaults: vec![],
+            deny_unknown_fields: false,
+            properties,
+            examples: vec![],
+            module: vec![],
+            required_any: vec![],
+        }
+    }
+
+    #[test]
+    fn should_fill_required_properties_with_minimal_values() {
+        let tests = round_trip_tests(
+            &[
+                struct_type(
+                    "Person",
+                    vec![
+                        property("id", "UserId", true),
+                        property("tags", "Vec<String>", true),
+                        property("age", "Option<i64>", false),
+                    ],
+                ),
+                GeneratedType {
+                    src: String::new(),
+                    name: String::from("UserId"),
+                    doc_alias: None,
+                    description: None,
+                    derives: vec![],
+                    removed_derives: vec![],
+                    kind: TypeKind::Alias(String::from("String")),
+                    constants: vec![],
+                    defaults: vec![],
+                    deny_unknown_fields: false,
+                    properties: vec![],
+                    examples: vec![],
+                    module: vec![],
+                    required_any: vec![],
+                },
+            ],
+            &CratePaths::default(),
+        )
+        .to_string();
+
+        assert!(tests.contains("fn person_round_trips"));
+        assert!(tests.contains(r#""{\"id\":\"\",\"tags\":[]}""#));
+        assert!(tests.contains(r#"vec ! ["id" , "tags"]"#));
+    }
+
+    #[test]
+    fn should_skip_structs_with_required_nested_types() {
+        let tests = round_trip_tests(
+            &[struct_type(
+                "Envelope",
+                vec![property("payload", "Payload", true)],
+            )],
+            &CratePaths::default(),
+        );
+
+        assert!(tests.is_empty());
+    }
+}
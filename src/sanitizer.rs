@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
 
-use crate::keywords::RUST_KEYWORDS;
+use crate::keywords::{RESERVED_MODULE_NAMES, RUST_KEYWORDS};
 use convert_case::{Case, Casing};
 
 pub fn sanitize_property_name(name: String) -> String {
@@ -14,6 +14,14 @@ pub fn sanitize_property_name(name: String) -> String {
     )
 }
 
+/// Like [`sanitize_property_name`], but also escapes the crates the generated code uses
+pub fn sanitize_module_name(name: String) -> String {
+    match sanitize_property_name(name) {
+        name if RESERVED_MODULE_NAMES.contains(&name.as_str()) => name + "_",
+        name => name,
+    }
+}
+
 fn escape_keywords(name: String) -> String {
     if RUST_KEYWORDS.contains(&name.as_str()) {
         name + "_"
@@ -40,9 +48,89 @@ pub fn sanitize_struct_name(name: String) -> String {
         .to_case(Case::Pascal)
 }
 
+/// Turns an arbitrary string, like an `enum` value, into a Pascal case variant name
+pub fn sanitize_variant_name(value: &str) -> String {
+    let words: String = value
+        .replace("@", " at ")
+        .replace("$", " dollar ")
+        .chars()
+        .map(|c| match c.is_alphanumeric() {
+            true => c,
+            false => ' ',
+        })
+        .collect();
+
+    match sanitize_struct_name(words) {
+        name if name.is_empty() => String::from("Empty"),
+        name if name == "Self" => String::from("SelfValue"),
+        name if name.starts_with(|c: char| c.is_alphabetic()) => name,
+        name => format!("Value{}", name),
+    }
+}
+
+/// Sanitizes every value and numbers the names which collide after sanitization
+pub fn sanitize_variant_names(values: &[String]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::with_capacity(values.len());
+
+    for value in values {
+        let name = sanitize_variant_name(value);
+        let mut unique_name = name.clone();
+        let mut counter = 2;
+
+        while names.contains(&unique_name) {
+            unique_name = format!("{}{}", name, counter);
+            counter += 1;
+        }
+
+        names.push(unique_name);
+    }
+
+    names
+}
+
+/// The singular of an English plural like `events` or `categories`, to name the items of arrays.
+/// Names whose singular wouldn't be a word, like `s`, stay as they are.
+pub fn singularize(name: &str) -> String {
+    let lower = name.to_lowercase();
+
+    let singular = if lower.ends_with("ies") && name.len() > 3 {
+        format!("{}y", &name[..name.len() - 3])
+    } else if ["sses", "shes", "ches", "xes", "zes", "uses"]
+        .iter()
+        .any(|x| lower.ends_with(x))
+    {
+        name[..name.len() - 2].to_string()
+    } else if lower.ends_with('s') && !["ss", "us", "is"].iter().any(|x| lower.ends_with(x)) {
+        name[..name.len() - 1].to_string()
+    } else {
+        return name.to_string();
+    };
+
+    match singular.starts_with(char::is_alphabetic) {
+        true => singular,
+        false => name.to_string(),
+    }
+}
+
+pub fn doc_alias(original_name: &str, name: &str) -> Option<String> {
+    let allowed = !original_name.is_empty()
+        && !original_name.eq_ignore_ascii_case(name)
+        && !original_name
+            .chars()
+            .any(|c| c.is_whitespace() || c == '"' || c == '\'');
+
+    match allowed {
+        true => Some(original_name.to_string()),
+        false => None,
+    }
+}
+
 #[cfg(test)]
 mod sanitizer_tests {
-    use crate::sanitizer::{sanitize_property_name, sanitize_struct_name};
+    use crate::sanitizer::{
+        doc_alias, sanitize_module_name, sanitize_property_name, sanitize_struct_name,
+        sanitize_variant_name, sanitize_variant_names, singularize,
+    };
 
     #[test]
     fn should_replace_at_in_property_names() {
@@ -74,6 +162,13 @@ mod sanitizer_tests {
         assert_eq!(s, "enum_");
     }
 
+    #[test]
+    fn should_rename_modules_shadowing_crates() {
+        assert_eq!(sanitize_module_name(String::from("serde")), "serde_");
+        assert_eq!(sanitize_module_name(String::from("mod")), "mod_");
+        assert_eq!(sanitize_module_name(String::from("orders")), "orders");
+    }
+
     #[test]
     fn should_create_pascal_case_struct_names() {
         let s = sanitize_struct_name(String::from("a-wonderful_rust struct"));
@@ -91,4 +186,94 @@ mod sanitizer_tests {
         let s = sanitize_struct_name(String::from("$type"));
         assert_eq!(s, "DollarType");
     }
+
+    #[test]
+    fn should_create_pascal_case_variant_names() {
+        for (value, name) in [
+            ("in-progress", "InProgress"),
+            ("in_progress", "InProgress"),
+            ("IN PROGRESS", "InProgress"),
+            ("inProgress", "InProgress"),
+            ("N/A", "NA"),
+            ("e-mail@home", "EMailAtHome"),
+            ("$ref", "DollarRef"),
+            ("über", "Über"),
+        ] {
+            assert_eq!(sanitize_variant_name(value), name, "{}", value);
+        }
+    }
+
+    #[test]
+    fn should_prefix_variant_names_starting_with_digits() {
+        assert_eq!(sanitize_variant_name("404"), "Value404");
+        assert_eq!(sanitize_variant_name("2xx"), "Value2Xx");
+        assert_eq!(sanitize_variant_name("-1"), "Value1");
+    }
+
+    #[test]
+    fn should_name_variants_without_letters_or_digits() {
+        assert_eq!(sanitize_variant_name(""), "Empty");
+        assert_eq!(sanitize_variant_name("*"), "Empty");
+        assert_eq!(sanitize_variant_name("self"), "SelfValue");
+    }
+
+    #[test]
+    fn should_number_variant_names_colliding_after_sanitization() {
+        let values: Vec<String> = ["in-progress", "in_progress", "InProgress", "done"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+
+        assert_eq!(
+            sanitize_variant_names(&values),
+            vec!["InProgress", "InProgress2", "InProgress3", "Done"]
+        );
+    }
+
+    #[test]
+    fn should_singularize_plurals() {
+        let names = [
+            "events",
+            "categories",
+            "addresses",
+            "boxes",
+            "statuses",
+            "status",
+            "data",
+        ];
+
+        assert_eq!(
+            names.iter().map(|x| singularize(x)).collect::<Vec<_>>(),
+            vec!["event", "category", "address", "box", "status", "status", "data"]
+        );
+    }
+
+    #[test]
+    fn should_keep_names_without_a_singular_word() {
+        let names = ["s", "_s", "1s", "S"];
+
+        assert_eq!(
+            names.iter().map(|x| singularize(x)).collect::<Vec<_>>(),
+            vec!["s", "_s", "1s", "S"]
+        );
+    }
+
+    #[test]
+    fn should_create_doc_alias_for_changed_names() {
+        assert_eq!(
+            doc_alias("userName", "user_name"),
+            Some(String::from("userName"))
+        );
+    }
+
+    #[test]
+    fn should_not_create_doc_alias_for_case_changes() {
+        assert_eq!(doc_alias("user", "User"), None);
+    }
+
+    #[test]
+    fn should_not_create_doc_alias_with_forbidden_characters() {
+        assert_eq!(doc_alias("user name", "UserName"), None);
+        assert_eq!(doc_alias("user\"name", "UserName"), None);
+    }
 }
@@ -0,0 +1,133 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const CONFIG_FILE: &str = "codegen.toml";
+
+const BUILD_SCRIPT: &str = r#"use jsonschema_code_generator::Generator;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+/// Generates the types of every schema in the directory configured in codegen.toml
+fn main() {
+    let config = fs::read_to_string("codegen.toml").expect("Could not read codegen.toml");
+    let value = |key: &str| {
+        config
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .find(|(name, _)| name.trim() == key)
+            .map(|(_, value)| value.trim().trim_matches('"').to_string())
+            .unwrap_or_else(|| panic!("codegen.toml lacks {}", key))
+    };
+    let schemas = value("schemas");
+    let output = value("output");
+
+    println!("cargo:rerun-if-changed=codegen.toml");
+    println!("cargo:rerun-if-changed={}", schemas);
+
+    let mut files: Vec<PathBuf> = fs::read_dir(&schemas)
+        .unwrap_or_else(|error| panic!("Could not read {}: {}", schemas, error))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().map_or(false, |x| x == "json"))
+        .collect();
+    files.sort();
+
+    let mut generator = Generator::new();
+
+    for file in &files {
+        generator.add_file(file);
+    }
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join(output), generator.generate().to_string()).unwrap();
+}
+"#;
+
+/// The files [`scaffold_build_script`] wrote, and the ones it left alone because they already existed
+#[derive(Clone, PartialEq, Debug)]
+pub struct Scaffold {
+    pub written: Vec<PathBuf>,
+    pub kept: Vec<PathBuf>,
+}
+
+/// Writes a `build.rs` and a `codegen.toml` which generate the types of every
+/// schema in `schemas` into `$OUT_DIR/schemas.rs`.
+pub fn scaffold_build_script(project_dir: &Path, schemas: &str) -> io::Result<Scaffold> {
+    let config = format!(
+        "# Read by build.rs\nschemas = \"{}\"\noutput = \"schemas.rs\"\n",
+        schemas.replace('\\', "/")
+    );
+    let files = [
+        (project_dir.join("build.rs"), BUILD_SCRIPT.to_string()),
+        (project_dir.join(CONFIG_FILE), config),
+    ];
+    let mut scaffold = Scaffold {
+        written: vec![],
+        kept: vec![],
+    };
+
+    for (path, content) in files {
+        match path.exists() {
+            true => scaffold.kept.push(path),
+            false => {
+                fs::write(&path, content)?;
+                scaffold.written.push(path);
+            }
+        }
+    }
+
+    Ok(scaffold)
+}
+
+/// What the project still has to do by hand after [`scaffold_build_script`]
+pub fn scaffold_next_steps() -> String {
+    format!(
+        "Add the generator and the crates of the generated code to Cargo.toml:\n\
+         \n\
+         [build-dependencies]\n\
+         jsonschema_code_generator = \"{}\"\n\
+         \n\
+         [dependencies]\n\
+         serde = {{ version = \"1\", features = [\"derive\"] }}\n\
+         serde_json = \"1\"\n\
+         \n\
+         and include the generated types in a module:\n\
+         \n\
+         include!(concat!(env!(\"OUT_DIR\"), \"/schemas.rs\"));\n",
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+#[cfg(test)]
+mod scaffold_tests {
+    use std::fs;
+
+    use crate::scaffold::scaffold_build_script;
+
+    #[test]
+    fn should_not_overwrite_existing_files() {
+        let dir = std::env::temp_dir().join("jsonschema_code_generator_scaffold_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("build.rs"), "fn main() {}").unwrap();
+
+        let scaffold = scaffold_build_script(&dir, "schemas").unwrap();
+
+        assert_eq!(scaffold.written, vec![dir.join("codegen.toml")]);
+        assert_eq!(scaffold.kept, vec![dir.join("build.rs")]);
+        assert_eq!(
+            fs::read_to_string(dir.join("codegen.toml")).unwrap(),
+            "# Read by build.rs\nschemas = \"schemas\"\noutput = \"schemas.rs\"\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("build.rs")).unwrap(),
+            "fn main() {}"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -4,8 +4,9 @@
 
 use std::collections::BTreeMap;
 
-use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
 
 #[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
 pub enum Types {
@@ -25,22 +26,45 @@ pub enum Types {
     Object,
 }
 
-#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+#[serde(remote = "Self")]
 pub struct Schema {
     #[serde(rename = "$ref")]
     pub ref_: Option<String>,
 
+    #[serde(rename = "$schema")]
+    pub dollar_schema: Option<String>,
+
+    #[serde(rename = "$id")]
+    pub dollar_id: Option<String>,
+
+    pub id: Option<String>,
+
     pub title: Option<String>,
 
+    pub description: Option<String>,
+
+    pub default: Option<Value>,
+
+    /// Only arrays are used, OpenAPI's example maps are ignored
+    pub examples: Option<Value>,
+
+    pub nullable: Option<bool>,
+
     #[serde(rename = "type")]
     pub type_: Option<Types>,
 
+    /// The types of a `type` array of more than one type, `type_` is `None` then
+    #[serde(skip)]
+    pub type_union: Vec<Types>,
+
     #[serde(rename = "enum")]
     pub enum_: Option<Vec<Value>>,
 
     pub required: Option<Vec<String>>,
 
-    pub constant: Option<Value>,
+    #[serde(rename = "const")]
+    pub const_: Option<Value>,
 
     #[serde(default)]
     pub properties: BTreeMap<String, Schema>,
@@ -48,9 +72,75 @@ pub struct Schema {
     #[serde(default, rename = "patternProperties")]
     pub pattern_properties: BTreeMap<String, Schema>,
 
+    pub pattern: Option<String>,
+
+    pub minimum: Option<f64>,
+
+    pub maximum: Option<f64>,
+
+    /// A boolean which makes `minimum` exclusive in draft 4, the exclusive bound itself later
+    #[serde(rename = "exclusiveMinimum")]
+    pub exclusive_minimum: Option<Value>,
+
+    /// A boolean which makes `maximum` exclusive in draft 4, the exclusive bound itself later
+    #[serde(rename = "exclusiveMaximum")]
+    pub exclusive_maximum: Option<Value>,
+
+    #[serde(rename = "multipleOf")]
+    pub multiple_of: Option<f64>,
+
+    #[serde(rename = "minLength")]
+    pub min_length: Option<u64>,
+
+    #[serde(rename = "maxLength")]
+    pub max_length: Option<u64>,
+
+    #[serde(rename = "minItems")]
+    pub min_items: Option<u64>,
+
+    #[serde(rename = "maxItems")]
+    pub max_items: Option<u64>,
+
+    #[serde(rename = "uniqueItems")]
+    pub unique_items: Option<bool>,
+
+    #[serde(rename = "minProperties")]
+    pub min_properties: Option<u64>,
+
+    #[serde(rename = "maxProperties")]
+    pub max_properties: Option<u64>,
+
+    /// The value is only sent by the server, like an `id` it assigns
+    #[serde(rename = "readOnly")]
+    pub read_only: Option<bool>,
+
+    /// The value is only sent to the server, like a password
+    #[serde(rename = "writeOnly")]
+    pub write_only: Option<bool>,
+
+    #[serde(rename = "contentEncoding")]
+    pub content_encoding: Option<String>,
+
+    pub format: Option<String>,
+
+    /// `false` is read as an empty schema, like every boolean schema
+    #[serde(default, rename = "additionalProperties")]
+    pub additional_properties: Box<Option<Schema>>,
+
+    #[serde(default, rename = "propertyNames")]
+    pub property_names: Box<Option<Schema>>,
+
     #[serde(default)]
     pub items: Box<Option<Schema>>,
 
+    /// The schemas of the leading items, from an `items` array before 2020-12
+    #[serde(default, rename = "prefixItems")]
+    pub prefix_items: Vec<Schema>,
+
+    /// The prefix items were given as an `items` array, which their pointers start with
+    #[serde(skip)]
+    pub items_array: bool,
+
     #[serde(default)]
     pub definitions: BTreeMap<String, Schema>,
 
@@ -65,4 +155,134 @@ pub struct Schema {
 
     #[serde(default, rename = "allOf")]
     pub all_of: Vec<Schema>,
+
+    /// Properties which have to be present if the key is, from an array in `dependencies` before 2019-09
+    #[serde(default, rename = "dependentRequired")]
+    pub dependent_required: BTreeMap<String, Vec<String>>,
+
+    /// Schemas the object has to match if the key is present, from `dependencies` before 2019-09
+    #[serde(default, rename = "dependentSchemas")]
+    pub dependent_schemas: BTreeMap<String, Schema>,
+
+    /// The dependent schemas were given as `dependencies`, which their pointers start with
+    #[serde(skip)]
+    pub dependencies_object: bool,
+
+    /// The condition of `then` and `else`
+    #[serde(default, rename = "if")]
+    pub if_: Box<Option<Schema>>,
+
+    #[serde(default)]
+    pub then: Box<Option<Schema>>,
+
+    #[serde(default, rename = "else")]
+    pub else_: Box<Option<Schema>>,
+
+    #[serde(rename = "x-aliases")]
+    pub x_aliases: Option<Vec<String>>,
+
+    /// Descriptions of the `enum` values, an array in their order or an object by value
+    #[serde(rename = "x-enumDescriptions")]
+    pub x_enum_descriptions: Option<Value>,
+
+    /// Path of a Rust type used instead of generating one, e.g. `crate::money::Amount`
+    #[serde(rename = "x-rust-type")]
+    pub x_rust_type: Option<String>,
+
+    /// Where the normalizer took the schema from, if it isn't where it is now
+    #[serde(skip)]
+    pub src: Option<String>,
+
+    /// The value of a boolean schema
+    #[serde(skip)]
+    pub boolean: Option<bool>,
+}
+
+impl Schema {
+    /// The keyword the pointers of the prefix items start with
+    pub fn prefix_items_keyword(&self) -> &'static str {
+        match self.items_array {
+            true => "items",
+            false => "prefixItems",
+        }
+    }
+
+    /// The keyword the pointers of the dependent schemas start with
+    pub fn dependent_schemas_keyword(&self) -> &'static str {
+        match self.dependencies_object {
+            true => "dependencies",
+            false => "dependentSchemas",
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Schema {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Value::deserialize(deserializer)? {
+            // `false` would reject every value, which no generated type can express
+            Value::Bool(value) => Ok(Schema {
+                boolean: Some(value),
+                ..Schema::default()
+            }),
+            Value::Object(mut map) => {
+                let types = match map.get("type") {
+                    Some(Value::Array(_)) => map.remove("type"),
+                    _ => None,
+                };
+                let items_array = map.get("items").is_some_and(Value::is_array);
+
+                if items_array {
+                    let items = map.remove("items").unwrap_or_default();
+                    map.insert(String::from("prefixItems"), items);
+
+                    // The items after an `items` array, like `items` after `prefixItems`
+                    if let Some(additional_items) = map.remove("additionalItems") {
+                        map.insert(String::from("items"), additional_items);
+                    }
+                }
+
+                // Arrays of `dependencies` are required names, other values are schemas
+                let dependencies = match map.remove("dependencies") {
+                    Some(Value::Object(dependencies)) => dependencies,
+                    _ => Map::new(),
+                };
+                let (required, schemas): (Map<String, Value>, Map<String, Value>) =
+                    dependencies.into_iter().partition(|(_, x)| x.is_array());
+                let dependencies_object =
+                    !schemas.is_empty() && !map.contains_key("dependentSchemas");
+
+                if dependencies_object {
+                    map.insert(String::from("dependentSchemas"), Value::Object(schemas));
+                }
+
+                if !required.is_empty() && !map.contains_key("dependentRequired") {
+                    map.insert(String::from("dependentRequired"), Value::Object(required));
+                }
+
+                let mut schema =
+                    Schema::deserialize(Value::Object(map)).map_err(D::Error::custom)?;
+                schema.items_array = items_array;
+                schema.dependencies_object = dependencies_object;
+
+                if let Some(types) = types {
+                    let mut types: Vec<Types> =
+                        serde_json::from_value(types).map_err(D::Error::custom)?;
+
+                    match types.len() {
+                        1 => schema.type_ = types.pop(),
+                        _ => schema.type_union = types,
+                    }
+                }
+
+                Ok(schema)
+            }
+            value => Schema::deserialize(value).map_err(D::Error::custom),
+        }
+    }
+}
+
+impl Serialize for Schema {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Schema::serialize(self, serializer)
+    }
 }
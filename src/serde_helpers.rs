@@ -0,0 +1,251 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use crate::generated::GeneratedType;
+use crate::options::CratePaths;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::collections::BTreeSet;
+
+const PREFIX: &str = "serde_helpers::";
+
+/// Emits a single `mod serde_helpers` with the helpers the properties refer to,
+/// or nothing if none of them needs one.
+pub fn serde_helpers(types: &[GeneratedType], paths: &CratePaths) -> TokenStream {
+    let used: BTreeSet<&str> = types
+        .iter()
+        .flat_map(|x| &x.properties)
+        .flat_map(|x| {
+            x.serde_options
+                .with
+                .iter()
+                .chain(x.serde_options.deserialize_with.iter())
+        })
+        .filter_map(|x| x.strip_prefix(PREFIX))
+        .collect();
+
+    if used.is_empty() {
+        return quote! {};
+    }
+
+    let serde_json = paths.serde_json();
+    let mut items = Vec::new();
+
+    if used.contains("base64") || used.contains("option_base64") {
+        items.push(base64_codec());
+    }
+
+    if used.iter().any(|x| x.contains("from_string_or_number")) {
+        items.push(quote! {
+            fn from_string_or_number<T>(value: #serde_json::Value) -> Result<T, String>
+            where
+                T: std::str::FromStr,
+                T::Err: std::fmt::Display,
+            {
+                match value {
+                    #serde_json::Value::Number(number) => {
+                        number.to_string().parse().map_err(|e: T::Err| e.to_string())
+                    }
+                    #serde_json::Value::String(text) => text.parse().map_err(|e: T::Err| e.to_string()),
+                    value => Err(format!("expected a number or a string, found {}", value)),
+                }
+            }
+        });
+    }
+
+    for name in used {
+        items.push(helper(name, paths));
+    }
+
+    quote! {
+        mod serde_helpers {
+            #(#items)*
+        }
+    }
+}
+
+fn helper(name: &str, paths: &CratePaths) -> TokenStream {
+    let serde = paths.serde();
+
+    match name {
+        "base64" => quote! {
+            pub mod base64 {
+                pub fn serialize<S: #serde::Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_str(&super::encode_base64(bytes))
+                }
+                pub fn deserialize<'de, D: #serde::Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+                    let text: String = #serde::Deserialize::deserialize(deserializer)?;
+                    super::decode_base64(&text).map_err(#serde::de::Error::custom)
+                }
+            }
+        },
+        "option_base64" => quote! {
+            pub mod option_base64 {
+                pub fn serialize<S: #serde::Serializer>(bytes: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error> {
+                    match bytes {
+                        Some(bytes) => serializer.serialize_some(&super::encode_base64(bytes)),
+                        None => serializer.serialize_none(),
+                    }
+                }
+                pub fn deserialize<'de, D: #serde::Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error> {
+                    let text: Option<String> = #serde::Deserialize::deserialize(deserializer)?;
+                    text.map(|text| super::decode_base64(&text))
+                        .transpose()
+                        .map_err(#serde::de::Error::custom)
+                }
+            }
+        },
+        "i64_from_string_or_number" => number_helper(name, quote! { i64 }, paths),
+        "f64_from_string_or_number" => number_helper(name, quote! { f64 }, paths),
+        "option_i64_from_string_or_number" => option_number_helper(name, quote! { i64 }, paths),
+        "option_f64_from_string_or_number" => option_number_helper(name, quote! { f64 }, paths),
+        "empty_string_as_none" => quote! {
+            pub fn empty_string_as_none<'de, D: #serde::Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+                let text: Option<String> = #serde::Deserialize::deserialize(deserializer)?;
+                Ok(text.filter(|text| !text.is_empty()))
+            }
+        },
+        _ => panic!("Unknown serde helper {}", name),
+    }
+}
+
+fn number_helper(name: &str, number_type: TokenStream, paths: &CratePaths) -> TokenStream {
+    let serde = paths.serde();
+    let serde_json = paths.serde_json();
+    let name = proc_macro2::Ident::new(name, proc_macro2::Span::call_site());
+
+    quote! {
+        pub fn #name<'de, D: #serde::Deserializer<'de>>(deserializer: D) -> Result<#number_type, D::Error> {
+            let value: #serde_json::Value = #serde::Deserialize::deserialize(deserializer)?;
+            from_string_or_number(value).map_err(#serde::de::Error::custom)
+        }
+    }
+}
+
+fn option_number_helper(name: &str, number_type: TokenStream, paths: &CratePaths) -> TokenStream {
+    let serde = paths.serde();
+    let serde_json = paths.serde_json();
+    let name = proc_macro2::Ident::new(name, proc_macro2::Span::call_site());
+
+    quote! {
+        pub fn #name<'de, D: #serde::Deserializer<'de>>(deserializer: D) -> Result<Option<#number_type>, D::Error> {
+            let value: Option<#serde_json::Value> = #serde::Deserialize::deserialize(deserializer)?;
+            value
+                .map(from_string_or_number)
+                .transpose()
+                .map_err(#serde::de::Error::custom)
+        }
+    }
+}
+
+fn base64_codec() -> TokenStream {
+    quote! {
+        const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+        fn encode_base64(bytes: &[u8]) -> String {
+            let mut text = String::with_capacity((bytes.len() + 2) / 3 * 4);
+            for chunk in bytes.chunks(3) {
+                let n = (chunk[0] as u32) << 16
+                    | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+                    | *chunk.get(2).unwrap_or(&0) as u32;
+                for i in 0..4 {
+                    match i <= chunk.len() {
+                        true => text.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char),
+                        false => text.push('='),
+                    }
+                }
+            }
+            text
+        }
+
+        fn decode_base64(text: &str) -> Result<Vec<u8>, String> {
+            let mut bytes = Vec::with_capacity(text.len() / 4 * 3);
+            let mut buffer = 0u32;
+            let mut bits = 0;
+            for c in text.trim_end_matches('=').bytes() {
+                let value = match BASE64_ALPHABET.iter().position(|x| *x == c) {
+                    Some(value) => value as u32,
+                    None => return Err(format!("invalid base64 character {:?}", c as char)),
+                };
+                buffer = buffer << 6 | value;
+                bits += 6;
+                if bits >= 8 {
+                    bits -= 8;
+                    bytes.push((buffer >> bits) as u8);
+                    buffer &= (1 << bits) - 1;
+                }
+            }
+            Ok(bytes)
+        }
+    }
+}
+
+#[cfg(test)]
+mod serde_helpers_tests {
+    use crate::generated::{GeneratedProperty, GeneratedType, SerdeOptions, TypeKind};
+    use crate::options::CratePaths;
+    use crate::serde_helpers::serde_helpers;
+
+    fn struct_type(with: Vec<&str>) -> GeneratedType {
+        GeneratedType {
+            src: String::new(),
+            name: String::from("Upload"),
+            doc_alias: None,
+            description: None,
+            derives: vec![],
+            removed_derives: vec![],
+            kind: TypeKind::Struct,
+            constants: vec![],
+            defaults: vec![],
+            deny_unknown_fields: false,
+            properties: with
+                .into_iter()
+                .map(|with| GeneratedProperty {
+                    name: String::from("data"),
+                    property_type: String::from("Vec<u8>"),
+                    serde_options: SerdeOptions {
+                        rename: None,
+                        skip_serializing_if: None,
+                        default: None,
+                        with: Some(String::from(with)),
+                        deserialize_with: None,
+                        aliases: vec![],
+                        flatten: false,
+                        skip_serializing: false,
+                        skip_deserializing: false,
+                    },
+                    description: None,
+                    map_constraints: None,
+                    value_constraints: None,
+                    requires: vec![],
+                })
+                .collect(),
+            examples: vec![],
+            module: vec![],
+            required_any: vec![],
+        }
+    }
+
+    #[test]
+    fn should_emit_each_helper_once() {
+        let helpers = serde_helpers(
+            &[
+                struct_type(vec!["serde_helpers::base64"]),
+                struct_type(vec!["serde_helpers::base64", "serde_helpers::base64"]),
+            ],
+            &CratePaths::default(),
+        )
+        .to_string();
+
+        assert_eq!(helpers.matches("mod serde_helpers").count(), 1);
+        assert_eq!(helpers.matches("pub mod base64").count(), 1);
+        assert_eq!(helpers.matches("fn encode_base64").count(), 1);
+        assert!(!helpers.contains("from_string_or_number"));
+    }
+
+    #[test]
+    fn should_not_emit_unused_helpers() {
+        assert!(serde_helpers(&[struct_type(vec![])], &CratePaths::default()).is_empty());
+    }
+}
@@ -0,0 +1,277 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use crate::generated::{GeneratedType, TypeKind};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Emits a `mod regex` in place of the crate, if a map key or a `validate` method checks
+/// a pattern, so the generated code only depends on `serde` and `serde_json`.
+pub fn standalone_helpers(types: &[GeneratedType]) -> TokenStream {
+    let checks_patterns = types.iter().any(|x| {
+        matches!(x.kind, TypeKind::MapKey(_))
+            || matches!(
+                &x.kind,
+                TypeKind::Constrained { constraints, .. } if constraints.pattern.is_some()
+            )
+            || x.properties.iter().any(|x| {
+                x.map_constraints
+                    .as_ref()
+                    .is_some_and(|x| x.key_pattern.is_some())
+                    || x.value_constraints
+                        .as_ref()
+                        .is_some_and(|x| x.has_pattern())
+            })
+    });
+
+    match checks_patterns {
+        true => pattern_matcher(),
+        false => quote! {},
+    }
+}
+
+/// A backtracking matcher of the pattern syntax schemas commonly use: literals, `.`,
+/// classes, `\d`, `\w` and `\s`, groups, alternatives, anchors and quantifiers.
+/// Other syntax is rejected by `Regex::new`.
+fn pattern_matcher() -> TokenStream {
+    quote! {
+        mod regex {
+            const DIGITS: &[(char, char)] = &[('0', '9')];
+            const WORD: &[(char, char)] = &[('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')];
+            const SPACE: &[(char, char)] = &[(' ', ' '), ('\t', '\r')];
+
+            enum Node {
+                Char(char),
+                Any,
+                Class(Vec<(char, char)>, bool),
+                Start,
+                End,
+                Group(Vec<Vec<Node>>),
+                Repeat(Box<Node>, usize, Option<usize>),
+            }
+
+            pub struct Regex(Node);
+
+            impl Regex {
+                pub fn new(pattern: &str) -> Result<Regex, String> {
+                    let chars: Vec<char> = pattern.chars().collect();
+                    let mut pos = 0;
+                    let node = parse_alternatives(&chars, &mut pos)?;
+                    match pos == chars.len() {
+                        true => Ok(Regex(node)),
+                        false => Err(format!("unmatched ) in the pattern {}", pattern)),
+                    }
+                }
+
+                pub fn is_match(&self, text: &str) -> bool {
+                    let chars: Vec<char> = text.chars().collect();
+                    (0..=chars.len()).any(|start| match_node(&self.0, &chars, start, &mut |_| true))
+                }
+            }
+
+            fn parse_alternatives(chars: &[char], pos: &mut usize) -> Result<Node, String> {
+                let mut alternatives = vec![Vec::new()];
+                while *pos < chars.len() && chars[*pos] != ')' {
+                    let c = chars[*pos];
+                    *pos += 1;
+                    let atom = match c {
+                        '|' => {
+                            alternatives.push(Vec::new());
+                            continue;
+                        }
+                        '^' => Node::Start,
+                        '$' => Node::End,
+                        '.' => Node::Any,
+                        '(' => {
+                            if chars[*pos..].starts_with(&['?', ':']) {
+                                *pos += 2;
+                            }
+                            let group = parse_alternatives(chars, pos)?;
+                            if *pos == chars.len() {
+                                return Err(String::from("unclosed ( in the pattern"));
+                            }
+                            *pos += 1;
+                            group
+                        }
+                        '[' => parse_class(chars, pos)?,
+                        '\\' => parse_escape(chars, pos)?,
+                        '*' | '+' | '?' | '{' => return Err(format!("nothing to repeat with {}", c)),
+                        c => Node::Char(c),
+                    };
+                    let atom = parse_quantifier(chars, pos, atom)?;
+                    alternatives.last_mut().unwrap().push(atom);
+                }
+                Ok(Node::Group(alternatives))
+            }
+
+            fn parse_quantifier(chars: &[char], pos: &mut usize, atom: Node) -> Result<Node, String> {
+                let number = |text: &str| {
+                    text.trim()
+                        .parse::<usize>()
+                        .map_err(|_| format!("invalid repetition {}", text))
+                };
+                let (min, max) = match chars.get(*pos) {
+                    Some('*') => (0, None),
+                    Some('+') => (1, None),
+                    Some('?') => (0, Some(1)),
+                    Some('{') => {
+                        let end = *pos + chars[*pos..]
+                            .iter()
+                            .position(|x| *x == '}')
+                            .ok_or("unclosed { in the pattern")?;
+                        let bounds: String = chars[*pos + 1..end].iter().collect();
+                        *pos = end;
+                        match bounds.split_once(',') {
+                            None => (number(&bounds)?, Some(number(&bounds)?)),
+                            Some((min, "")) => (number(min)?, None),
+                            Some((min, max)) => (number(min)?, Some(number(max)?)),
+                        }
+                    }
+                    _ => return Ok(atom),
+                };
+                *pos += 1;
+                // Lazy quantifiers match the same texts
+                if chars.get(*pos) == Some(&'?') {
+                    *pos += 1;
+                }
+                Ok(Node::Repeat(Box::new(atom), min, max))
+            }
+
+            fn parse_escape(chars: &[char], pos: &mut usize) -> Result<Node, String> {
+                let c = *chars.get(*pos).ok_or("trailing \\ in the pattern")?;
+                *pos += 1;
+                Ok(match c {
+                    'd' => Node::Class(DIGITS.to_vec(), false),
+                    'D' => Node::Class(DIGITS.to_vec(), true),
+                    'w' => Node::Class(WORD.to_vec(), false),
+                    'W' => Node::Class(WORD.to_vec(), true),
+                    's' => Node::Class(SPACE.to_vec(), false),
+                    'S' => Node::Class(SPACE.to_vec(), true),
+                    'n' => Node::Char('\n'),
+                    't' => Node::Char('\t'),
+                    'r' => Node::Char('\r'),
+                    'f' => Node::Char('\x0C'),
+                    'v' => Node::Char('\x0B'),
+                    c if c.is_ascii_alphanumeric() => return Err(format!("unsupported escape \\{}", c)),
+                    c => Node::Char(c),
+                })
+            }
+
+            fn parse_class(chars: &[char], pos: &mut usize) -> Result<Node, String> {
+                let negated = chars.get(*pos) == Some(&'^');
+                if negated {
+                    *pos += 1;
+                }
+                let mut ranges = Vec::new();
+                loop {
+                    let start = match parse_class_member(chars, pos)? {
+                        None => return Ok(Node::Class(ranges, negated)),
+                        Some(Node::Char(c)) => c,
+                        Some(Node::Class(mut members, false)) => {
+                            ranges.append(&mut members);
+                            continue;
+                        }
+                        Some(_) => return Err(String::from("unsupported negated class in a class")),
+                    };
+                    let end = match (chars.get(*pos), chars.get(*pos + 1)) {
+                        (Some('-'), Some(x)) if *x != ']' => {
+                            *pos += 1;
+                            match parse_class_member(chars, pos)? {
+                                Some(Node::Char(c)) => c,
+                                _ => return Err(String::from("invalid range in a class")),
+                            }
+                        }
+                        _ => start,
+                    };
+                    ranges.push((start, end));
+                }
+            }
+
+            fn parse_class_member(chars: &[char], pos: &mut usize) -> Result<Option<Node>, String> {
+                let c = *chars.get(*pos).ok_or("unclosed [ in the pattern")?;
+                *pos += 1;
+                match c {
+                    ']' => Ok(None),
+                    '\\' => parse_escape(chars, pos).map(Some),
+                    c => Ok(Some(Node::Char(c))),
+                }
+            }
+
+            fn match_node(node: &Node, text: &[char], pos: usize, next: &mut dyn FnMut(usize) -> bool) -> bool {
+                let c = text.get(pos).copied();
+                match node {
+                    Node::Char(x) => c == Some(*x) && next(pos + 1),
+                    Node::Any => c.is_some_and(|c| c != '\n') && next(pos + 1),
+                    Node::Class(ranges, negated) => {
+                        c.is_some_and(|c| ranges.iter().any(|(start, end)| *start <= c && c <= *end) != *negated)
+                            && next(pos + 1)
+                    }
+                    Node::Start => pos == 0 && next(pos),
+                    Node::End => pos == text.len() && next(pos),
+                    Node::Group(alternatives) => alternatives.iter().any(|x| match_sequence(x, text, pos, next)),
+                    Node::Repeat(inner, min, max) => match_repeat(inner, *min, *max, text, pos, next),
+                }
+            }
+
+            fn match_sequence(nodes: &[Node], text: &[char], pos: usize, next: &mut dyn FnMut(usize) -> bool) -> bool {
+                match nodes.split_first() {
+                    None => next(pos),
+                    Some((first, rest)) => match_node(first, text, pos, &mut |pos| match_sequence(rest, text, pos, next)),
+                }
+            }
+
+            fn match_repeat(
+                inner: &Node,
+                min: usize,
+                max: Option<usize>,
+                text: &[char],
+                pos: usize,
+                next: &mut dyn FnMut(usize) -> bool,
+            ) -> bool {
+                // Repetitions which match nothing only count towards the minimum
+                let more = max != Some(0)
+                    && match_node(inner, text, pos, &mut |end| {
+                        (end != pos || min > 0)
+                            && match_repeat(inner, min.saturating_sub(1), max.map(|x| x - 1), text, end, next)
+                    });
+                more || (min == 0 && next(pos))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod standalone_tests {
+    use crate::generated::{GeneratedType, TypeKind};
+    use crate::standalone::standalone_helpers;
+
+    fn generated_type(kind: TypeKind) -> GeneratedType {
+        GeneratedType {
+            src: String::new(),
+            name: String::from("Key"),
+            doc_alias: None,
+            description: None,
+            derives: vec![],
+            removed_derives: vec![],
+            kind,
+            constants: vec![],
+            defaults: vec![],
+            deny_unknown_fields: false,
+            properties: vec![],
+            examples: vec![],
+            module: vec![],
+            required_any: vec![],
+        }
+    }
+
+    #[test]
+    fn should_only_emit_the_matcher_for_patterns() {
+        let key = generated_type(TypeKind::MapKey(String::from("^x-")));
+
+        assert!(standalone_helpers(&[key])
+            .to_string()
+            .contains("pub struct Regex"));
+        assert!(standalone_helpers(&[generated_type(TypeKind::Struct)]).is_empty());
+    }
+}
@@ -0,0 +1,134 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use crate::parser::{AllOf, AnyOf, DataType, EmptyObject, KeyedMap, Object, OneOf, Ref, Root};
+use crate::ref_parser::{parse_ref, RefPath};
+use std::collections::BTreeMap;
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct SchemaStatistics {
+    pub file: String,
+    pub definitions: usize,
+    pub properties: usize,
+    /// Deepest nesting of schemas including the root, without following `$ref`s
+    pub max_depth: usize,
+    /// Number of `$ref`s pointing to other files
+    pub external_refs: usize,
+    /// Number of allowed values of every `enum`, keyed by source
+    pub enum_cardinalities: BTreeMap<String, usize>,
+}
+
+impl From<&Root> for SchemaStatistics {
+    fn from(root: &Root) -> Self {
+        let mut statistics = SchemaStatistics {
+            file: root.file.display().to_string(),
+            definitions: root.definitions.len(),
+            enum_cardinalities: root.enums.clone(),
+            ..SchemaStatistics::default()
+        };
+
+        statistics.max_depth = statistics.visit(&root.data_type);
+
+        let mut definitions: Vec<(&String, _)> = root.definitions.iter().collect();
+        definitions.sort_by_key(|(name, _)| *name);
+
+        for (_, data_type) in definitions {
+            statistics.visit(data_type);
+        }
+
+        statistics
+    }
+}
+
+impl SchemaStatistics {
+    fn visit(&mut self, data_type: &DataType) -> usize {
+        let depth = match data_type {
+            DataType::PrimitiveType(_)
+            | DataType::StringEnum(_)
+            | DataType::Custom(_)
+            | DataType::Any => 0,
+            DataType::Array(data_type) | DataType::Set(data_type) | DataType::Map(data_type) => {
+                self.visit(data_type)
+            }
+            DataType::KeyedMap(KeyedMap { values, .. }) => self.visit(values),
+            DataType::EmptyObject(EmptyObject {
+                additional_properties,
+                ..
+            }) => additional_properties
+                .as_ref()
+                .map(|x| self.visit(x))
+                .unwrap_or_default(),
+            DataType::Object(Object { properties, .. }) => {
+                self.properties += properties.len();
+
+                properties
+                    .iter()
+                    .map(|property| self.visit(&property.data_type))
+                    .max()
+                    .unwrap_or_default()
+            }
+            DataType::Ref(Ref { ref_path, .. }) => {
+                if let RefPath { file: Some(_), .. } = parse_ref(ref_path.clone()) {
+                    self.external_refs += 1;
+                }
+
+                0
+            }
+            DataType::OneOf(OneOf { types, .. })
+            | DataType::AnyOf(AnyOf { types, .. })
+            | DataType::AllOf(AllOf { types, .. }) => types
+                .iter()
+                .map(|data_type| self.visit(data_type))
+                .max()
+                .unwrap_or_default(),
+            DataType::Tuple(items) => items
+                .iter()
+                .map(|data_type| self.visit(data_type))
+                .max()
+                .unwrap_or_default(),
+        };
+
+        depth + 1
+    }
+}
+
+#[cfg(all(test, feature = "fs"))]
+mod statistics_tests {
+    use crate::parser::{parse_from_file, Overlay};
+    use crate::statistics::SchemaStatistics;
+    use std::collections::BTreeMap;
+    use std::path::Path;
+
+    #[test]
+    fn should_count_schema_elements() {
+        let root = parse_from_file(
+            Path::new("src/examples/statistics/statistics.schema.json"),
+            &Overlay::new(),
+        );
+
+        let mut enum_cardinalities = BTreeMap::new();
+        enum_cardinalities.insert(
+            String::from("src/examples/statistics/statistics.schema.json#/definitions/level"),
+            3,
+        );
+        enum_cardinalities.insert(
+            String::from(
+                "src/examples/statistics/statistics.schema.json#/properties/meta/properties/kind",
+            ),
+            2,
+        );
+
+        assert_eq!(
+            SchemaStatistics::from(&root),
+            SchemaStatistics {
+                file: String::from("src/examples/statistics/statistics.schema.json"),
+                definitions: 1,
+                properties: 5,
+                max_depth: 4,
+                external_refs: 1,
+                enum_cardinalities,
+            }
+        );
+    }
+}
@@ -0,0 +1,254 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use crate::error::Error;
+use crate::generator::file_stem;
+use crate::parser::{parse_error, read_source, Overlay};
+use crate::ref_parser::{parse_ref, unescape_segment};
+
+/// Keywords whose values are data rather than schemas, so a `$ref` key in them is no reference
+const DATA_KEYWORDS: [&str; 4] = ["const", "enum", "default", "examples"];
+
+/// Copies the schema the pointer of the file points to into a self-contained schema.
+/// Every schema it refers to, directly or through other references and files,
+/// is copied into its `$defs` and the `$ref`s are rewritten to point there.
+pub fn extract_subset(file: &Path, pointer: &str, overlay: &Overlay) -> Result<Value, Error> {
+    let pointer = pointer.trim_start_matches('#');
+    let mut extraction = Extraction {
+        overlay,
+        root: (file.to_path_buf(), pointer.to_string()),
+        documents: HashMap::new(),
+        names: HashMap::new(),
+        used_names: HashSet::new(),
+        pending: Vec::new(),
+    };
+
+    let mut subset = extraction.lookup(file, pointer)?;
+
+    if let Some(Value::Object(defs)) = subset.get("$defs") {
+        extraction.used_names.extend(defs.keys().cloned());
+    }
+
+    extraction.rewrite(&mut subset, file);
+
+    let mut defs = Map::new();
+
+    while let Some((file, pointer, name)) = extraction.pending.pop() {
+        let mut schema = extraction.lookup(&file, &pointer)?;
+        extraction.rewrite(&mut schema, &file);
+        defs.insert(name, schema);
+    }
+
+    let dialect = extraction.documents[file].get("$schema").cloned();
+    let title = match pointer.rsplit('/').next() {
+        Some(segment) if !segment.is_empty() => Some(unescape_segment(segment)),
+        _ => None,
+    };
+
+    if let Value::Object(subset) = &mut subset {
+        if let (Some(dialect), false) = (dialect, subset.contains_key("$schema")) {
+            subset.insert(String::from("$schema"), dialect);
+        }
+
+        // Keeps the name of the extracted definition, which is otherwise lost
+        if let (Some(title), false) = (title, subset.contains_key("title")) {
+            subset.insert(String::from("title"), Value::String(title));
+        }
+
+        if !defs.is_empty() {
+            match subset
+                .entry("$defs")
+                .or_insert_with(|| Value::Object(Map::new()))
+            {
+                Value::Object(own) => own.extend(defs),
+                _ => unreachable!("`$defs` of a schema is always an object"),
+            }
+        }
+    }
+
+    Ok(subset)
+}
+
+struct Extraction<'a> {
+    overlay: &'a Overlay,
+    /// File and pointer of the extracted schema, references to it point to `#`
+    root: (PathBuf, String),
+    documents: HashMap<PathBuf, Value>,
+    /// Names in `$defs` of the referenced schemas, keyed by file and pointer
+    names: HashMap<(PathBuf, String), String>,
+    used_names: HashSet<String>,
+    /// Referenced schemas which still have to be copied
+    pending: Vec<(PathBuf, String, String)>,
+}
+
+impl<'a> Extraction<'a> {
+    fn lookup(&mut self, file: &Path, pointer: &str) -> Result<Value, Error> {
+        if !self.documents.contains_key(file) {
+            let (path, text) = read_source(file, self.overlay)?;
+            let document = serde_json::from_str(&text).map_err(|err| parse_error(&path, err))?;
+            self.documents.insert(file.to_path_buf(), document);
+        }
+
+        self.documents[file]
+            .pointer(pointer)
+            .cloned()
+            .ok_or_else(|| Error::Resolve {
+                location: format!("{}#{}", file.display(), pointer),
+                message: String::from("the pointer points to nothing"),
+            })
+    }
+
+    fn rewrite(&mut self, value: &mut Value, file: &Path) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(reference)) = map.get("$ref") {
+                    let reference = self.reference(file, reference);
+                    map.insert(String::from("$ref"), Value::String(reference));
+                }
+
+                for (key, child) in map.iter_mut() {
+                    if !DATA_KEYWORDS.contains(&key.as_str()) {
+                        self.rewrite(child, file);
+                    }
+                }
+            }
+            Value::Array(values) => {
+                for child in values {
+                    self.rewrite(child, file);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The `$ref` to the copy of the referenced schema, remote references are kept
+    fn reference(&mut self, file: &Path, reference: &str) -> String {
+        if reference.contains("://") {
+            return reference.to_string();
+        }
+
+        let target = parse_ref(reference.to_string());
+        let target_file = match &target.file {
+            Some(target_file) => match file.parent() {
+                Some(base_path) => base_path.join(target_file),
+                None => PathBuf::from(target_file),
+            },
+            None => file.to_path_buf(),
+        };
+        let key = (target_file, target.path.unwrap_or_default());
+
+        if key == self.root {
+            return String::from("#");
+        }
+
+        let name = match self.names.get(&key) {
+            Some(name) => name.clone(),
+            None => {
+                let base_name = match key.1.rsplit('/').next() {
+                    Some(segment) if !segment.is_empty() => unescape_segment(segment),
+                    _ => file_stem(&key.0),
+                };
+                let mut name = base_name.clone();
+                let mut counter = 2;
+
+                while self.used_names.contains(&name) {
+                    name = format!("{}{}", base_name, counter);
+                    counter += 1;
+                }
+
+                self.used_names.insert(name.clone());
+                self.names.insert(key.clone(), name.clone());
+                self.pending.push((key.0, key.1, name.clone()));
+                name
+            }
+        };
+
+        format!("#/$defs/{}", name.replace('~', "~0").replace('/', "~1"))
+    }
+}
+
+#[cfg(test)]
+mod subset_tests {
+    use serde_json::json;
+    use std::path::Path;
+
+    use crate::error::Error;
+    use crate::parser::Overlay;
+    use crate::subset::extract_subset;
+
+    #[test]
+    fn should_copy_the_referenced_schemas_into_defs() {
+        let mut overlay = Overlay::new();
+        overlay.insert(
+            String::from("specs/api.json"),
+            json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "definitions": {
+                    "User": {
+                        "type": "object",
+                        "properties": {
+                            "address": { "$ref": "#/definitions/Address" },
+                            "manager": { "$ref": "#/definitions/User" },
+                            "team": { "$ref": "teams.json#/definitions/Team" },
+                            "kind": { "const": { "$ref": "data" } }
+                        }
+                    },
+                    "Address": { "type": "string" },
+                    "Unrelated": { "type": "integer" }
+                }
+            })
+            .to_string(),
+        );
+        overlay.insert(
+            String::from("specs/teams.json"),
+            json!({
+                "definitions": {
+                    "Team": { "properties": { "lead": { "$ref": "api.json#/definitions/User" } } }
+                }
+            })
+            .to_string(),
+        );
+
+        let subset =
+            extract_subset(Path::new("specs/api.json"), "#/definitions/User", &overlay).unwrap();
+
+        assert_eq!(
+            subset,
+            json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "title": "User",
+                "type": "object",
+                "properties": {
+                    "address": { "$ref": "#/$defs/Address" },
+                    "manager": { "$ref": "#" },
+                    "team": { "$ref": "#/$defs/Team" },
+                    "kind": { "const": { "$ref": "data" } }
+                },
+                "$defs": {
+                    "Address": { "type": "string" },
+                    "Team": { "properties": { "lead": { "$ref": "#" } } }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn should_fail_on_pointers_to_nothing() {
+        let mut overlay = Overlay::new();
+        overlay.insert(String::from("api.json"), String::from("{}"));
+
+        assert_eq!(
+            extract_subset(Path::new("api.json"), "#/definitions/User", &overlay),
+            Err(Error::Resolve {
+                location: String::from("api.json#/definitions/User"),
+                message: String::from("the pointer points to nothing"),
+            })
+        );
+    }
+}
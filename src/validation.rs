@@ -0,0 +1,772 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use crate::generated::{GeneratedProperty, GeneratedType, TypeKind};
+use crate::options::{MapType, SetType};
+use crate::parser::{is_integer_type, MapConstraints, ValueConstraints};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::quote;
+use std::collections::{HashMap, HashSet};
+
+/// Emits a `validate` method for every type which checks constraints, either its own or
+/// those of the types of its fields, and the `ValidationError` they return, or nothing
+/// if no type checks any.
+pub fn validate_methods(types: &[GeneratedType], vis: &TokenStream) -> TokenStream {
+    let aliases: HashMap<&str, &str> = types
+        .iter()
+        .filter_map(|x| match &x.kind {
+            TypeKind::Alias(target) => Some((x.name.as_str(), target.as_str())),
+            _ => None,
+        })
+        .collect();
+    let mut validated: HashSet<&str> = types
+        .iter()
+        .filter(|x| {
+            x.kind == TypeKind::Struct && !own_checks(&x.properties, &x.required_any).is_empty()
+        })
+        .map(|x| x.name.as_str())
+        .collect();
+
+    // Types whose fields are of validated types are validated as well
+    loop {
+        let nested: Vec<&str> = types
+            .iter()
+            .filter(|x| !validated.contains(x.name.as_str()))
+            .filter(|x| !nested_checks(x, &validated, &aliases).is_empty())
+            .map(|x| x.name.as_str())
+            .collect();
+
+        if nested.is_empty() {
+            break;
+        }
+
+        validated.extend(nested);
+    }
+
+    if validated.is_empty() {
+        return quote! {};
+    }
+
+    let methods: Vec<TokenStream> = types
+        .iter()
+        .filter(|x| validated.contains(x.name.as_str()))
+        .map(|x| {
+            let name = Ident::new(&x.name, Span::call_site());
+            let own_checks = match x.kind {
+                TypeKind::Struct => own_checks(&x.properties, &x.required_any),
+                _ => vec![],
+            };
+            let nested_checks = nested_checks(x, &validated, &aliases);
+
+            quote! {
+                impl #name {
+                    pub fn validate(&self) -> Result<(), ValidationError> {
+                        #(#own_checks)*
+                        #(#nested_checks)*
+                        Ok(())
+                    }
+                }
+            }
+        })
+        .collect();
+
+    quote! {
+        ///A value which violates a constraint of its schema
+        #[derive(Clone, PartialEq, Eq, Debug)]
+        #vis struct ValidationError {
+            ///JSON pointer to the invalid value, empty if it is the validated value itself
+            pub path: String,
+            pub message: String,
+        }
+        impl ValidationError {
+            pub fn new(message: impl Into<String>) -> Self {
+                ValidationError {
+                    path: String::new(),
+                    message: message.into(),
+                }
+            }
+            ///The error of a value nested in the validated one under the key or index
+            pub fn at(mut self, segment: impl std::fmt::Display) -> Self {
+                let segment = segment.to_string().replace('~', "~0").replace('/', "~1");
+                self.path = format!("/{}{}", segment, self.path);
+                self
+            }
+        }
+        impl std::fmt::Display for ValidationError {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self.path.is_empty() {
+                    true => f.write_str(&self.message),
+                    false => write!(f, "{} at {}", self.message, self.path),
+                }
+            }
+        }
+        impl std::error::Error for ValidationError {}
+        impl From<String> for ValidationError {
+            fn from(message: String) -> Self {
+                ValidationError::new(message)
+            }
+        }
+        #(#methods)*
+    }
+}
+
+/// The calls of `validate` on the values of the type which are of validated types
+fn nested_checks(
+    generated_type: &GeneratedType,
+    validated: &HashSet<&str>,
+    aliases: &HashMap<&str, &str>,
+) -> Vec<TokenStream> {
+    let name = Ident::new(&generated_type.name, Span::call_site());
+    let variants: Vec<(&String, &String)> = match &generated_type.kind {
+        TypeKind::UntaggedEnum(variants) => variants
+            .iter()
+            .map(|x| (&x.name, &x.variant_type))
+            .collect(),
+        TypeKind::TaggedEnum { variants, .. } => variants
+            .iter()
+            .map(|x| (&x.name, &x.variant_type))
+            .collect(),
+        TypeKind::Struct => {
+            return generated_type
+                .properties
+                .iter()
+                .filter_map(|property| {
+                    let field = Ident::new(&property.name, Span::call_site());
+                    let key = property_key(property);
+                    // Flattened properties share the keys of the struct
+                    let segments = match property.serde_options.flatten {
+                        true => vec![],
+                        false => vec![quote! { #key }],
+                    };
+                    let check =
+                        nested_check(&property.property_type, segments, validated, aliases)?;
+
+                    Some(quote! {
+                        {
+                            let value = &self.#field;
+                            #check
+                        }
+                    })
+                })
+                .collect();
+        }
+        TypeKind::Wrapper => {
+            let property = &generated_type.properties[0];
+            let key = property_key(property);
+
+            return nested_check(
+                &property.property_type,
+                vec![quote! { #key }],
+                validated,
+                aliases,
+            )
+            .map(|check| {
+                quote! {
+                    {
+                        let value = &self.0;
+                        #check
+                    }
+                }
+            })
+            .into_iter()
+            .collect();
+        }
+        TypeKind::Newtype(target) => {
+            return nested_check(target, vec![], validated, aliases)
+                .map(|check| {
+                    quote! {
+                        {
+                            let value = &self.0;
+                            #check
+                        }
+                    }
+                })
+                .into_iter()
+                .collect();
+        }
+        TypeKind::Map { value, .. } => {
+            return nested_check(value, vec![quote! { key }], validated, aliases)
+                .map(|check| {
+                    quote! {
+                        for (key, value) in self.0.iter() {
+                            #check
+                        }
+                    }
+                })
+                .into_iter()
+                .collect();
+        }
+        _ => return vec![],
+    };
+
+    let arms: Vec<TokenStream> = variants
+        .into_iter()
+        .filter_map(|(variant, variant_type)| {
+            let variant = Ident::new(variant, Span::call_site());
+            let check = nested_check(variant_type, vec![], validated, aliases)?;
+
+            Some(quote! {
+                #name::#variant(value) => {
+                    #check
+                }
+            })
+        })
+        .collect();
+
+    match arms.is_empty() {
+        true => vec![],
+        false => vec![quote! {
+            #[allow(unreachable_patterns)]
+            match self {
+                #(#arms)*
+                _ => {}
+            }
+        }],
+    }
+}
+
+/// Validates the `value` of the type and everything of a validated type in it, adding
+/// the segments to the path of the error, or `None` if nothing in it is validated
+fn nested_check(
+    type_name: &str,
+    segments: Vec<TokenStream>,
+    validated: &HashSet<&str>,
+    aliases: &HashMap<&str, &str>,
+) -> Option<TokenStream> {
+    let type_name = type_name.trim();
+
+    if let Some(inner) = generic_argument(type_name, "Option") {
+        let check = nested_check(inner, segments, validated, aliases)?;
+
+        return Some(quote! {
+            if let Some(value) = value {
+                #check
+            }
+        });
+    }
+
+    if let Some(inner) = generic_argument(type_name, "Box") {
+        let check = nested_check(inner, segments, validated, aliases)?;
+
+        return Some(quote! {
+            let value = &**value;
+            #check
+        });
+    }
+
+    if type_name.starts_with("Vec<") || SetType::is_set(type_name) {
+        let inner = type_arguments(type_name).pop()?;
+        let check = nested_check(
+            inner,
+            [segments, vec![quote! { index }]].concat(),
+            validated,
+            aliases,
+        )?;
+
+        return Some(quote! {
+            for (index, value) in value.iter().enumerate() {
+                #check
+            }
+        });
+    }
+
+    if MapType::is_map(type_name) {
+        let inner = type_arguments(type_name).pop()?;
+        let check = nested_check(
+            inner,
+            [segments, vec![quote! { key }]].concat(),
+            validated,
+            aliases,
+        )?;
+
+        return Some(quote! {
+            for (key, value) in value.iter() {
+                #check
+            }
+        });
+    }
+
+    if validated.contains(type_name) {
+        let segments = segments.iter().rev();
+
+        return Some(match segments.len() {
+            0 => quote! { value.validate()?; },
+            _ => quote! { value.validate().map_err(|error| error #(.at(#segments))*)?; },
+        });
+    }
+
+    match aliases.get(type_name) {
+        Some(target) if *target != type_name => nested_check(target, segments, validated, aliases),
+        _ => None,
+    }
+}
+
+/// The argument of a type like `Option<T>`
+fn generic_argument<'a>(type_name: &'a str, generic: &str) -> Option<&'a str> {
+    type_name
+        .strip_prefix(generic)
+        .and_then(|x| x.strip_prefix('<'))
+        .and_then(|x| x.strip_suffix('>'))
+}
+
+/// The arguments of a type like `BTreeMap<K, V>`, split at the commas outside of nested arguments
+pub(crate) fn type_arguments(type_name: &str) -> Vec<&str> {
+    let arguments = match type_name.find('<') {
+        Some(start) if type_name.ends_with('>') => &type_name[start + 1..type_name.len() - 1],
+        _ => return vec![],
+    };
+    let mut depth = 0;
+    let mut start = 0;
+    let mut result = Vec::new();
+
+    for (i, c) in arguments.char_indices() {
+        match c {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                result.push(arguments[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    result.push(arguments[start..].trim());
+    result
+}
+
+/// Compiles the pattern into `pattern` once, for every call of the function it is placed in
+fn cached_regex(pattern: &str) -> TokenStream {
+    quote! {
+        static PATTERN: std::sync::OnceLock<Result<regex::Regex, String>> = std::sync::OnceLock::new();
+        let pattern = PATTERN
+            .get_or_init(|| regex::Regex::new(#pattern).map_err(|e| e.to_string()))
+            .as_ref()
+            .map_err(|e| e.clone())?;
+    }
+}
+
+/// The checks of the map and value constraints of the properties of a struct,
+/// and of the properties which have to be present
+fn own_checks(properties: &[GeneratedProperty], required_any: &[Vec<String>]) -> Vec<TokenStream> {
+    let mut checks: Vec<TokenStream> = properties
+        .iter()
+        .filter_map(|property| {
+            let field = proc_macro2::Ident::new(&property.name, Span::call_site());
+            let mut checks = Vec::new();
+
+            if let Some(constraints) = &property.value_constraints {
+                let value_type = property
+                    .property_type
+                    .strip_prefix("Option<")
+                    .and_then(|x| x.strip_suffix('>'))
+                    .unwrap_or(&property.property_type);
+                let key = property_key(property);
+                checks.extend(value_checks(
+                    value_type,
+                    constraints,
+                    &|message, segments| {
+                        quote! { ValidationError::new(#message) #(.at(#segments))* .at(#key) }
+                    },
+                ));
+            }
+
+            if let Some(constraints) = &property.map_constraints {
+                checks.extend(map_checks(property, constraints));
+            }
+
+            if checks.is_empty() {
+                return None;
+            }
+
+            Some(match property.property_type.starts_with("Option<") {
+                true => quote! {
+                    if let Some(value) = &self.#field {
+                        #(#checks)*
+                    }
+                },
+                false => quote! {
+                    {
+                        let value = &self.#field;
+                        #(#checks)*
+                    }
+                },
+            })
+        })
+        .collect();
+
+    if !required_any.is_empty() {
+        let alternatives: Vec<TokenStream> = required_any
+            .iter()
+            .map(|names| {
+                let present: Vec<TokenStream> = names
+                    .iter()
+                    .filter_map(|name| properties.iter().find(|x| property_key(x) == name))
+                    .filter(|x| x.property_type.starts_with("Option<"))
+                    .map(|x| {
+                        let field = proc_macro2::Ident::new(&x.name, Span::call_site());
+                        quote! { self.#field.is_some() }
+                    })
+                    .collect();
+
+                match present.len() {
+                    0 => quote! { true },
+                    1 => quote! { #(#present)* },
+                    _ => quote! { (#(#present)&&*) },
+                }
+            })
+            .collect();
+        let expected = required_any
+            .iter()
+            .map(|names| names.join(" and "))
+            .collect::<Vec<String>>()
+            .join(", or ");
+        let message = format!("Expected {}", expected);
+
+        checks.push(quote! {
+            if !(#(#alternatives)||*) {
+                return Err(ValidationError::new(#message));
+            }
+        });
+    }
+
+    for property in properties.iter().filter(|x| !x.requires.is_empty()) {
+        let key = property_key(property);
+        let missing: Vec<TokenStream> = property
+            .requires
+            .iter()
+            .filter_map(|name| properties.iter().find(|x| property_key(x) == name))
+            .filter(|x| x.property_type.starts_with("Option<"))
+            .map(|x| {
+                let field = proc_macro2::Ident::new(&x.name, Span::call_site());
+                let message = format!("{} is required if {} is present", property_key(x), key);
+
+                quote! {
+                    if self.#field.is_none() {
+                        return Err(ValidationError::new(#message));
+                    }
+                }
+            })
+            .collect();
+
+        if missing.is_empty() {
+            continue;
+        }
+
+        let field = proc_macro2::Ident::new(&property.name, Span::call_site());
+
+        let check = match (
+            property.property_type.starts_with("Option<"),
+            &property.serde_options.default,
+        ) {
+            (true, _) => quote! {
+                if self.#field.is_some() {
+                    #(#missing)*
+                }
+            },
+            // A required property is always present, one with a default may have been absent
+            (false, None) => quote! { #(#missing)* },
+            (false, Some(_)) => continue,
+        };
+
+        checks.push(check);
+    }
+
+    checks
+}
+
+/// The checks of the pattern and length of a string, the bounds of a number
+/// and the item count and items of an array. `error` turns a message, which doesn't
+/// name the value, and the path segments of an item into the returned error.
+pub(crate) fn value_checks(
+    value_type: &str,
+    constraints: &ValueConstraints,
+    error: &dyn Fn(TokenStream, &[TokenStream]) -> TokenStream,
+) -> Vec<TokenStream> {
+    item_value_checks(value_type, constraints, &[], error)
+}
+
+fn item_value_checks(
+    value_type: &str,
+    constraints: &ValueConstraints,
+    segments: &[TokenStream],
+    error: &dyn Fn(TokenStream, &[TokenStream]) -> TokenStream,
+) -> Vec<TokenStream> {
+    let integer = is_integer_type(value_type);
+    let number = |x: f64| match integer {
+        true => Literal::i64_unsuffixed(x as i64),
+        false => Literal::f64_unsuffixed(x),
+    };
+    let fail = |message: TokenStream| {
+        let error = error(message, segments);
+        quote! { return Err(#error); }
+    };
+    let mut checks = Vec::new();
+
+    if let Some(pattern) = &constraints.pattern {
+        let pattern_check = cached_regex(pattern);
+        let fail = fail(quote! { format!("'{}' does not match {}", value, #pattern) });
+        checks.push(quote! {
+            {
+                #pattern_check
+                if !pattern.is_match(value) {
+                    #fail
+                }
+            }
+        });
+    }
+
+    if let Some(min) = constraints.min_length {
+        let min = Literal::u64_unsuffixed(min);
+        let fail = fail(quote! { format!("is shorter than {} characters", #min) });
+        checks.push(quote! {
+            if (value.chars().count() as u64) < #min {
+                #fail
+            }
+        });
+    }
+
+    if let Some(max) = constraints.max_length {
+        let max = Literal::u64_unsuffixed(max);
+        let fail = fail(quote! { format!("is longer than {} characters", #max) });
+        checks.push(quote! {
+            if value.chars().count() as u64 > #max {
+                #fail
+            }
+        });
+    }
+
+    if let Some((min, exclusive)) = constraints.minimum {
+        let min = number(min);
+        checks.push(match exclusive {
+            true => {
+                let fail = fail(quote! { format!("is {}, not greater than {}", value, #min) });
+                quote! {
+                    if *value <= #min {
+                        #fail
+                    }
+                }
+            }
+            false => {
+                let fail = fail(quote! { format!("is {}, less than {}", value, #min) });
+                quote! {
+                    if *value < #min {
+                        #fail
+                    }
+                }
+            }
+        });
+    }
+
+    if let Some((max, exclusive)) = constraints.maximum {
+        let max = number(max);
+        checks.push(match exclusive {
+            true => {
+                let fail = fail(quote! { format!("is {}, not less than {}", value, #max) });
+                quote! {
+                    if *value >= #max {
+                        #fail
+                    }
+                }
+            }
+            false => {
+                let fail = fail(quote! { format!("is {}, greater than {}", value, #max) });
+                quote! {
+                    if *value > #max {
+                        #fail
+                    }
+                }
+            }
+        });
+    }
+
+    if let Some(multiple_of) = constraints.multiple_of {
+        let divisor = Literal::f64_unsuffixed(multiple_of);
+        let fail = fail(quote! { format!("is {}, not a multiple of {}", value, #divisor) });
+
+        checks.push(match (integer, multiple_of.fract() == 0.0) {
+            (true, true) => {
+                let multiple_of = number(multiple_of);
+                quote! {
+                    if *value % #multiple_of != 0 {
+                        #fail
+                    }
+                }
+            }
+            (integer, _) => {
+                let value = match integer {
+                    true => quote! { *value as f64 },
+                    false => quote! { *value },
+                };
+                // Decimal divisors like 0.01 aren't exact in binary
+                quote! {
+                    let quotient = #value / #divisor;
+                    if (quotient - quotient.round()).abs() > 1e-9 {
+                        #fail
+                    }
+                }
+            }
+        });
+    }
+
+    if let Some(min) = constraints.min_items {
+        let min = Literal::u64_unsuffixed(min);
+        let fail = fail(quote! { format!("has {} items, fewer than {}", value.len(), #min) });
+        checks.push(quote! {
+            if (value.len() as u64) < #min {
+                #fail
+            }
+        });
+    }
+
+    if let Some(max) = constraints.max_items {
+        let max = Literal::u64_unsuffixed(max);
+        let fail = fail(quote! { format!("has {} items, more than {}", value.len(), #max) });
+        checks.push(quote! {
+            if value.len() as u64 > #max {
+                #fail
+            }
+        });
+    }
+
+    if let Some(items) = &constraints.items {
+        let item_type = match value_type.starts_with("Vec<") || SetType::is_set(value_type) {
+            true => type_arguments(value_type).pop(),
+            false => None,
+        };
+
+        // Only the types the checks work on, not the newtypes of the items
+        if let Some(item_type) = item_type.filter(|x| is_checked_type(x)) {
+            let index = Ident::new(
+                &match segments.len() {
+                    0 => String::from("index"),
+                    depth => format!("index{}", depth),
+                },
+                Span::call_site(),
+            );
+            let segments = [vec![quote! { #index }], segments.to_vec()].concat();
+            let item_checks = item_value_checks(item_type, items, &segments, error);
+
+            if !item_checks.is_empty() {
+                checks.push(quote! {
+                    for (#index, value) in value.iter().enumerate() {
+                        #(#item_checks)*
+                    }
+                });
+            }
+        }
+    }
+
+    checks
+}
+
+/// Whether the value checks work on the type
+fn is_checked_type(type_name: &str) -> bool {
+    matches!(type_name, "String" | "f64" | "f32")
+        || is_integer_type(type_name)
+        || type_name.starts_with("Vec<")
+        || SetType::is_set(type_name)
+}
+
+/// The checks of the entry count, the keys and the required keys of a map
+fn map_checks(property: &GeneratedProperty, constraints: &MapConstraints) -> Vec<TokenStream> {
+    // The keys of flattened maps are those of the struct
+    let at = match property.serde_options.flatten {
+        true => quote! {},
+        false => {
+            let key = property_key(property);
+            quote! { .at(#key) }
+        }
+    };
+    let mut checks = Vec::new();
+
+    if let Some(min) = constraints.min_properties {
+        let min = Literal::u64_unsuffixed(min);
+        checks.push(quote! {
+            if (value.len() as u64) < #min {
+                return Err(ValidationError::new(format!("has {} entries, fewer than {}", value.len(), #min)) #at);
+            }
+        });
+    }
+
+    if let Some(max) = constraints.max_properties {
+        let max = Literal::u64_unsuffixed(max);
+        checks.push(quote! {
+            if value.len() as u64 > #max {
+                return Err(ValidationError::new(format!("has {} entries, more than {}", value.len(), #max)) #at);
+            }
+        });
+    }
+
+    if let Some(pattern) = &constraints.key_pattern {
+        let pattern_check = cached_regex(pattern);
+        checks.push(quote! {
+            {
+                #pattern_check
+                if let Some(key) = value.keys().find(|key| !pattern.is_match(key)) {
+                    return Err(ValidationError::new(format!("key '{}' does not match {}", key, #pattern)).at(key) #at);
+                }
+            }
+        });
+    }
+
+    for required_key in &constraints.required_keys {
+        let message = match property.serde_options.flatten {
+            true => format!("Missing the required property {}", required_key),
+            false => format!("lacks the required key {}", required_key),
+        };
+
+        checks.push(quote! {
+            if !value.contains_key(#required_key) {
+                return Err(ValidationError::new(#message) #at);
+            }
+        });
+    }
+
+    checks
+}
+
+/// The JSON key of the property
+fn property_key(property: &GeneratedProperty) -> &str {
+    match &property.serde_options.rename {
+        Some(rename) => rename,
+        None => &property.name,
+    }
+}
+
+#[cfg(test)]
+mod validation_tests {
+    use crate::validation::{nested_check, type_arguments};
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn should_split_type_arguments_outside_of_nested_ones() {
+        assert_eq!(
+            type_arguments("BTreeMap<String, BTreeMap<String, (i64, Child)>>"),
+            vec!["String", "BTreeMap<String, (i64, Child)>"]
+        );
+        assert!(type_arguments("Child").is_empty());
+    }
+
+    #[test]
+    fn should_only_check_values_of_validated_types() {
+        let validated = HashSet::from(["Child"]);
+        let aliases = HashMap::from([("Children", "Vec<Child>")]);
+        let check = |type_name: &str| {
+            nested_check(type_name, vec![], &validated, &aliases).map(|x| x.to_string())
+        };
+
+        assert_eq!(check("Option<Vec<String>>"), None);
+        assert_eq!(
+            check("Option<Box<Child>>"),
+            Some(String::from(
+                "if let Some (value) = value { let value = & * * value ; value . validate () ? ; }"
+            ))
+        );
+        assert_eq!(
+            check("Children"),
+            Some(String::from("for (index , value) in value . iter () . enumerate () { value . validate () . map_err (| error | error . at (index)) ? ; }"))
+        );
+    }
+}
@@ -0,0 +1,57 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! Compiles generated code, which `lib_tests` keeps in sync with the generator.
+
+#[allow(dead_code, clippy::all)]
+mod draft_04_builders {
+    include!("../schemas/draft-04.builders.rs");
+}
+
+#[allow(dead_code, clippy::all)]
+mod validation {
+    include!("../schemas/validation.rs");
+}
+
+#[test]
+fn should_compile_the_builders_of_draft_04() {
+    let builder: draft_04_builders::UnknownBuilder = draft_04_builders::Unknown::builder();
+
+    builder.set_default(serde_json::json!(3));
+}
+
+fn validation_error(json: &str) -> validation::ValidationError {
+    let order: validation::Order = serde_json::from_str(json).unwrap();
+
+    order.validate().unwrap_err()
+}
+
+#[test]
+fn should_point_to_the_invalid_value() {
+    let error = validation_error(r#"{"count": 3}"#);
+
+    assert_eq!(error.path, "/count");
+    assert_eq!(error.to_string(), "is 3, less than 5 at /count");
+    assert_eq!(validation_error(r#"{"child": {"x": 5}}"#).path, "/child/x");
+    assert_eq!(
+        validation_error(r#"{"labels": {"x-a": "", "x-b": "", "x-c": ""}}"#).path,
+        "/labels"
+    );
+}
+
+#[test]
+fn should_validate_the_items_of_arrays() {
+    let error = validation_error(r#"{"tags": ["a", "B"]}"#);
+
+    assert_eq!(error.path, "/tags/1");
+    assert_eq!(error.message, "'B' does not match ^[a-z]+$");
+    assert_eq!(
+        validation_error(r#"{"grid": [[1], [2, 10]]}"#).path,
+        "/grid/1/1"
+    );
+
+    let order: validation::Order =
+        serde_json::from_str(r#"{"count": 5, "tags": ["a"], "grid": [[9]]}"#).unwrap();
+    assert_eq!(order.validate(), Ok(()));
+}
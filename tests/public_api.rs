@@ -0,0 +1,88 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! Uses the API like a downstream build script, so removing or renaming
+//! a part of the public surface fails to compile.
+
+use std::path::Path;
+
+use jsonschema_code_generator::emitters::sql::create_tables;
+use jsonschema_code_generator::{
+    BytesStyle, Error, GeneratedProperty, GeneratedType, Generator, GeneratorOptions,
+    MapConstraints, MapType, NullStyle, Plan, Report, TypeKind, Visibility,
+};
+
+#[test]
+fn should_expose_the_generated_types() {
+    let mut generator = Generator::new();
+    generator.set_bytes_style(BytesStyle::Base64);
+    generator.set_null_style(NullStyle::Unit);
+    generator.add_str(
+        "map.constraints.schema.json",
+        include_str!("../src/examples/generator/map.constraints.schema.json"),
+    );
+
+    let plan: Plan = generator.plan();
+    let report: Report = generator.report();
+    let options: GeneratorOptions = generator.options().clone();
+    let types: Vec<GeneratedType> = generator.into();
+
+    let type_names: Vec<&str> = plan.types.iter().map(|x| x.name.as_str()).collect();
+    let constraints: Vec<&MapConstraints> = types[0]
+        .properties
+        .iter()
+        .filter_map(|x: &GeneratedProperty| x.map_constraints.as_ref())
+        .collect();
+
+    assert_eq!(type_names, vec![types[0].name.as_str()]);
+    assert!(matches!(types[0].kind, TypeKind::Struct));
+    assert!(!constraints.is_empty());
+    assert_eq!(report.types, plan.types);
+    assert_eq!(options.bytes_style, BytesStyle::Base64);
+}
+
+#[test]
+fn should_return_errors_of_unreadable_files() {
+    let mut generator = Generator::new();
+
+    match generator.try_add_file(Path::new("does/not/exist.json")) {
+        Err(Error::Read { file, .. }) => assert_eq!(file, Path::new("does/not/exist.json")),
+        result => panic!("Expected a read error, got {:?}", result),
+    }
+
+    assert!(generator.generate_result().is_ok());
+}
+
+#[test]
+fn should_build_options_outside_of_the_crate() {
+    let options = GeneratorOptions::default()
+        .with_derives(["Eq"])
+        .with_map_type(MapType::HashMap)
+        .with_visibility(Visibility::Crate)
+        .with_serialize_none(true)
+        .with_header("use serde::{Serialize, Deserialize};")
+        .with_null_style(NullStyle::Unit)
+        .with_type_name("#", "Order")
+        .with_builder_threshold(Some(8))
+        .with_external_schema("common.json#", "common_models::");
+    let generator = Generator::new_with_options(options.clone());
+
+    assert_eq!(generator.options(), &options);
+    assert_eq!(options.type_names["#"], "Order");
+    assert_eq!(options.external_schemas["common.json"], "common_models");
+}
+
+#[test]
+fn should_emit_tables_of_generated_types() {
+    let mut generator = Generator::new();
+    generator.add_str(
+        "user.json",
+        r#"{"title": "User", "type": "object", "required": ["id"], "properties": {"id": {"type": "integer"}, "name": {"type": "string"}}}"#,
+    );
+    let types: Vec<GeneratedType> = generator.into();
+
+    let sql = create_tables(&types);
+
+    assert!(sql.starts_with("CREATE TABLE \"user\" ("));
+}